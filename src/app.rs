@@ -9,12 +9,17 @@ use crate::components::core_zmq_listener::{CoreZMQListener, ZMQMessage};
 use crate::context::AppContext;
 use crate::database::Database;
 use crate::logging::initialize_logger;
+use crate::model::qualified_identity::PrivateKeyTarget;
+use crate::ui::components::message_history::add_message_history_panel;
+use crate::ui::components::private_key_unlock::add_private_key_unlock_window;
 use crate::ui::contracts_documents::document_query_screen::DocumentQueryScreen;
 use crate::ui::dpns::dpns_contested_names_screen::{
     DPNSScreen, DPNSSubscreen, ScheduledVoteCastingStatus,
 };
 use crate::ui::identities::identities_screen::IdentitiesScreen;
+use crate::ui::identities::keys::key_info_screen::KeyInfoScreen;
 use crate::ui::network_chooser_screen::NetworkChooserScreen;
+use crate::ui::theme::ThemeMode;
 use crate::ui::tools::proof_log_screen::ProofLogScreen;
 use crate::ui::tools::proof_visualizer_screen::ProofVisualizerScreen;
 use crate::ui::tools::transition_visualizer_screen::TransitionVisualizerScreen;
@@ -22,6 +27,9 @@ use crate::ui::wallets::wallets_screen::WalletsBalancesScreen;
 use crate::ui::{MessageType, RootScreenType, Screen, ScreenLike, ScreenType};
 use dash_sdk::dpp::dashcore::Network;
 use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::dpp::identity::KeyID;
+use dash_sdk::dpp::platform_value::string_encoding::Encoding;
+use dash_sdk::platform::Identifier;
 use derive_more::From;
 use eframe::{egui, App};
 use std::collections::BTreeMap;
@@ -61,6 +69,7 @@ pub struct AppState {
     pub task_result_receiver: tokiompsc::Receiver<TaskResult>, // Channel receiver for receiving task results
     last_repaint: Instant, // Track the last time we requested a repaint
     last_scheduled_vote_check: Instant, // Last time we checked if there are scheduled masternode votes to cast
+    theme_mode: ThemeMode,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -118,6 +127,15 @@ pub enum AppAction {
     PopThenAddScreenToMainScreen(RootScreenType, Screen),
     BackendTask(BackendTask),
     BackendTasks(Vec<BackendTask>, BackendTasksExecutionMode),
+    /// Asks the visible screen where to go back to, see [`ScreenLike::pop_screen`].
+    Back,
+    /// Switches to the color scheme and saves it in the settings.
+    SetTheme(ThemeMode),
+    /// Opens the key info screen of a key of a local identity.
+    OpenKeyInfo {
+        identity_id: Identifier,
+        key_id: KeyID,
+    },
     Custom(String),
 }
 
@@ -143,6 +161,7 @@ impl AppState {
         db.initialize(&db_file_path).unwrap();
 
         let settings = db.get_settings().expect("expected to get settings");
+        let theme_mode = db.get_theme_mode().unwrap_or_default();
 
         let password_info = settings
             .clone()
@@ -196,7 +215,7 @@ impl AppState {
 
         let mut wallets_balances_screen = WalletsBalancesScreen::new(&mainnet_app_context);
 
-        let mut selected_main_screen = RootScreenType::RootScreenIdentities;
+        let mut selected_main_screen = RootScreenType::default();
 
         let mut chosen_network = Network::Dash;
 
@@ -313,6 +332,7 @@ impl AppState {
             task_result_receiver,
             last_repaint,
             last_scheduled_vote_check: Instant::now(),
+            theme_mode,
         }
     }
 
@@ -326,6 +346,14 @@ impl AppState {
         }
     }
 
+    /// Shows `message` on the visible screen and keeps it in the message history.
+    fn display_message(&mut self, message: &str, message_type: MessageType) {
+        self.current_app_context()
+            .record_message(message, message_type);
+        self.visible_screen_mut()
+            .display_message(message, message_type);
+    }
+
     // Handle the backend task and send the result through the channel
     pub fn handle_backend_task(&self, task: BackendTask) {
         let sender = self.task_result_sender.clone();
@@ -397,6 +425,41 @@ impl AppState {
         }
     }
 
+    fn key_info_screen(&self, identity_id: &Identifier, key_id: KeyID) -> Result<Screen, String> {
+        let app_context = self.current_app_context();
+        let identity = app_context
+            .db
+            .get_local_qualified_identity(
+                identity_id,
+                app_context,
+                &app_context.wallets.read().unwrap(),
+            )
+            .map_err(|e| format!("Error loading identity: {}", e))?
+            .ok_or_else(|| {
+                format!(
+                    "Identity {} is not a local identity",
+                    identity_id.to_string(Encoding::Base58)
+                )
+            })?;
+        let key = identity
+            .identity
+            .get_public_key_by_id(key_id)
+            .cloned()
+            .ok_or_else(|| format!("Identity has no key with id {}", key_id))?;
+        let private_key_data = identity
+            .private_keys
+            .get_cloned_private_key_data_and_wallet_info(&(
+                PrivateKeyTarget::PrivateKeyOnMainIdentity,
+                key_id,
+            ));
+        Ok(Screen::KeyInfoScreen(KeyInfoScreen::new(
+            identity,
+            key,
+            private_key_data,
+            app_context,
+        )))
+    }
+
     pub fn visible_screen_mut(&mut self) -> &mut Screen {
         if self.screen_stack.is_empty() {
             self.active_root_screen_mut()
@@ -469,8 +532,7 @@ impl App for AppState {
                         self.visible_screen_mut().refresh();
                     }
                     BackendTaskSuccessResult::Message(message) => {
-                        self.visible_screen_mut()
-                            .display_message(&message, MessageType::Success);
+                        self.display_message(&message, MessageType::Success);
                     }
                     BackendTaskSuccessResult::Documents(_) => {
                         self.visible_screen_mut().display_task_result(message);
@@ -484,11 +546,14 @@ impl App for AppState {
                     BackendTaskSuccessResult::DPNSVoteResults(_) => {
                         self.visible_screen_mut().display_task_result(message);
                     }
+                    BackendTaskSuccessResult::ProposedDPNSVotes(_) => {
+                        self.visible_screen_mut().display_task_result(message);
+                    }
                     BackendTaskSuccessResult::CastScheduledVote(vote) => {
                         let _ = self
                             .current_app_context()
                             .mark_vote_executed(vote.voter_id.as_slice(), vote.contested_name);
-                        self.visible_screen_mut().display_message(
+                        self.display_message(
                             "Successfully cast scheduled vote",
                             MessageType::Success,
                         );
@@ -509,10 +574,21 @@ impl App for AppState {
                     BackendTaskSuccessResult::PageDocuments(_, _) => {
                         self.visible_screen_mut().display_task_result(message);
                     }
+                    BackendTaskSuccessResult::IdentityBalanceReconciliation(_) => {
+                        self.visible_screen_mut().display_task_result(message);
+                    }
+                    BackendTaskSuccessResult::BulkToppedUpIdentities(_) => {
+                        self.visible_screen_mut().display_task_result(message);
+                    }
+                    BackendTaskSuccessResult::IdentityNonce(_, _) => {
+                        self.visible_screen_mut().display_task_result(message);
+                    }
+                    BackendTaskSuccessResult::IdentityChangesToConfirm(_, _) => {
+                        self.visible_screen_mut().display_task_result(message);
+                    }
                 },
                 TaskResult::Error(message) => {
-                    self.visible_screen_mut()
-                        .display_message(&message, MessageType::Error);
+                    self.display_message(&message, MessageType::Error);
                 }
                 TaskResult::Refresh => {
                     self.visible_screen_mut().refresh();
@@ -630,10 +706,31 @@ impl App for AppState {
             }
         }
 
+        self.theme_mode.apply(ctx);
+
         // Use a timer to repaint the UI every 0.05 seconds
         ctx.request_repaint_after(std::time::Duration::from_millis(50));
 
-        let action = self.visible_screen_mut().ui(ctx);
+        add_message_history_panel(ctx, self.current_app_context());
+
+        let other_app_context = match self.chosen_network {
+            Network::Dash => self.testnet_app_context.as_deref(),
+            _ => Some(self.mainnet_app_context.as_ref()),
+        };
+        if add_private_key_unlock_window(ctx, self.current_app_context(), other_app_context) {
+            // Screens loaded their identities while the keys were still encrypted
+            for screen in self.main_screens.values_mut() {
+                screen.refresh();
+            }
+            for screen in self.screen_stack.iter_mut() {
+                screen.refresh();
+            }
+        }
+
+        let mut action = self.visible_screen_mut().ui(ctx);
+        if action == AppAction::Back {
+            action = self.visible_screen_mut().pop_screen();
+        }
 
         match action {
             AppAction::AddScreen(screen) => self.screen_stack.push(screen),
@@ -687,6 +784,13 @@ impl App for AppState {
                     .update_settings(RootScreenType::RootScreenNetworkChooser)
                     .ok();
             }
+            AppAction::SetTheme(theme_mode) => {
+                self.theme_mode = theme_mode;
+                self.current_app_context()
+                    .db
+                    .update_theme_mode(theme_mode)
+                    .ok();
+            }
             AppAction::PopThenAddScreenToMainScreen(root_screen_type, screen) => {
                 self.screen_stack = vec![screen];
                 self.selected_main_screen = root_screen_type;
@@ -695,7 +799,14 @@ impl App for AppState {
                     .update_settings(root_screen_type)
                     .ok();
             }
-            AppAction::Custom(_) => {}
+            AppAction::OpenKeyInfo {
+                identity_id,
+                key_id,
+            } => match self.key_info_screen(&identity_id, key_id) {
+                Ok(screen) => self.screen_stack.push(screen),
+                Err(e) => self.display_message(&e, MessageType::Error),
+            },
+            AppAction::Back | AppAction::Custom(_) => {}
         }
     }
 }
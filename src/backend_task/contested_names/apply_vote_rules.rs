@@ -0,0 +1,183 @@
+use crate::app::TaskResult;
+use crate::backend_task::contested_names::ProposedDPNSVote;
+use crate::backend_task::BackendTaskSuccessResult;
+use crate::context::AppContext;
+use crate::model::qualified_identity::QualifiedIdentity;
+use crate::model::vote_rule::{AutoVoteLogEntry, AutoVoteOutcome};
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::platform::Identifier;
+use dash_sdk::Sdk;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+impl AppContext {
+    /// Evaluates the vote rules against the ongoing contests. Matching votes are cast right
+    /// away in fully automatic mode, otherwise they are sent to the UI for confirmation.
+    pub(super) async fn apply_dpns_vote_rules(
+        self: &Arc<Self>,
+        sdk: &Sdk,
+        sender: mpsc::Sender<TaskResult>,
+    ) -> Result<(), String> {
+        let rules = self
+            .db
+            .get_vote_rules(self)
+            .map_err(|e| format!("Error loading vote rules: {}", e))?;
+        if !rules.iter().any(|rule| rule.enabled) {
+            return Ok(());
+        }
+
+        let voters = self
+            .db
+            .get_local_voting_identities(self)
+            .map_err(|e| format!("Error loading voting identities: {}", e))?;
+        if voters.is_empty() {
+            return Ok(());
+        }
+
+        let settled_names = self
+            .db
+            .get_settled_auto_vote_names(self)
+            .map_err(|e| format!("Error loading auto-vote log: {}", e))?;
+        let scheduled_votes = self.get_scheduled_votes().unwrap_or_default();
+
+        let proposals: Vec<ProposedDPNSVote> = self
+            .ongoing_contested_names()
+            .map_err(|e| format!("Error loading contested names: {}", e))?
+            .iter()
+            .filter(|name| !settled_names.contains(&name.normalized_contested_name))
+            .filter(|name| {
+                !scheduled_votes
+                    .iter()
+                    .any(|vote| vote.contested_name == name.normalized_contested_name)
+            })
+            .filter_map(|name| {
+                rules.iter().find_map(|rule| {
+                    rule.vote_for(name).map(|choice| ProposedDPNSVote {
+                        contested_name: name.normalized_contested_name.clone(),
+                        choice,
+                        rule_pattern: rule.name_pattern.clone(),
+                    })
+                })
+            })
+            .collect();
+
+        if proposals.is_empty() {
+            return Ok(());
+        }
+
+        let fully_automatic = self
+            .db
+            .get_auto_vote_fully_automatic()
+            .map_err(|e| format!("Error loading auto-vote setting: {}", e))?;
+
+        let task_result = if fully_automatic {
+            self.cast_proposed_dpns_votes(&proposals, &voters, sdk, sender.clone())
+                .await
+                .into()
+        } else {
+            TaskResult::Success(BackendTaskSuccessResult::ProposedDPNSVotes(proposals))
+        };
+
+        sender
+            .send(task_result)
+            .await
+            .map_err(|e| format!("Error sending vote rule results: {}", e))
+    }
+
+    /// Casts votes produced by the vote rules with every voter and records each outcome in the
+    /// auto-vote audit trail.
+    pub(super) async fn cast_proposed_dpns_votes(
+        self: &Arc<Self>,
+        proposals: &[ProposedDPNSVote],
+        voters: &Vec<QualifiedIdentity>,
+        sdk: &Sdk,
+        sender: mpsc::Sender<TaskResult>,
+    ) -> Result<BackendTaskSuccessResult, String> {
+        let mut cast = 0;
+        let mut errors = Vec::new();
+
+        for proposal in proposals {
+            let entries: Vec<(Option<Identifier>, Option<String>)> = match self
+                .vote_on_dpns_name(
+                    &proposal.contested_name,
+                    proposal.choice,
+                    voters,
+                    sdk,
+                    sender.clone(),
+                )
+                .await
+            {
+                Ok(BackendTaskSuccessResult::DPNSVoteResults(results)) => results
+                    .into_iter()
+                    .zip(voters.iter())
+                    .map(|((_, _, result), voter)| (Some(voter.identity.id()), result.err()))
+                    .collect(),
+                Ok(_) => vec![],
+                Err(e) => vec![(None, Some(e))],
+            };
+
+            for (voter_id, error) in entries {
+                let outcome = match &error {
+                    None => {
+                        cast += 1;
+                        AutoVoteOutcome::Cast
+                    }
+                    Some(e) => {
+                        errors.push(format!("{}: {}", proposal.contested_name, e));
+                        AutoVoteOutcome::Failed
+                    }
+                };
+                self.db
+                    .insert_auto_vote_log_entry(
+                        self,
+                        &AutoVoteLogEntry::now(
+                            &proposal.contested_name,
+                            proposal.choice,
+                            &proposal.rule_pattern,
+                            voter_id,
+                            outcome,
+                            error,
+                        ),
+                    )
+                    .map_err(|e| format!("Error writing auto-vote log: {}", e))?;
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(BackendTaskSuccessResult::Message(format!(
+                "Vote rules: cast {} votes",
+                cast
+            )))
+        } else {
+            Err(format!(
+                "Vote rules: cast {} votes, {} failed:\n\n{}",
+                cast,
+                errors.len(),
+                errors.join("\n")
+            ))
+        }
+    }
+
+    /// Records proposals the user declined so they are not proposed again.
+    pub(super) fn dismiss_proposed_dpns_votes(
+        &self,
+        proposals: &[ProposedDPNSVote],
+    ) -> Result<BackendTaskSuccessResult, String> {
+        for proposal in proposals {
+            self.db
+                .insert_auto_vote_log_entry(
+                    self,
+                    &AutoVoteLogEntry::now(
+                        &proposal.contested_name,
+                        proposal.choice,
+                        &proposal.rule_pattern,
+                        None,
+                        AutoVoteOutcome::Dismissed,
+                        None,
+                    ),
+                )
+                .map_err(|e| format!("Error writing auto-vote log: {}", e))?;
+        }
+        Ok(BackendTaskSuccessResult::Refresh)
+    }
+}
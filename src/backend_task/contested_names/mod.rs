@@ -1,3 +1,4 @@
+mod apply_vote_rules;
 mod query_dpns_contested_resources;
 mod query_dpns_vote_contenders;
 mod query_ending_times;
@@ -23,6 +24,8 @@ pub(crate) enum ContestedResourceTask {
     ClearAllScheduledVotes,
     ClearExecutedScheduledVotes,
     DeleteScheduledVote(Identifier, String),
+    CastProposedDPNSVotes(Vec<ProposedDPNSVote>, Vec<QualifiedIdentity>),
+    DismissProposedDPNSVotes(Vec<ProposedDPNSVote>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -34,6 +37,14 @@ pub struct ScheduledDPNSVote {
     pub executed_successfully: bool,
 }
 
+/// A vote produced by a vote rule that still needs the user's confirmation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProposedDPNSVote {
+    pub contested_name: String,
+    pub choice: ResourceVoteChoice,
+    pub rule_pattern: String,
+}
+
 impl AppContext {
     pub async fn run_contested_resource_task(
         self: &Arc<Self>,
@@ -116,6 +127,13 @@ impl AppContext {
                 .delete_scheduled_vote(voter_id.as_slice(), contested_name)
                 .map(|_| BackendTaskSuccessResult::Refresh)
                 .map_err(|e| format!("Error clearing scheduled vote: {}", e.to_string())),
+            ContestedResourceTask::CastProposedDPNSVotes(proposals, voters) => {
+                self.cast_proposed_dpns_votes(proposals, voters, sdk, sender)
+                    .await
+            }
+            ContestedResourceTask::DismissProposedDPNSVotes(proposals) => {
+                self.dismiss_proposed_dpns_votes(proposals)
+            }
         }
     }
 }
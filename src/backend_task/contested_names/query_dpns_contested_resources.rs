@@ -242,6 +242,15 @@ impl AppContext {
             }
         }
 
+        // Contests and contenders are now up to date, see whether any vote rule applies
+        if let Err(e) = self.apply_dpns_vote_rules(sdk, sender.clone()).await {
+            tracing::error!("Error applying vote rules: {}", e);
+            sender
+                .send(TaskResult::Error(e))
+                .await
+                .expect("expected to send error");
+        }
+
         sender
             .send(TaskResult::Success(BackendTaskSuccessResult::Message(
                 "Successfully refreshed DPNS contests".to_string(),
@@ -0,0 +1,39 @@
+use crate::app::TaskResult;
+use crate::backend_task::identity::{BulkTopUpOutcome, IdentityTopUpInfo};
+use crate::backend_task::BackendTaskSuccessResult;
+use crate::context::AppContext;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use tokio::sync::mpsc;
+
+impl AppContext {
+    /// Tops up each identity one after the other so that every asset lock is built from the
+    /// wallet's remaining UTXOs. A failed top up is recorded and the batch moves on.
+    pub(super) async fn bulk_top_up_identities(
+        &self,
+        top_ups: Vec<IdentityTopUpInfo>,
+        sender: mpsc::Sender<TaskResult>,
+    ) -> Result<BackendTaskSuccessResult, String> {
+        let mut outcomes = Vec::with_capacity(top_ups.len());
+
+        for top_up_info in top_ups {
+            let identity_id = top_up_info.qualified_identity.identity.id();
+            let display_name = top_up_info.qualified_identity.display_short_string();
+
+            let result = match self.top_up_identity(top_up_info, sender.clone()).await {
+                Ok(BackendTaskSuccessResult::ToppedUpIdentity(qualified_identity)) => {
+                    Ok(qualified_identity.identity.balance())
+                }
+                Ok(_) => Err("Unexpected result while topping up identity".to_string()),
+                Err(e) => Err(e),
+            };
+
+            outcomes.push(BulkTopUpOutcome {
+                identity_id,
+                display_name,
+                result,
+            });
+        }
+
+        Ok(BackendTaskSuccessResult::BulkToppedUpIdentities(outcomes))
+    }
+}
@@ -0,0 +1,30 @@
+use crate::context::AppContext;
+use crate::model::qualified_identity::QualifiedIdentity;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::dpp::platform_value::string_encoding::Encoding;
+use dash_sdk::platform::Fetch;
+use dash_sdk::query_types::IdentityNonceFetcher;
+use dash_sdk::Sdk;
+
+use super::BackendTaskSuccessResult;
+
+impl AppContext {
+    pub(super) async fn fetch_identity_nonce(
+        &self,
+        sdk: &Sdk,
+        qualified_identity: QualifiedIdentity,
+    ) -> Result<BackendTaskSuccessResult, String> {
+        let identity_id = qualified_identity.identity.id();
+        let IdentityNonceFetcher(nonce) = IdentityNonceFetcher::fetch(sdk, identity_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| {
+                format!(
+                    "No nonce found for identity {}",
+                    identity_id.to_string(Encoding::Base58)
+                )
+            })?;
+
+        Ok(BackendTaskSuccessResult::IdentityNonce(identity_id, nonce))
+    }
+}
@@ -301,6 +301,9 @@ impl AppContext {
         // Insert qualified identity into the database
         self.insert_local_qualified_identity(&qualified_identity, None)
             .map_err(|e| format!("Database error: {}", e))?;
+        self.db
+            .mark_identity_synced(&qualified_identity.identity.id(), self)
+            .map_err(|e| format!("Database error: {}", e))?;
 
         Ok(BackendTaskSuccessResult::Message(
             "Successfully loaded identity".to_string(),
@@ -1,6 +1,9 @@
 mod add_key_to_identity;
+mod bulk_top_up_identities;
+mod fetch_identity_nonce;
 mod load_identity;
 mod load_identity_from_wallet;
+mod reconcile_identity_balances;
 mod refresh_identity;
 mod refresh_loaded_identities_dpns_names;
 mod register_dpns_name;
@@ -245,18 +248,45 @@ pub struct RegisterDpnsNameInput {
     pub name_input: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentityBalanceReconciliation {
+    pub identity_id: Identifier,
+    pub display_name: String,
+    pub stored_balance: Credits,
+    /// The balance reported by Platform, or the reason it could not be fetched
+    pub platform_balance: Result<Credits, String>,
+}
+
+/// The outcome of a single identity's top up within a bulk top up
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkTopUpOutcome {
+    pub identity_id: Identifier,
+    pub display_name: String,
+    /// The identity's new balance, or the reason the top up failed
+    pub result: Result<Credits, String>,
+}
+
+impl IdentityBalanceReconciliation {
+    pub fn is_in_sync(&self) -> bool {
+        matches!(self.platform_balance, Ok(balance) if balance == self.stored_balance)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum IdentityTask {
     LoadIdentity(IdentityInputToLoad),
     SearchIdentityFromWallet(WalletArcRef, IdentityIndex),
     RegisterIdentity(IdentityRegistrationInfo),
     TopUpIdentity(IdentityTopUpInfo),
+    BulkTopUpIdentities(Vec<IdentityTopUpInfo>),
     AddKeyToIdentity(QualifiedIdentity, QualifiedIdentityPublicKey, [u8; 32]),
     WithdrawFromIdentity(QualifiedIdentity, Option<Address>, Credits, Option<KeyID>),
     Transfer(QualifiedIdentity, Identifier, Credits, Option<KeyID>),
     RegisterDpnsName(RegisterDpnsNameInput),
     RefreshIdentity(QualifiedIdentity),
     RefreshLoadedIdentitiesOwnedDPNSNames,
+    ReconcileIdentityBalances(Vec<QualifiedIdentity>),
+    FetchIdentityNonce(QualifiedIdentity),
 }
 
 fn verify_key_input(
@@ -469,9 +499,19 @@ impl AppContext {
             IdentityTask::TopUpIdentity(top_up_info) => {
                 self.top_up_identity(top_up_info, sender).await
             }
+            IdentityTask::BulkTopUpIdentities(top_ups) => {
+                self.bulk_top_up_identities(top_ups, sender).await
+            }
             IdentityTask::RefreshLoadedIdentitiesOwnedDPNSNames => {
                 self.refresh_loaded_identities_dpns_names(sender).await
             }
+            IdentityTask::FetchIdentityNonce(qualified_identity) => {
+                self.fetch_identity_nonce(sdk, qualified_identity).await
+            }
+            IdentityTask::ReconcileIdentityBalances(qualified_identities) => {
+                self.reconcile_identity_balances(sdk, qualified_identities)
+                    .await
+            }
         }
     }
 }
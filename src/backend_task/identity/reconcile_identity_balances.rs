@@ -0,0 +1,41 @@
+use crate::context::AppContext;
+use crate::model::qualified_identity::QualifiedIdentity;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::platform::Fetch;
+use dash_sdk::query_types::IdentityBalance;
+use dash_sdk::Sdk;
+
+use super::{BackendTaskSuccessResult, IdentityBalanceReconciliation};
+
+impl AppContext {
+    /// Fetches the live balance of each identity from Platform and compares it with the
+    /// balance stored locally. Failures for individual identities are reported per identity
+    /// rather than aborting the whole batch.
+    pub(super) async fn reconcile_identity_balances(
+        &self,
+        sdk: &Sdk,
+        qualified_identities: Vec<QualifiedIdentity>,
+    ) -> Result<BackendTaskSuccessResult, String> {
+        let mut reconciliations = Vec::with_capacity(qualified_identities.len());
+
+        for qualified_identity in qualified_identities {
+            let identity_id = qualified_identity.identity.id();
+            let platform_balance = match IdentityBalance::fetch(sdk, identity_id).await {
+                Ok(Some(balance)) => Ok(balance),
+                Ok(None) => Err("Identity not found in Platform state".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+
+            reconciliations.push(IdentityBalanceReconciliation {
+                identity_id,
+                display_name: qualified_identity.display_short_string(),
+                stored_balance: qualified_identity.identity.balance(),
+                platform_balance,
+            });
+        }
+
+        Ok(BackendTaskSuccessResult::IdentityBalanceReconciliation(
+            reconciliations,
+        ))
+    }
+}
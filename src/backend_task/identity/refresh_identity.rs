@@ -1,5 +1,6 @@
 use crate::app::TaskResult;
 use crate::context::AppContext;
+use crate::model::identity_changes::IdentityChanges;
 use crate::model::qualified_identity::QualifiedIdentity;
 use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
 use dash_sdk::dpp::platform_value::string_encoding::Encoding;
@@ -49,11 +50,24 @@ impl AppContext {
             local_qualified_identities.remove(outdated_identity_index);
 
         // Update the identity
+        let changes =
+            IdentityChanges::between(&qualified_identity_to_update.identity, &refreshed_identity);
         qualified_identity_to_update.identity = refreshed_identity;
 
+        // Keys are not expected to change behind our back, let the user review them first
+        if changes.has_key_changes() {
+            return Ok(BackendTaskSuccessResult::IdentityChangesToConfirm(
+                qualified_identity_to_update,
+                changes,
+            ));
+        }
+
         // Insert the updated identity into local state
         self.insert_local_qualified_identity(&qualified_identity_to_update, None)
             .map_err(|e| e.to_string())?;
+        self.db
+            .mark_identity_synced(&qualified_identity_to_update.identity.id(), self)
+            .map_err(|e| e.to_string())?;
 
         // Send refresh message to refresh the Identities Screen
         sender
@@ -67,7 +67,7 @@ impl AppContext {
             qualified_identity.dpns_names = owned_dpns_names;
 
             // Update qualified identity in the database
-            self.update_local_qualified_identity(&qualified_identity)
+            self.save_refreshed_local_qualified_identity(&qualified_identity)
                 .map_err(|e| format!("Error refreshing owned DPNS names: Database error: {}", e))?;
         }
 
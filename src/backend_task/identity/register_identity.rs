@@ -217,7 +217,7 @@ impl AppContext {
                     });
                     for utxo in used_utxos.keys() {
                         self.db
-                            .drop_utxo(utxo, &self.network.to_string())
+                            .drop_utxo(utxo, self.network)
                             .map_err(|e| e.to_string())?;
                     }
                 }
@@ -281,7 +281,7 @@ impl AppContext {
                         !utxo_map.is_empty()
                     });
                     self.db
-                        .drop_utxo(&utxo, &self.network.to_string())
+                        .drop_utxo(&utxo, self.network)
                         .map_err(|e| e.to_string())?;
                 }
 
@@ -3,10 +3,14 @@ use crate::backend_task::contested_names::ContestedResourceTask;
 use crate::backend_task::contract::ContractTask;
 use crate::backend_task::core::{CoreItem, CoreTask};
 use crate::backend_task::document::DocumentTask;
-use crate::backend_task::identity::IdentityTask;
+use crate::backend_task::identity::{
+    BulkTopUpOutcome, IdentityBalanceReconciliation, IdentityTask,
+};
 use crate::context::AppContext;
+use crate::database::READ_ONLY_MODE_ERROR;
+use crate::model::identity_changes::IdentityChanges;
 use crate::model::qualified_identity::QualifiedIdentity;
-use contested_names::ScheduledDPNSVote;
+use contested_names::{ProposedDPNSVote, ScheduledDPNSVote};
 use dash_sdk::dpp::prelude::DataContract;
 use dash_sdk::dpp::state_transition::StateTransition;
 use dash_sdk::dpp::voting::vote_choices::resource_vote_choice::ResourceVoteChoice;
@@ -24,6 +28,7 @@ pub mod contract;
 pub mod core;
 pub mod document;
 pub mod identity;
+pub mod platform_status;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum BackendTask {
@@ -33,6 +38,22 @@ pub(crate) enum BackendTask {
     ContestedResourceTask(ContestedResourceTask),
     CoreTask(CoreTask),
     BroadcastStateTransition(StateTransition),
+    TestPlatformConnection,
+}
+
+impl BackendTask {
+    /// Whether the task only reads, so it may run in read-only mode. Tasks that broadcast or
+    /// store what they fetch are not.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            BackendTask::DocumentTask(_)
+                | BackendTask::TestPlatformConnection
+                | BackendTask::IdentityTask(IdentityTask::FetchIdentityNonce(_))
+                | BackendTask::CoreTask(CoreTask::GetBestChainLock)
+                | BackendTask::CoreTask(CoreTask::GetBestChainLocks)
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -47,9 +68,15 @@ pub(crate) enum BackendTaskSuccessResult {
     SuccessfulVotes(Vec<Vote>),
     DPNSVoteResults(Vec<(String, ResourceVoteChoice, Result<(), String>)>),
     CastScheduledVote(ScheduledDPNSVote),
+    ProposedDPNSVotes(Vec<ProposedDPNSVote>),
     FetchedContract(DataContract),
     FetchedContracts(Vec<Option<DataContract>>),
     PageDocuments(IndexMap<Identifier, Option<Document>>, Option<Start>),
+    IdentityBalanceReconciliation(Vec<IdentityBalanceReconciliation>),
+    BulkToppedUpIdentities(Vec<BulkTopUpOutcome>),
+    IdentityNonce(Identifier, u64),
+    /// A refreshed identity whose keys changed on Platform, not saved until the user confirms
+    IdentityChangesToConfirm(QualifiedIdentity, IdentityChanges),
 }
 
 impl BackendTaskSuccessResult {}
@@ -95,6 +122,9 @@ impl AppContext {
         task: BackendTask,
         sender: mpsc::Sender<TaskResult>,
     ) -> Result<BackendTaskSuccessResult, String> {
+        if self.is_read_only() && !task.is_read_only() {
+            return Err(READ_ONLY_MODE_ERROR.to_string());
+        }
         let sdk = self.sdk.clone();
         match task {
             BackendTask::ContractTask(contract_task) => {
@@ -115,6 +145,7 @@ impl AppContext {
                 self.broadcast_state_transition(state_transition, &sdk)
                     .await
             }
+            BackendTask::TestPlatformConnection => self.test_platform_connection().await,
         }
     }
 }
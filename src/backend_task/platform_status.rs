@@ -0,0 +1,79 @@
+use crate::backend_task::BackendTaskSuccessResult;
+use crate::context::AppContext;
+use chrono::{DateTime, Utc};
+use dash_sdk::dpp::block::extended_epoch_info::ExtendedEpochInfo;
+use dash_sdk::platform::Fetch;
+use std::time::{Duration, Instant};
+
+/// How long a connection test waits for Platform before reporting a timeout.
+pub const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlatformConnection {
+    Online {
+        latency: Duration,
+        platform_height: u64,
+        core_height: u32,
+    },
+    /// Platform did not answer within [`CONNECTION_TEST_TIMEOUT`]
+    TimedOut,
+    /// The request failed, for instance because the connection was refused
+    Failed(String),
+}
+
+/// Outcome of the last connection test of a network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlatformConnectionStatus {
+    pub checked_at: DateTime<Utc>,
+    pub connection: PlatformConnection,
+}
+
+impl PlatformConnectionStatus {
+    pub fn is_online(&self) -> bool {
+        matches!(self.connection, PlatformConnection::Online { .. })
+    }
+}
+
+impl AppContext {
+    /// Fetches the current epoch from Platform to check it can be reached. The result is kept
+    /// in `platform_connection_status`.
+    pub async fn test_platform_connection(&self) -> Result<BackendTaskSuccessResult, String> {
+        let started = Instant::now();
+        let fetched = tokio::time::timeout(
+            CONNECTION_TEST_TIMEOUT,
+            ExtendedEpochInfo::fetch_with_metadata(&self.sdk, 0, None),
+        )
+        .await;
+        let connection = match fetched {
+            Ok(Ok((_, metadata))) => PlatformConnection::Online {
+                latency: started.elapsed(),
+                platform_height: metadata.height,
+                core_height: metadata.core_chain_locked_height,
+            },
+            Ok(Err(e)) => PlatformConnection::Failed(e.to_string()),
+            Err(_) => PlatformConnection::TimedOut,
+        };
+        *self.platform_connection_status.lock().unwrap() = Some(PlatformConnectionStatus {
+            checked_at: Utc::now(),
+            connection: connection.clone(),
+        });
+
+        match connection {
+            PlatformConnection::Online {
+                latency,
+                platform_height,
+                core_height,
+            } => Ok(BackendTaskSuccessResult::Message(format!(
+                "Connected to Platform in {} ms, platform height {}, core height {}",
+                latency.as_millis(),
+                platform_height,
+                core_height
+            ))),
+            PlatformConnection::TimedOut => Err(format!(
+                "Platform did not answer within {} seconds",
+                CONNECTION_TEST_TIMEOUT.as_secs()
+            )),
+            PlatformConnection::Failed(e) => Err(format!("Could not reach Platform: {}", e)),
+        }
+    }
+}
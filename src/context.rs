@@ -1,16 +1,22 @@
 use crate::app_dir::{core_cookie_path, core_user_data_dir_path};
 use crate::backend_task::contested_names::ScheduledDPNSVote;
+use crate::backend_task::platform_status::PlatformConnectionStatus;
 use crate::components::core_zmq_listener::ZMQConnectionEvent;
 use crate::config::{Config, NetworkConfig};
 use crate::context_provider::Provider;
-use crate::database::Database;
+use crate::database::{network_db_name, Database, SetAliasError};
 use crate::model::contested_name::ContestedName;
 use crate::model::password_info::PasswordInfo;
 use crate::model::qualified_contract::QualifiedContract;
 use crate::model::qualified_identity::{DPNSNameInfo, QualifiedIdentity};
+use crate::model::wallet::encryption::{
+    decrypt_message, derive_password_key, encrypt_message, DASH_SECRET_MESSAGE,
+};
 use crate::model::wallet::{Wallet, WalletSeedHash};
 use crate::sdk_wrapper::initialize_sdk;
-use crate::ui::RootScreenType;
+use crate::ui::components::message_history::MessageHistory;
+use crate::ui::components::private_key_unlock::PrivateKeyUnlockPrompt;
+use crate::ui::{MessageType, RootScreenType};
 use crossbeam_channel::{Receiver, Sender};
 use dash_sdk::dashcore_rpc::dashcore::{InstantLock, Transaction};
 use dash_sdk::dashcore_rpc::{Auth, Client};
@@ -48,8 +54,17 @@ pub struct AppContext {
     pub(crate) has_wallet: AtomicBool,
     pub(crate) wallets: RwLock<BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>>,
     pub(crate) password_info: Option<PasswordInfo>,
+    /// Key derived from the main password, used to encrypt identity private keys at rest. Set
+    /// once the user unlocks their keys for this session.
+    pub(crate) private_key_encryption_key: RwLock<Option<[u8; 32]>>,
+    /// Main password prompt shown while the identity private keys are locked
+    pub(crate) private_key_unlock_prompt: Mutex<PrivateKeyUnlockPrompt>,
     pub(crate) transactions_waiting_for_finality: Mutex<BTreeMap<Txid, Option<AssetLockProof>>>,
     pub(crate) platform_version: &'static PlatformVersion,
+    /// Result of the last connection test, `None` until one ran
+    pub(crate) platform_connection_status: Mutex<Option<PlatformConnectionStatus>>,
+    /// Messages shown to the user on this network, listed in the message history panel
+    pub(crate) message_history: Mutex<MessageHistory>,
 }
 
 impl AppContext {
@@ -108,8 +123,9 @@ impl AppContext {
             }
         }.expect("Failed to create CoreClient");
 
+        // The identity keys can't be unlocked before the context exists
         let wallets: BTreeMap<_, _> = db
-            .get_wallets(&network)
+            .get_wallets(&network, None)
             .expect("expected to get wallets")
             .into_iter()
             .map(|w| (w.seed_hash(), Arc::new(RwLock::new(w))))
@@ -130,9 +146,13 @@ impl AppContext {
             has_wallet: (!wallets.is_empty()).into(),
             wallets: RwLock::new(wallets),
             password_info,
+            private_key_encryption_key: RwLock::new(None),
+            private_key_unlock_prompt: Mutex::new(PrivateKeyUnlockPrompt::default()),
             transactions_waiting_for_finality: Mutex::new(BTreeMap::new()),
             platform_version: PlatformVersion::latest(),
             zmq_connection_status: Mutex::new(ZMQConnectionEvent::Disconnected),
+            platform_connection_status: Mutex::new(None),
+            message_history: Mutex::new(MessageHistory::default()),
         };
 
         let app_context = Arc::new(app_context);
@@ -142,13 +162,7 @@ impl AppContext {
     }
 
     pub(crate) fn network_string(&self) -> String {
-        match self.network {
-            Network::Dash => "dash".to_string(),
-            Network::Testnet => "testnet".to_string(),
-            Network::Devnet => format!("devnet:{}", self.devnet_name.clone().unwrap_or_default()),
-            Network::Regtest => "regtest".to_string(),
-            _ => "unknown".to_string(),
-        }
+        network_db_name(self.network, self.devnet_name.as_deref())
     }
 
     pub fn insert_local_identity(&self, identity: &Identity) -> Result<()> {
@@ -169,6 +183,14 @@ impl AppContext {
         )
     }
 
+    /// Inserts many local qualified identities into the database in one transaction
+    pub fn insert_local_qualified_identities(
+        &self,
+        identities: &[(QualifiedIdentity, Option<(&[u8], u32)>)],
+    ) -> Result<()> {
+        self.db.insert_local_qualified_identities(identities, self)
+    }
+
     /// Updates a local qualified identity in the database
     pub fn update_local_qualified_identity(
         &self,
@@ -178,8 +200,40 @@ impl AppContext {
             .update_local_qualified_identity(qualified_identity, self)
     }
 
+    /// Saves what was fetched from Platform for a local qualified identity, without recording
+    /// it in the audit log
+    pub fn save_refreshed_local_qualified_identity(
+        &self,
+        qualified_identity: &QualifiedIdentity,
+    ) -> Result<()> {
+        self.db
+            .save_refreshed_local_qualified_identity(qualified_identity, self)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.db.is_read_only()
+    }
+
+    /// Fails with an error explaining the app is in read-only mode, if it is.
+    pub fn ensure_writable(&self) -> Result<()> {
+        self.db.ensure_writable()
+    }
+
+    /// Keeps `message` in the message history.
+    pub fn record_message(&self, message: &str, message_type: MessageType) {
+        self.message_history
+            .lock()
+            .unwrap()
+            .push(message, message_type);
+    }
+
     /// Sets the alias for an identity
-    pub fn set_alias(&self, identifier: &Identifier, new_alias: Option<&str>) -> Result<()> {
+    pub fn set_alias(
+        &self,
+        identifier: &Identifier,
+        new_alias: Option<&str>,
+    ) -> std::result::Result<(), SetAliasError> {
+        self.ensure_writable()?;
         self.db.set_alias(identifier, new_alias)
     }
 
@@ -198,12 +252,128 @@ impl AppContext {
         )
     }
 
+    pub fn private_key_encryption_key(&self) -> Option<[u8; 32]> {
+        *self.private_key_encryption_key.read().unwrap()
+    }
+
+    /// Whether a main password is set, which identity private keys are then encrypted with.
+    pub fn has_main_password(&self) -> bool {
+        matches!(self.db.get_settings(), Ok(Some((_, _, Some(_), _, _))))
+    }
+
+    /// Shows the main password prompt again, even if the user put it off.
+    pub fn request_private_key_unlock(&self) {
+        self.private_key_unlock_prompt.lock().unwrap().dismissed = false;
+    }
+
+    /// Makes `password` the main password, unless one is set already, and unlocks the identity
+    /// private keys with it. Replacing the main password would leave the keys encrypted with the
+    /// previous one unreadable.
+    pub fn set_main_password(&self, password: &str) -> std::result::Result<(), String> {
+        if self.has_main_password() {
+            return Ok(());
+        }
+        let (encrypted_message, salt, nonce) = encrypt_message(DASH_SECRET_MESSAGE, password)?;
+        self.db
+            .update_main_password(&salt, &nonce, &encrypted_message)
+            .map_err(|e| e.to_string())?;
+        self.unlock_private_keys(password)
+    }
+
+    /// Serializes `qualified_identity` for the identity table, with its clear private keys
+    /// encrypted. While the keys are locked, an identity with clear keys is refused and the
+    /// unlock prompt shown again, so no key is written in the clear. Without a main password
+    /// there is nothing to encrypt with and the keys are stored as they are.
+    pub fn identity_stored_bytes(
+        &self,
+        qualified_identity: &QualifiedIdentity,
+    ) -> std::result::Result<Vec<u8>, String> {
+        let encryption_key = self.private_key_encryption_key();
+        if encryption_key.is_none()
+            && qualified_identity.private_keys.has_clear_keys()
+            && self.has_main_password()
+        {
+            self.request_private_key_unlock();
+            return Err(
+                "Identity keys are locked, unlock them with your main password to save this identity"
+                    .to_string(),
+            );
+        }
+        qualified_identity.to_stored_bytes(encryption_key.as_ref())
+    }
+
+    /// Forgets the key derived from the main password, so the identity keys have to be unlocked
+    /// again. Needed once the main password may have been replaced, as by restoring an archive.
+    pub fn lock_private_keys(&self) {
+        *self.private_key_encryption_key.write().unwrap() = None;
+        self.request_private_key_unlock();
+    }
+
+    /// Encrypts the identity private keys still stored in the clear, if the keys are unlocked
+    /// and the app is not in read-only mode.
+    pub fn encrypt_stored_private_keys(&self) -> std::result::Result<(), String> {
+        let Some(encryption_key) = self.private_key_encryption_key() else {
+            return Ok(());
+        };
+        if self.is_read_only() {
+            return Ok(());
+        }
+        self.db
+            .encrypt_identity_private_keys(&encryption_key)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Checks `password` against the main password and derives the key used to encrypt identity
+    /// private keys. The keys still stored in the clear are then encrypted at rest.
+    pub fn unlock_private_keys(&self, password: &str) -> std::result::Result<(), String> {
+        let password_info = self
+            .db
+            .get_settings()
+            .map_err(|e| e.to_string())?
+            .and_then(|(_, _, password_info, _, _)| password_info)
+            .ok_or("Set a main password by creating or importing a wallet first".to_string())?;
+
+        let check = decrypt_message(
+            &password_info.password_checker,
+            password,
+            &password_info.salt,
+            &password_info.nonce,
+        )
+        .map_err(|_| "Incorrect password".to_string())?;
+        if check != DASH_SECRET_MESSAGE {
+            return Err("Incorrect password".to_string());
+        }
+
+        let mut salt = password_info.salt.clone();
+        salt.extend_from_slice(b"identity_private_keys");
+        let key: [u8; 32] = derive_password_key(password, &salt)?
+            .try_into()
+            .map_err(|_| "Derived key has the wrong length".to_string())?;
+        *self.private_key_encryption_key.write().unwrap() = Some(key);
+
+        // In read-only mode this waits until the mode is turned off
+        self.encrypt_stored_private_keys()
+    }
+
     /// Fetches all local qualified identities from the database
     pub fn load_local_qualified_identities(&self) -> Result<Vec<QualifiedIdentity>> {
         let wallets = self.wallets.read().unwrap();
         self.db.get_local_qualified_identities(self, &wallets)
     }
 
+    /// Fetches `limit` local identities starting at row `offset`, along with the number of rows
+    /// of that page that could not be decoded
+    pub fn load_local_qualified_identities_page(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<QualifiedIdentity>, usize)> {
+        let wallets = self.wallets.read().unwrap();
+        self.db
+            .get_local_qualified_identities_paged(self, &wallets, limit, offset)
+    }
+
     /// Fetches all voting identities from the database
     pub fn load_local_voting_identities(&self) -> Result<Vec<QualifiedIdentity>> {
         self.db.get_local_voting_identities(self)
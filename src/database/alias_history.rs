@@ -0,0 +1,170 @@
+use crate::database::Database;
+use chrono::Utc;
+use dash_sdk::platform::Identifier;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Alias edits of an identity this close together are kept as one change, the alias field
+/// saves on every keystroke.
+const ALIAS_EDIT_MERGE_SECONDS: i64 = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasChange {
+    pub time: i64,
+    pub old_alias: Option<String>,
+    pub new_alias: Option<String>,
+}
+
+/// Records that the alias of the identity `id` changed from `old_alias` to `new_alias`.
+/// Failing to record it is only logged, it never fails the rename.
+pub(super) fn record_alias_change(
+    conn: &Connection,
+    id: &[u8],
+    old_alias: Option<&str>,
+    new_alias: Option<&str>,
+) {
+    if old_alias == new_alias {
+        return;
+    }
+    if let Err(e) = write_alias_change(conn, id, old_alias, new_alias, Utc::now().timestamp()) {
+        tracing::warn!(
+            "Failed to record the alias change of identity {}: {}",
+            hex::encode(id),
+            e
+        );
+    }
+}
+
+fn write_alias_change(
+    conn: &Connection,
+    id: &[u8],
+    old_alias: Option<&str>,
+    new_alias: Option<&str>,
+    time: i64,
+) -> rusqlite::Result<()> {
+    let latest = conn
+        .query_row(
+            "SELECT id, time, old_alias, new_alias FROM alias_history WHERE identity_id = ?
+             ORDER BY id DESC LIMIT 1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    // Still typing the previous change, extend it instead of recording every keystroke
+    if let Some((row_id, latest_time, latest_old_alias, latest_new_alias)) = latest {
+        if time - latest_time <= ALIAS_EDIT_MERGE_SECONDS
+            && latest_new_alias.as_deref() == old_alias
+        {
+            if latest_old_alias.as_deref() == new_alias {
+                conn.execute("DELETE FROM alias_history WHERE id = ?", params![row_id])?;
+            } else {
+                conn.execute(
+                    "UPDATE alias_history SET new_alias = ?, time = ? WHERE id = ?",
+                    params![new_alias, time, row_id],
+                )?;
+            }
+            return Ok(());
+        }
+    }
+
+    conn.execute(
+        "INSERT INTO alias_history (identity_id, network, old_alias, new_alias, time)
+         SELECT id, network, ?, ?, ? FROM identity WHERE id = ?",
+        params![old_alias, new_alias, time, id],
+    )?;
+    Ok(())
+}
+
+impl Database {
+    pub fn initialize_alias_history_table(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS alias_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                identity_id BLOB NOT NULL,
+                network TEXT NOT NULL,
+                old_alias TEXT,
+                new_alias TEXT,
+                time INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        self.execute(
+            "CREATE INDEX IF NOT EXISTS idx_alias_history_identity ON alias_history (identity_id)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// The alias changes of an identity, newest first.
+    pub fn get_alias_history(&self, identifier: &Identifier) -> rusqlite::Result<Vec<AliasChange>> {
+        self.query_rows(
+            "SELECT time, old_alias, new_alias FROM alias_history WHERE identity_id = ?
+             ORDER BY id DESC",
+            params![identifier.to_vec()],
+            |row| {
+                Ok(AliasChange {
+                    time: row.get(0)?,
+                    old_alias: row.get(1)?,
+                    new_alias: row.get(2)?,
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_alias_changes_are_recorded_and_merged() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        db.execute(
+            "INSERT INTO identity (id, is_local, alias, network) VALUES (?, 1, 'old', 'testnet')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+        let id = [1u8; 32];
+        let history = || {
+            db.get_alias_history(&Identifier::new(id))
+                .expect("expected to read alias history")
+                .into_iter()
+                .map(|change| (change.old_alias, change.new_alias))
+                .collect::<Vec<_>>()
+        };
+        let conn = db.conn.lock().unwrap();
+
+        // Typing a new alias one character at a time is a single change
+        write_alias_change(&conn, &id, Some("old"), Some("n"), 1000).unwrap();
+        write_alias_change(&conn, &id, Some("n"), Some("new"), 1010).unwrap();
+        // Cleared much later, a change of its own
+        write_alias_change(&conn, &id, Some("new"), None, 5000).unwrap();
+        // Typed back right away, which undoes the clearing
+        write_alias_change(&conn, &id, None, Some("new"), 5010).unwrap();
+        write_alias_change(&conn, &id, Some("new"), None, 9000).unwrap();
+        drop(conn);
+
+        assert_eq!(
+            history(),
+            vec![
+                (Some("new".to_string()), None),
+                (Some("old".to_string()), Some("new".to_string())),
+            ]
+        );
+
+        // set_alias records through the same path
+        db.set_alias(&Identifier::new(id), Some("renamed"))
+            .expect("expected to set alias");
+        assert_eq!(history()[0], (None, Some("renamed".to_string())));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::database::Database;
+use crate::database::{network_db_name, Database};
 use dash_sdk::dpp::dashcore::hashes::Hash;
 use dash_sdk::dpp::dashcore::{
     consensus::{deserialize, serialize},
@@ -45,7 +45,7 @@ impl Database {
                 amount,
                 &islock_bytes,
                 wallet_seed_hash,
-                network.to_string()
+                network_db_name(network, None)
             ],
         )?;
 
@@ -179,7 +179,7 @@ impl Database {
             "SELECT transaction_data, amount, instant_lock_data, chain_locked_height, identity_id, wallet, network FROM asset_lock_transaction where network = ?",
         )?;
 
-        let mut rows = stmt.query(params![network.to_string()])?;
+        let mut rows = stmt.query(params![network_db_name(network, None)])?;
 
         let mut results = Vec::new();
 
@@ -0,0 +1,160 @@
+use crate::database::Database;
+use chrono::Utc;
+use dash_sdk::platform::Identifier;
+use rusqlite::{params, Connection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityAuditAction {
+    Insert,
+    Update,
+    SetAlias,
+    Delete,
+}
+
+impl IdentityAuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentityAuditAction::Insert => "Insert",
+            IdentityAuditAction::Update => "Update",
+            IdentityAuditAction::SetAlias => "SetAlias",
+            IdentityAuditAction::Delete => "Delete",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Insert" => Some(IdentityAuditAction::Insert),
+            "Update" => Some(IdentityAuditAction::Update),
+            "SetAlias" => Some(IdentityAuditAction::SetAlias),
+            "Delete" => Some(IdentityAuditAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentityAuditEntry {
+    pub time: i64,
+    pub action: IdentityAuditAction,
+    pub identity_id: Identifier,
+    pub network: String,
+}
+
+/// Appends an entry for the identity `id` to the audit log, with the network of its row.
+/// Failing to record it is only logged, it never fails the change being audited. Only changes
+/// the user made are recorded: migrations, the encryption of stored private keys and saving
+/// what was refreshed from Platform rewrite identities without calling this.
+pub(super) fn record_identity_audit(conn: &Connection, action: IdentityAuditAction, id: &[u8]) {
+    if let Err(e) = conn.execute(
+        "INSERT INTO audit_log (time, action, identity_id, network)
+         SELECT ?, ?, id, network FROM identity WHERE id = ?",
+        params![Utc::now().timestamp(), action.as_str(), id],
+    ) {
+        tracing::warn!(
+            "Failed to record {} of identity {} in the audit log: {}",
+            action.as_str(),
+            hex::encode(id),
+            e
+        );
+    }
+}
+
+impl Database {
+    pub fn initialize_audit_log_table(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                time INTEGER NOT NULL,
+                action TEXT NOT NULL,
+                identity_id BLOB NOT NULL,
+                network TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// The latest `limit` audit log entries of all networks, newest first.
+    pub fn get_audit_log(&self, limit: u32) -> rusqlite::Result<Vec<IdentityAuditEntry>> {
+        self.query_rows(
+            "SELECT time, action, identity_id, network FROM audit_log ORDER BY id DESC LIMIT ?",
+            params![limit],
+            |row| {
+                let action: String = row.get(1)?;
+                let identity_id: Vec<u8> = row.get(2)?;
+                Ok(IdentityAuditEntry {
+                    time: row.get(0)?,
+                    action: IdentityAuditAction::parse(&action)
+                        .ok_or(rusqlite::Error::InvalidQuery)?,
+                    identity_id: Identifier::from_bytes(&identity_id)
+                        .map_err(|_| rusqlite::Error::InvalidQuery)?,
+                    network: row.get(3)?,
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_identity_mutations_are_audited() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'testnet')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+        let identifier = Identifier::new([1; 32]);
+
+        db.set_alias(&identifier, Some("audited"))
+            .expect("expected to set alias");
+        // Unknown identities change nothing and are not recorded
+        assert!(db
+            .set_alias(&Identifier::new([2; 32]), Some("missing"))
+            .is_err());
+
+        let log = db.get_audit_log(10).expect("expected to read audit log");
+        assert_eq!(
+            log,
+            vec![IdentityAuditEntry {
+                time: log[0].time,
+                action: IdentityAuditAction::SetAlias,
+                identity_id: identifier,
+                network: "testnet".to_string(),
+            }]
+        );
+
+        // The log can't be written, the alias change still goes through
+        db.execute("DROP TABLE audit_log", []).unwrap();
+        db.set_alias(&identifier, None)
+            .expect("expected to clear alias");
+    }
+
+    #[test]
+    fn test_internal_rewrites_are_not_audited() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'TESTNET')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+
+        db.normalize_network_columns()
+            .expect("expected to normalize networks");
+        db.encrypt_identity_private_keys(&[5; 32])
+            .expect("expected to encrypt private keys");
+
+        assert!(db
+            .get_audit_log(10)
+            .expect("expected to read audit log")
+            .is_empty());
+    }
+}
@@ -1,74 +1,212 @@
 use crate::context::AppContext;
+use crate::database::alias_history::record_alias_change;
+use crate::database::audit_log::{record_identity_audit, IdentityAuditAction};
 use crate::database::Database;
-use crate::model::qualified_identity::QualifiedIdentity;
+use crate::model::alias_import::AliasImportEntry;
+use crate::model::qualified_identity::{QualifiedIdentity, TopUp};
 use crate::model::wallet::{Wallet, WalletSeedHash};
+use chrono::Utc;
 use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
 use dash_sdk::platform::Identifier;
-use rusqlite::params;
-use std::collections::BTreeMap;
+use rusqlite::{params, Connection, OptionalExtension, Params};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 
+/// Favorites first, then by alias with unnamed identities last. The id keeps pages stable.
+const LOCAL_IDENTITIES_ORDER: &str =
+    "ORDER BY is_favorite DESC, alias IS NULL, alias COLLATE NOCASE, id";
+
+#[derive(Debug, thiserror::Error)]
+pub enum SetAliasError {
+    #[error("The alias \"{0}\" is already used by another identity")]
+    AliasAlreadyTaken(String),
+    #[error(transparent)]
+    Database(#[from] rusqlite::Error),
+}
+
+/// What importing the alias of one identity did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasImportOutcome {
+    Set,
+    /// The identity already had this alias
+    Unchanged,
+    /// No identity with this id on the network, nothing is created for it
+    UnknownIdentity,
+    /// Another identity already uses the alias, the identity keeps its alias
+    AliasTaken(String),
+}
+
+/// Aggregated figures about the local identities of a network.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdentitySummary {
+    /// Number of identities per identity type, keyed by the stored type name.
+    pub count_by_type: BTreeMap<String, u64>,
+    /// Sum of the recorded top up amounts, in duffs.
+    pub total_top_up_amount: u64,
+    /// Number of identities with at least one private key stored locally.
+    pub with_private_keys: u64,
+}
+
+impl IdentitySummary {
+    pub fn total_count(&self) -> u64 {
+        self.count_by_type.values().sum()
+    }
+}
+
 impl Database {
-    /// Updates the alias of a specified identity.
+    /// Updates the alias of a specified identity. Aliases must be unique among the identities of
+    /// a network, clearing the alias is always allowed.
     pub fn set_alias(
         &self,
         identifier: &Identifier,
         new_alias: Option<&str>,
-    ) -> rusqlite::Result<()> {
+    ) -> Result<(), SetAliasError> {
+        self.ensure_writable()?;
         let id = identifier.to_vec();
         let conn = self.conn.lock().unwrap();
 
+        if let Some(alias) = new_alias.filter(|alias| !alias.is_empty()) {
+            let taken: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM identity WHERE alias = ?1 AND id != ?2 AND is_deleted = 0
+                 AND network = (SELECT network FROM identity WHERE id = ?2))",
+                params![alias, id],
+                |row| row.get(0),
+            )?;
+            if taken {
+                return Err(SetAliasError::AliasAlreadyTaken(alias.to_string()));
+            }
+        }
+
+        let old_alias: Option<String> = conn
+            .query_row(
+                "SELECT alias FROM identity WHERE id = ?",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+
         let rows_updated = conn.execute(
             "UPDATE identity SET alias = ? WHERE id = ?",
             params![new_alias, id],
         )?;
 
         if rows_updated == 0 {
-            return Err(rusqlite::Error::QueryReturnedNoRows);
+            return Err(rusqlite::Error::QueryReturnedNoRows.into());
         }
+        record_identity_audit(&conn, IdentityAuditAction::SetAlias, &id);
+        record_alias_change(&conn, &id, old_alias.as_deref(), new_alias);
 
         Ok(())
     }
+
+    /// Sets the aliases of `entries` in a single transaction. Ids without an identity on the
+    /// current network and aliases used by another identity are skipped, the outcome of each
+    /// entry is returned in order. Entries are applied one after the other, so an alias freed
+    /// by an earlier entry can be taken by a later one.
+    pub fn import_aliases(
+        &self,
+        entries: &[AliasImportEntry],
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<AliasImportOutcome>> {
+        self.ensure_writable()?;
+        self.import_aliases_for_network(entries, &app_context.network_string())
+    }
+
+    fn import_aliases_for_network(
+        &self,
+        entries: &[AliasImportEntry],
+        network: &str,
+    ) -> rusqlite::Result<Vec<AliasImportOutcome>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut outcomes = Vec::with_capacity(entries.len());
+        for (identifier, new_alias) in entries {
+            let id = identifier.to_vec();
+            let new_alias = new_alias.as_deref().filter(|alias| !alias.is_empty());
+            let Some(old_alias) = tx
+                .query_row(
+                    "SELECT alias FROM identity WHERE id = ? AND network = ? AND is_deleted = 0",
+                    params![id, network],
+                    |row| row.get::<_, Option<String>>(0),
+                )
+                .optional()?
+            else {
+                outcomes.push(AliasImportOutcome::UnknownIdentity);
+                continue;
+            };
+            if old_alias.as_deref() == new_alias {
+                outcomes.push(AliasImportOutcome::Unchanged);
+                continue;
+            }
+            if let Some(alias) = new_alias {
+                let taken: bool = tx.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM identity WHERE alias = ? AND id != ? AND is_deleted = 0
+                     AND network = ?)",
+                    params![alias, id, network],
+                    |row| row.get(0),
+                )?;
+                if taken {
+                    outcomes.push(AliasImportOutcome::AliasTaken(alias.to_string()));
+                    continue;
+                }
+            }
+            tx.execute(
+                "UPDATE identity SET alias = ? WHERE id = ?",
+                params![new_alias, id],
+            )?;
+            record_identity_audit(&tx, IdentityAuditAction::SetAlias, &id);
+            record_alias_change(&tx, &id, old_alias.as_deref(), new_alias);
+            outcomes.push(AliasImportOutcome::Set);
+        }
+        tx.commit()?;
+        Ok(outcomes)
+    }
+
     pub fn insert_local_qualified_identity(
         &self,
         qualified_identity: &QualifiedIdentity,
         wallet_and_identity_id_info: Option<(&[u8], u32)>,
         app_context: &AppContext,
     ) -> rusqlite::Result<()> {
-        let id = qualified_identity.identity.id().to_vec();
-        let data = qualified_identity.to_bytes();
-        let alias = qualified_identity.alias.clone();
-        let identity_type = format!("{:?}", qualified_identity.identity_type);
+        let row =
+            LocalIdentityRow::new(qualified_identity, wallet_and_identity_id_info, app_context)?;
+        let conn = self.conn.lock().unwrap();
+        insert_local_identity_row(&conn, &row, &app_context.network_string())
+    }
 
-        let network = app_context.network_string();
+    /// Inserts many local qualified identities in a single transaction. If any insert fails
+    /// none of the identities are written.
+    pub fn insert_local_qualified_identities(
+        &self,
+        identities: &[(QualifiedIdentity, Option<(&[u8], u32)>)],
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        // Serialized up front so the write lock isn't held while private keys are encrypted
+        let rows = identities
+            .iter()
+            .map(|(qualified_identity, wallet_and_identity_id_info)| {
+                LocalIdentityRow::new(
+                    qualified_identity,
+                    *wallet_and_identity_id_info,
+                    app_context,
+                )
+            })
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        self.insert_local_identity_rows_for_network(&rows, &app_context.network_string())
+    }
 
-        if let Some((wallet, wallet_index)) = wallet_and_identity_id_info {
-            // If wallet information is provided, insert with wallet and wallet_index
-            self.execute(
-                "INSERT OR REPLACE INTO identity
-             (id, data, is_local, alias, identity_type, network, wallet, wallet_index)
-             VALUES (?, ?, 1, ?, ?, ?, ?, ?)",
-                params![
-                    id,
-                    data,
-                    alias,
-                    identity_type,
-                    network,
-                    wallet,
-                    wallet_index
-                ],
-            )?;
-        } else {
-            // If wallet information is not provided, insert without wallet and wallet_index
-            self.execute(
-                "INSERT OR REPLACE INTO identity
-             (id, data, is_local, alias, identity_type, network)
-             VALUES (?, ?, 1, ?, ?, ?)",
-                params![id, data, alias, identity_type, network],
-            )?;
+    fn insert_local_identity_rows_for_network(
+        &self,
+        rows: &[LocalIdentityRow],
+        network: &str,
+    ) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        for row in rows {
+            insert_local_identity_row(&tx, row, network)?;
         }
-
-        Ok(())
+        tx.commit()
     }
 
     pub fn update_local_qualified_identity(
@@ -76,9 +214,32 @@ impl Database {
         qualified_identity: &QualifiedIdentity,
         app_context: &AppContext,
     ) -> rusqlite::Result<()> {
+        self.write_local_qualified_identity(qualified_identity, app_context, true)
+    }
+
+    /// Saves what was fetched from Platform for a local identity. Unlike
+    /// [`Database::update_local_qualified_identity`] this isn't recorded in the audit log, since
+    /// the user didn't change anything.
+    pub fn save_refreshed_local_qualified_identity(
+        &self,
+        qualified_identity: &QualifiedIdentity,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.write_local_qualified_identity(qualified_identity, app_context, false)
+    }
+
+    fn write_local_qualified_identity(
+        &self,
+        qualified_identity: &QualifiedIdentity,
+        app_context: &AppContext,
+        audit: bool,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         // Extract the fields from `qualified_identity` to use in the SQL update
         let id = qualified_identity.identity.id().to_vec();
-        let data = qualified_identity.to_bytes();
+        let data = app_context
+            .identity_stored_bytes(qualified_identity)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
         let alias = qualified_identity.alias.clone();
         let identity_type = format!("{:?}", qualified_identity.identity_type);
 
@@ -86,12 +247,16 @@ impl Database {
         let network = app_context.network_string();
 
         // Execute the update statement
-        self.execute(
+        let conn = self.conn.lock().unwrap();
+        let rows_updated = conn.execute(
             "UPDATE identity
          SET data = ?, alias = ?, identity_type = ?, network = ?, is_local = 1
          WHERE id = ?",
             params![data, alias, identity_type, network, id],
         )?;
+        if audit && rows_updated > 0 {
+            record_identity_audit(&conn, IdentityAuditAction::Update, &id);
+        }
 
         Ok(())
     }
@@ -103,17 +268,24 @@ impl Database {
         identity_index: u32,
         app_context: &AppContext,
     ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         let id = qualified_identity.identity.id().to_vec();
-        let data = qualified_identity.to_bytes();
+        let data = app_context
+            .identity_stored_bytes(qualified_identity)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
         let alias = qualified_identity.alias.clone();
         let identity_type = format!("{:?}", qualified_identity.identity_type);
 
         let network = app_context.network_string();
 
         self.execute(
-            "INSERT OR REPLACE INTO identity
+            "INSERT INTO identity
          (id, data, is_local, alias, identity_type, network, is_in_creation, wallet, wallet_index)
-         VALUES (?, ?, 1, ?, ?, ?, 1, ?, ?)",
+         VALUES (?, ?, 1, ?, ?, ?, 1, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET data = excluded.data, is_local = 1,
+         alias = excluded.alias, identity_type = excluded.identity_type,
+         network = excluded.network, is_deleted = 0, is_in_creation = 1, info = NULL,
+         wallet = excluded.wallet, wallet_index = excluded.wallet_index",
             params![
                 id,
                 data,
@@ -134,6 +306,7 @@ impl Database {
         qualified_identity: Option<&QualifiedIdentity>,
         app_context: &AppContext,
     ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         let id = identifier.to_vec();
         let alias = qualified_identity.and_then(|qi| qi.alias.clone());
         let identity_type =
@@ -148,12 +321,13 @@ impl Database {
             conn.prepare("SELECT COUNT(*) FROM identity WHERE id = ? AND network = ?")?;
         let count: i64 = stmt.query_row(params![id, network], |row| row.get(0))?;
 
-        // If the identity doesn't exist, insert it
+        // If the identity doesn't exist, insert it. Its data was just fetched when there is any.
         if count == 0 {
-            self.execute(
-                "INSERT INTO identity (id, data, is_local, alias, identity_type, network)
-             VALUES (?, ?, 0, ?, ?, ?)",
-                params![id, data, alias, identity_type, network],
+            let last_synced_at = data.as_ref().map(|_| Utc::now().timestamp());
+            conn.execute(
+                "INSERT INTO identity (id, data, is_local, alias, identity_type, network, last_synced_at)
+             VALUES (?, ?, 0, ?, ?, ?, ?)",
+                params![id, data, alias, identity_type, network, last_synced_at],
             )?;
         }
 
@@ -165,117 +339,676 @@ impl Database {
         app_context: &AppContext,
         wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
     ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
-        let network = app_context.network_string();
+        self.get_local_qualified_identities_and_skipped(app_context, wallets)
+            .map(|(identities, _)| identities)
+    }
+
+    /// Like [`Database::get_local_qualified_identities`], also returning how many stored
+    /// identities could not be decoded and were left out.
+    pub fn get_local_qualified_identities_and_skipped(
+        &self,
+        app_context: &AppContext,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+    ) -> rusqlite::Result<(Vec<QualifiedIdentity>, usize)> {
+        self.query_local_qualified_identities(
+            app_context,
+            wallets,
+            &format!(
+                "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL
+                 {}",
+                LOCAL_IDENTITIES_ORDER
+            ),
+            params![app_context.network_string()],
+        )
+    }
+
+    /// Loads a single local identity of the current network, with its top ups and `wallets`
+    /// associated. `None` when there is no such identity.
+    pub fn get_local_qualified_identity(
+        &self,
+        identifier: &Identifier,
+        app_context: &AppContext,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+    ) -> rusqlite::Result<Option<QualifiedIdentity>> {
+        self.get_local_qualified_identity_for_network(
+            identifier,
+            &app_context.network_string(),
+            app_context.private_key_encryption_key().as_ref(),
+            wallets,
+        )
+    }
+
+    fn get_local_qualified_identity_for_network(
+        &self,
+        identifier: &Identifier,
+        network: &str,
+        encryption_key: Option<&[u8; 32]>,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+    ) -> rusqlite::Result<Option<QualifiedIdentity>> {
+        self.query_local_qualified_identities_with_key(
+            encryption_key,
+            wallets,
+            "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL
+             AND id = ?",
+            params![network, identifier.to_vec()],
+        )
+        .map(|(identities, _)| identities.into_iter().next())
+    }
 
+    /// Returns one page of the local identities, in the same order as
+    /// [`Database::get_local_qualified_identities`], along with the number of rows of the page
+    /// that could not be decoded.
+    pub fn get_local_qualified_identities_paged(
+        &self,
+        app_context: &AppContext,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+        limit: u32,
+        offset: u32,
+    ) -> rusqlite::Result<(Vec<QualifiedIdentity>, usize)> {
+        self.query_local_qualified_identities(
+            app_context,
+            wallets,
+            &format!(
+                "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL
+                 {} LIMIT ? OFFSET ?",
+                LOCAL_IDENTITIES_ORDER
+            ),
+            params![app_context.network_string(), limit, offset],
+        )
+    }
+
+    /// Number of rows [`Database::get_local_qualified_identities_paged`] pages through.
+    pub fn count_local_qualified_identities(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<u32> {
+        self.count_local_qualified_identities_for_network(&app_context.network_string())
+    }
+
+    fn count_local_qualified_identities_for_network(&self, network: &str) -> rusqlite::Result<u32> {
         let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COUNT(*) FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL",
+            params![network],
+            |row| row.get(0),
+        )
+    }
 
-        // Prepare the main statement to select identities, including wallet_index
-        let mut stmt = conn.prepare(
-            "SELECT data, alias, wallet_index FROM identity WHERE is_local = 1 AND network = ? AND data IS NOT NULL",
-        )?;
+    /// Returns the local identities whose alias contains `query`, ignoring case. An empty query
+    /// returns all local identities.
+    pub fn search_local_identities_by_alias(
+        &self,
+        app_context: &AppContext,
+        query: &str,
+    ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
+        let wallets = app_context.wallets.read().unwrap();
+        let query = query.trim();
+        if query.is_empty() {
+            return self.get_local_qualified_identities(app_context, &wallets);
+        }
+
+        // Match `%` and `_` in the query literally
+        let pattern = format!(
+            "%{}%",
+            query
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        self.query_local_qualified_identities(
+            app_context,
+            &wallets,
+            "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL
+             AND alias LIKE ? ESCAPE '\\'",
+            params![app_context.network_string(), pattern],
+        )
+        .map(|(identities, _)| identities)
+    }
+
+    /// Up to `max` aliases of local identities closest to `query` by edit distance, ignoring
+    /// case, closest first. Only aliases starting like the query are ranked, unless there are
+    /// none, then all of them are.
+    pub fn suggest_aliases(
+        &self,
+        app_context: &AppContext,
+        query: &str,
+        max: usize,
+    ) -> rusqlite::Result<Vec<String>> {
+        self.suggest_aliases_for_network(&app_context.network_string(), query, max)
+    }
+
+    fn suggest_aliases_for_network(
+        &self,
+        network: &str,
+        query: &str,
+        max: usize,
+    ) -> rusqlite::Result<Vec<String>> {
+        let query = query.trim().to_lowercase();
+        let Some(first) = query.chars().next() else {
+            return Ok(Vec::new());
+        };
+        let select_aliases = |pattern: &str| {
+            self.query_rows(
+                "SELECT alias FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL
+                 AND alias IS NOT NULL AND alias != '' AND alias LIKE ? ESCAPE '\\'",
+                params![network, pattern],
+                |row| row.get::<_, String>(0),
+            )
+        };
 
-        // Prepare the statement to select top-ups (will be used multiple times)
-        let mut top_up_stmt =
-            conn.prepare("SELECT top_up_index, amount FROM top_up WHERE identity_id = ?")?;
+        let prefix = match first {
+            '\\' | '%' | '_' => format!("\\{}%", first),
+            _ => format!("{}%", first),
+        };
+        let mut aliases = select_aliases(&prefix)?;
+        if aliases.is_empty() {
+            aliases = select_aliases("%")?;
+        }
 
-        // Iterate over each identity
-        let identity_iter = stmt.query_map(params![network], |row| {
-            let data: Vec<u8> = row.get(0)?;
-            let alias: Option<String> = row.get(1)?;
-            let wallet_index: Option<u32> = row.get(2)?;
+        let mut ranked: Vec<(usize, String)> = aliases
+            .into_iter()
+            .map(|alias| (levenshtein(&query, &alias.to_lowercase()), alias))
+            .collect();
+        ranked.sort();
+        Ok(ranked
+            .into_iter()
+            .take(max)
+            .map(|(_, alias)| alias)
+            .collect())
+    }
 
-            let mut identity: QualifiedIdentity = QualifiedIdentity::from_bytes(&data);
-            identity.alias = alias;
-            identity.wallet_index = wallet_index;
+    /// Runs `sql`, which must select `id, data, alias, wallet_index` from the identity table, and
+    /// loads the matching identities with their wallets and top ups. Also returns the number of
+    /// rows that could not be decoded.
+    pub(super) fn query_local_qualified_identities<P: Params>(
+        &self,
+        app_context: &AppContext,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+        sql: &str,
+        params: P,
+    ) -> rusqlite::Result<(Vec<QualifiedIdentity>, usize)> {
+        self.query_local_qualified_identities_with_key(
+            app_context.private_key_encryption_key().as_ref(),
+            wallets,
+            sql,
+            params,
+        )
+    }
+
+    fn query_local_qualified_identities_with_key<P: Params>(
+        &self,
+        encryption_key: Option<&[u8; 32]>,
+        wallets: &BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
+        sql: &str,
+        params: P,
+    ) -> rusqlite::Result<(Vec<QualifiedIdentity>, usize)> {
+        let rows = self.query_rows(sql, params, |row| {
+            let data: Vec<u8> = row.get(1)?;
+            let alias: Option<String> = row.get(2)?;
+            let wallet_index: Option<u32> = row.get(3)?;
+            let identity =
+                QualifiedIdentity::from_stored_bytes(&data, encryption_key).map(|mut identity| {
+                    identity.alias = alias;
+                    identity.wallet_index = wallet_index;
+                    identity
+                });
+            Ok((row.get::<_, Vec<u8>>(0)?, identity))
+        })?;
+        let (mut identities, skipped) = skip_undecodable_identities(rows);
 
+        for identity in identities.iter_mut() {
             // Associate wallets
             identity.associated_wallets = wallets.clone(); //todo: use less wallets
 
-            // Retrieve the identity_id as bytes
-            let identity_id = identity.identity.id().to_buffer();
+            identity.top_ups = self
+                .query_rows(
+                    "SELECT top_up_index, amount, created_at FROM top_up WHERE identity_id = ?",
+                    params![identity.identity.id().to_buffer()],
+                    |row| {
+                        let created_at: Option<i64> = row.get(2)?;
+                        Ok((
+                            row.get::<_, u32>(0)?,
+                            TopUp {
+                                amount: row.get(1)?,
+                                // Rows migrated from before the column existed may hold 0
+                                created_at: created_at.filter(|time| *time > 0),
+                            },
+                        ))
+                    },
+                )?
+                .into_iter()
+                .collect();
+        }
 
-            // Query the top_up table for this identity_id
-            let mut top_ups = BTreeMap::new();
-            let mut rows = top_up_stmt.query(params![identity_id])?;
+        Ok((identities, skipped))
+    }
 
-            while let Some(top_up_row) = rows.next()? {
-                let top_up_index: u32 = top_up_row.get(0)?;
-                let amount: u32 = top_up_row.get(1)?;
-                top_ups.insert(top_up_index, amount);
-            }
+    pub fn local_identity_summary(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<IdentitySummary> {
+        self.local_identity_summary_for_network(&app_context.network_string())
+    }
 
-            // Assign the top_ups to the identity
-            identity.top_ups = top_ups;
+    fn local_identity_summary_for_network(
+        &self,
+        network: &str,
+    ) -> rusqlite::Result<IdentitySummary> {
+        let count_by_type = self
+            .query_rows(
+                "SELECT COALESCE(identity_type, ''), COUNT(*) FROM identity
+                 WHERE is_local = 1 AND is_deleted = 0 AND network = ? GROUP BY identity_type",
+                params![network],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, u64>(1)?)),
+            )?
+            .into_iter()
+            .collect();
 
-            Ok(identity)
-        })?;
+        let conn = self.conn.lock().unwrap();
+        let total_top_up_amount: i64 = conn.query_row(
+            "SELECT COALESCE(SUM(top_up.amount), 0) FROM top_up
+             JOIN identity ON identity.id = top_up.identity_id
+             WHERE identity.is_local = 1 AND identity.is_deleted = 0 AND identity.network = ?",
+            params![network],
+            |row| row.get(0),
+        )?;
+        drop(conn);
+
+        // Private keys live inside the serialized identity, so this one can't be an aggregate.
+        // Each row is decoded and dropped right away rather than loading full identities.
+        let with_private_keys = self
+            .query_rows(
+                "SELECT data FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL",
+                params![network],
+                |row| {
+                    let data: Vec<u8> = row.get(0)?;
+                    Ok(QualifiedIdentity::from_bytes(&data)
+                        .is_ok_and(|identity| !identity.private_keys.private_keys.is_empty()))
+                },
+            )?
+            .into_iter()
+            .filter(|has_private_keys| *has_private_keys)
+            .count() as u64;
 
-        let identities: rusqlite::Result<Vec<QualifiedIdentity>> = identity_iter.collect();
-        identities
+        Ok(IdentitySummary {
+            count_by_type,
+            total_top_up_amount: total_top_up_amount as u64,
+            with_private_keys,
+        })
     }
 
     pub fn get_local_voting_identities(
         &self,
         app_context: &AppContext,
     ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
-        let network = app_context.network_string();
+        let encryption_key = app_context.private_key_encryption_key();
+        let rows = self.query_rows(
+            "SELECT id, data FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND identity_type != 'User' AND data IS NOT NULL",
+            params![app_context.network_string()],
+            |row| {
+                let data: Vec<u8> = row.get(1)?;
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    QualifiedIdentity::from_stored_bytes(&data, encryption_key.as_ref()),
+                ))
+            },
+        )?;
+        Ok(skip_undecodable_identities(rows).0)
+    }
 
-        let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT data FROM identity WHERE is_local = 1 AND network = ? AND identity_type != 'User' AND data IS NOT NULL",
+    pub fn get_local_user_identities(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
+        let encryption_key = app_context.private_key_encryption_key();
+        let rows = self.query_rows(
+            "SELECT id, data FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND identity_type = 'User' AND data IS NOT NULL",
+            params![app_context.network_string()],
+            |row| {
+                let data: Vec<u8> = row.get(1)?;
+                Ok((
+                    row.get::<_, Vec<u8>>(0)?,
+                    QualifiedIdentity::from_stored_bytes(&data, encryption_key.as_ref()),
+                ))
+            },
         )?;
-        let identity_iter = stmt.query_map(params![network], |row| {
-            let data: Vec<u8> = row.get(0)?;
-            let identity: QualifiedIdentity = QualifiedIdentity::from_bytes(&data);
+        Ok(skip_undecodable_identities(rows).0)
+    }
 
-            Ok(identity)
-        })?;
+    /// Marks the identity as deleted. It stays in the database, with its keys, until
+    /// [`Database::purge_deleted_identities`] is called, and can be brought back with
+    /// [`Database::restore_local_qualified_identity`].
+    pub fn delete_local_qualified_identity(
+        &self,
+        identifier: &Identifier,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.ensure_identity_order_table_exists()?;
+        let id = identifier.to_vec();
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let rows_updated = tx.execute(
+            "UPDATE identity SET is_deleted = 1 WHERE id = ? AND network = ? AND is_local = 1",
+            params![id, app_context.network_string()],
+        )?;
+        if rows_updated > 0 {
+            // A deleted identity no longer has a place in the custom order
+            tx.execute(
+                "DELETE FROM identity_order WHERE identity_id = ?",
+                params![id],
+            )?;
+            record_identity_audit(&tx, IdentityAuditAction::Delete, &id);
+        }
+        tx.commit()
+    }
 
-        let identities: rusqlite::Result<Vec<QualifiedIdentity>> = identity_iter.collect();
-        identities
+    pub fn restore_local_qualified_identity(
+        &self,
+        identifier: &Identifier,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE identity SET is_deleted = 0 WHERE id = ? AND network = ? AND is_local = 1",
+            params![identifier.to_vec(), app_context.network_string()],
+        )?;
+        Ok(())
     }
 
-    pub fn get_local_user_identities(
+    /// Returns the local identities that were deleted but not purged yet.
+    pub fn get_deleted_local_identities(
         &self,
         app_context: &AppContext,
     ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
+        let wallets = app_context.wallets.read().unwrap();
+        self.query_local_qualified_identities(
+            app_context,
+            &wallets,
+            "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 1 AND network = ? AND data IS NOT NULL",
+            params![app_context.network_string()],
+        )
+        .map(|(identities, _)| identities)
+    }
+
+    /// Permanently removes every deleted identity of the current network.
+    pub fn purge_deleted_identities(&self, app_context: &AppContext) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         let network = app_context.network_string();
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
 
-        let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT data FROM identity WHERE is_local = 1 AND network = ? AND identity_type = 'User' AND data IS NOT NULL",
+        // Asset locks only keep a weak reference to identities that were being created
+        tx.execute(
+            "UPDATE asset_lock_transaction SET identity_id_potentially_in_creation = NULL
+             WHERE identity_id_potentially_in_creation IN
+             (SELECT id FROM identity WHERE is_deleted = 1 AND network = ?)",
+            params![network],
+        )?;
+        tx.execute(
+            "DELETE FROM identity WHERE is_deleted = 1 AND network = ?",
+            params![network],
         )?;
-        let identity_iter = stmt.query_map(params![network], |row| {
-            let data: Vec<u8> = row.get(0)?;
-            let identity: QualifiedIdentity = QualifiedIdentity::from_bytes(&data);
 
-            Ok(identity)
-        })?;
+        tx.commit()
+    }
+
+    /// Encrypts the clear private keys of every stored identity with `encryption_key`, whatever
+    /// its network and including deleted and wallet-linked ones, in a single transaction. Rows
+    /// without clear keys are left untouched. The user changed nothing, so nothing is recorded
+    /// in the audit log. Returns the number of identities rewritten.
+    pub fn encrypt_identity_private_keys(
+        &self,
+        encryption_key: &[u8; 32],
+    ) -> rusqlite::Result<usize> {
+        self.ensure_writable()?;
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        let rows = tx
+            .prepare("SELECT id, data FROM identity WHERE data IS NOT NULL")?
+            .query_map([], |row| {
+                Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let mut rewritten = 0;
+        for (id, data) in rows {
+            let identity = match QualifiedIdentity::from_bytes(&data) {
+                Ok(identity) => identity,
+                Err(e) => {
+                    tracing::warn!(
+                        "Not encrypting the keys of identity {}: {}",
+                        hex::encode(&id),
+                        e
+                    );
+                    continue;
+                }
+            };
+            if !identity.private_keys.has_clear_keys() {
+                continue;
+            }
+            let data = identity
+                .to_stored_bytes(Some(encryption_key))
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+            tx.execute(
+                "UPDATE identity SET data = ? WHERE id = ?",
+                params![data, id],
+            )?;
+            rewritten += 1;
+        }
 
-        let identities: rusqlite::Result<Vec<QualifiedIdentity>> = identity_iter.collect();
-        identities
+        tx.commit()?;
+        Ok(rewritten)
     }
 
-    /// Deletes a local qualified identity with the given identifier from the database.
-    pub fn delete_local_qualified_identity(
+    /// Ids of the local identities of the current network whose registration was started but
+    /// never completed.
+    pub fn get_in_creation_identity_ids(
         &self,
-        identifier: &Identifier,
         app_context: &AppContext,
-    ) -> rusqlite::Result<()> {
-        let id = identifier.to_vec();
+    ) -> rusqlite::Result<Vec<Identifier>> {
+        self.query_rows(
+            "SELECT id FROM identity WHERE is_in_creation = 1 AND is_local = 1 AND is_deleted = 0 AND network = ?",
+            params![app_context.network_string()],
+            |row| {
+                let id: Vec<u8> = row.get(0)?;
+                Identifier::from_bytes(&id).map_err(|_| rusqlite::Error::InvalidQuery)
+            },
+        )
+    }
+
+    /// The local identities of the current network whose registration was started but never
+    /// completed, each with the wallet and wallet index it was being created from, so the
+    /// registration can be resumed.
+    pub fn get_in_creation_identities(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<(QualifiedIdentity, Vec<u8>, u32)>> {
         let network = app_context.network_string();
+        let mut wallet_info: HashMap<Vec<u8>, (Vec<u8>, u32)> = self
+            .query_rows(
+                "SELECT id, wallet, wallet_index FROM identity WHERE is_in_creation = 1 AND is_local = 1 AND is_deleted = 0 AND network = ?
+                 AND wallet IS NOT NULL AND wallet_index IS NOT NULL",
+                params![network],
+                |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))),
+            )?
+            .into_iter()
+            .collect();
 
-        let conn = self.conn.lock().unwrap();
+        let wallets = app_context.wallets.read().unwrap();
+        let (identities, _) = self.query_local_qualified_identities(
+            app_context,
+            &wallets,
+            "SELECT id, data, alias, wallet_index FROM identity WHERE is_in_creation = 1 AND is_local = 1 AND is_deleted = 0 AND network = ?
+             AND data IS NOT NULL AND wallet IS NOT NULL AND wallet_index IS NOT NULL",
+            params![network],
+        )?;
 
-        // Perform the deletion only if the identity is marked as local
-        conn.execute(
-            "DELETE FROM identity WHERE id = ? AND network = ? AND is_local = 1",
+        Ok(identities
+            .into_iter()
+            .filter_map(|identity| {
+                let (wallet, wallet_index) =
+                    wallet_info.remove(&identity.identity.id().to_vec())?;
+                Some((identity, wallet, wallet_index))
+            })
+            .collect())
+    }
+
+    /// Abandons the creation of an identity. Its row is removed and the asset lock that was
+    /// funding it goes back to the wallet. Identities whose creation completed are never
+    /// touched. Returns whether an identity was removed.
+    pub fn cancel_identity_in_creation(
+        &self,
+        identifier: &Identifier,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<bool> {
+        self.ensure_writable()?;
+        self.cancel_identity_in_creation_for_network(identifier, &app_context.network_string())
+    }
+
+    fn cancel_identity_in_creation_for_network(
+        &self,
+        identifier: &Identifier,
+        network: &str,
+    ) -> rusqlite::Result<bool> {
+        let id = identifier.to_vec();
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "UPDATE asset_lock_transaction SET identity_id_potentially_in_creation = NULL
+             WHERE identity_id_potentially_in_creation IN
+             (SELECT id FROM identity WHERE id = ? AND is_in_creation = 1 AND network = ?)",
             params![id, network],
         )?;
+        let removed = tx.execute(
+            "DELETE FROM identity WHERE id = ? AND is_in_creation = 1 AND network = ?",
+            params![id, network],
+        )?;
+
+        tx.commit()?;
+        Ok(removed > 0)
+    }
+
+    /// Adds the soft-delete flag to databases created before it existed.
+    pub fn add_identity_is_deleted_column(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "ALTER TABLE identity ADD COLUMN is_deleted INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+        Ok(())
+    }
+
+    pub fn add_identity_is_favorite_column(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "ALTER TABLE identity ADD COLUMN is_favorite INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Marks an identity of the current network as a favorite, listed before the others.
+    pub fn set_identity_favorite(
+        &self,
+        identifier: &Identifier,
+        favorite: bool,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.set_identity_favorite_for_network(identifier, favorite, &app_context.network_string())
+    }
+
+    fn set_identity_favorite_for_network(
+        &self,
+        identifier: &Identifier,
+        favorite: bool,
+        network: &str,
+    ) -> rusqlite::Result<()> {
+        self.execute(
+            "UPDATE identity SET is_favorite = ? WHERE id = ? AND network = ?",
+            params![favorite, identifier.to_vec(), network],
+        )?;
+        Ok(())
+    }
+
+    pub fn add_identity_last_synced_at_column(&self) -> rusqlite::Result<()> {
+        self.execute("ALTER TABLE identity ADD COLUMN last_synced_at INTEGER", [])?;
+        Ok(())
+    }
+
+    /// Records that the data of an identity of the current network was just fetched from
+    /// Platform.
+    pub fn mark_identity_synced(
+        &self,
+        identifier: &Identifier,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.mark_identity_synced_for_network(
+            identifier,
+            &app_context.network_string(),
+            Utc::now().timestamp(),
+        )
+    }
 
+    fn mark_identity_synced_for_network(
+        &self,
+        identifier: &Identifier,
+        network: &str,
+        synced_at: i64,
+    ) -> rusqlite::Result<()> {
+        self.execute(
+            "UPDATE identity SET last_synced_at = ? WHERE id = ? AND network = ?",
+            params![synced_at, identifier.to_vec(), network],
+        )?;
         Ok(())
     }
 
+    /// When each identity of the current network was last fetched from Platform, as a unix
+    /// timestamp. Identities never synced are left out.
+    pub fn get_identity_last_synced_times(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<HashMap<Identifier, i64>> {
+        self.get_identity_last_synced_times_for_network(&app_context.network_string())
+    }
+
+    fn get_identity_last_synced_times_for_network(
+        &self,
+        network: &str,
+    ) -> rusqlite::Result<HashMap<Identifier, i64>> {
+        let rows = self.query_rows(
+            "SELECT id, last_synced_at FROM identity WHERE network = ? AND last_synced_at IS NOT NULL",
+            params![network],
+            |row| {
+                let id: Vec<u8> = row.get(0)?;
+                let identifier =
+                    Identifier::from_bytes(&id).map_err(|_| rusqlite::Error::InvalidQuery)?;
+                Ok((identifier, row.get(1)?))
+            },
+        )?;
+        Ok(rows.into_iter().collect())
+    }
+
+    pub fn get_favorite_identity_ids(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<Identifier>> {
+        self.query_rows(
+            "SELECT id FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND is_favorite = 1",
+            params![app_context.network_string()],
+            |row| {
+                Identifier::from_bytes(&row.get::<_, Vec<u8>>(0)?).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Blob,
+                        Box::new(e),
+                    )
+                })
+            },
+        )
+    }
+
     /// Creates the identity_order table if it doesn't already exist
     /// with two columns: `pos` (int) and `identity_id` (blob).
     /// pos is the "position" in the custom ordering.
@@ -294,6 +1027,7 @@ impl Database {
     /// Saves the user’s custom identity order (the entire list).
     /// This method overwrites whatever was there before.
     pub fn save_identity_order(&self, all_ids: Vec<Identifier>) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         // Make sure table exists
         self.ensure_identity_order_table_exists()?;
 
@@ -368,3 +1102,630 @@ impl Database {
         Ok(final_list)
     }
 }
+
+/// Number of single character insertions, deletions and substitutions turning `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Keeps the identities that could be decoded. The others are logged with their id and counted,
+/// so a single corrupt row doesn't prevent loading the rest.
+fn skip_undecodable_identities(
+    rows: Vec<(Vec<u8>, Result<QualifiedIdentity, String>)>,
+) -> (Vec<QualifiedIdentity>, usize) {
+    let mut skipped = 0;
+    let identities = rows
+        .into_iter()
+        .filter_map(|(id, identity)| match identity {
+            Ok(identity) => Some(identity),
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping stored identity {}: {}",
+                    bs58::encode(&id).into_string(),
+                    e
+                );
+                skipped += 1;
+                None
+            }
+        })
+        .collect();
+    (identities, skipped)
+}
+
+/// The values written to the identity table for a local identity.
+struct LocalIdentityRow<'a> {
+    id: Vec<u8>,
+    data: Vec<u8>,
+    alias: Option<String>,
+    identity_type: String,
+    wallet_and_identity_id_info: Option<(&'a [u8], u32)>,
+}
+
+impl<'a> LocalIdentityRow<'a> {
+    fn new(
+        qualified_identity: &QualifiedIdentity,
+        wallet_and_identity_id_info: Option<(&'a [u8], u32)>,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Self> {
+        app_context.ensure_writable()?;
+        Ok(LocalIdentityRow {
+            id: qualified_identity.identity.id().to_vec(),
+            data: app_context
+                .identity_stored_bytes(qualified_identity)
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?,
+            alias: qualified_identity.alias.clone(),
+            identity_type: format!("{:?}", qualified_identity.identity_type),
+            wallet_and_identity_id_info,
+        })
+    }
+}
+
+fn insert_local_identity_row(
+    conn: &Connection,
+    row: &LocalIdentityRow,
+    network: &str,
+) -> rusqlite::Result<()> {
+    let LocalIdentityRow {
+        id,
+        data,
+        alias,
+        identity_type,
+        wallet_and_identity_id_info,
+    } = row;
+
+    if let Some((wallet, wallet_index)) = wallet_and_identity_id_info {
+        // If wallet information is provided, insert with wallet and wallet_index
+        conn.execute(
+            "INSERT INTO identity
+             (id, data, is_local, alias, identity_type, network, wallet, wallet_index)
+             VALUES (?, ?, 1, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data, is_local = 1,
+             alias = excluded.alias, identity_type = excluded.identity_type,
+             network = excluded.network, is_deleted = 0, is_in_creation = 0, info = NULL,
+             wallet = excluded.wallet, wallet_index = excluded.wallet_index",
+            params![
+                id,
+                data,
+                alias,
+                identity_type,
+                network,
+                wallet,
+                wallet_index
+            ],
+        )?;
+    } else {
+        // If wallet information is not provided, insert without wallet and wallet_index
+        conn.execute(
+            "INSERT INTO identity
+             (id, data, is_local, alias, identity_type, network)
+             VALUES (?, ?, 1, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET data = excluded.data, is_local = 1,
+             alias = excluded.alias, identity_type = excluded.identity_type,
+             network = excluded.network, is_deleted = 0, is_in_creation = 0, info = NULL,
+             wallet = NULL, wallet_index = NULL",
+            params![id, data, alias, identity_type, network],
+        )?;
+    }
+    record_identity_audit(conn, IdentityAuditAction::Insert, id);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn database_with_identities(identities: &[([u8; 32], Option<&str>, &str)]) -> Database {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        for (id, alias, network) in identities {
+            db.execute(
+                "INSERT INTO identity (id, is_local, alias, network) VALUES (?, 1, ?, ?)",
+                params![id.to_vec(), alias, network],
+            )
+            .expect("expected to insert identity");
+        }
+        db
+    }
+
+    #[test]
+    fn test_set_alias_rejects_alias_taken_on_same_network() {
+        let db = database_with_identities(&[
+            ([1; 32], Some("alice"), "testnet"),
+            ([2; 32], None, "testnet"),
+            ([3; 32], None, "dash"),
+        ]);
+
+        let result = db.set_alias(&Identifier::new([2; 32]), Some("alice"));
+        assert!(matches!(result, Err(SetAliasError::AliasAlreadyTaken(alias)) if alias == "alice"));
+
+        // The same alias on another network is fine
+        db.set_alias(&Identifier::new([3; 32]), Some("alice"))
+            .expect("expected alias to be free on another network");
+        // Setting an identity's alias to its current value is not a collision
+        db.set_alias(&Identifier::new([1; 32]), Some("alice"))
+            .expect("expected to keep own alias");
+    }
+
+    #[test]
+    fn test_set_alias_allows_clearing_aliases() {
+        let db = database_with_identities(&[
+            ([1; 32], Some(""), "testnet"),
+            ([2; 32], Some("bob"), "testnet"),
+        ]);
+
+        db.set_alias(&Identifier::new([2; 32]), Some(""))
+            .expect("expected empty aliases to be exempt");
+        db.set_alias(&Identifier::new([2; 32]), None)
+            .expect("expected to clear alias");
+        db.set_alias(&Identifier::new([1; 32]), Some("bob"))
+            .expect("expected cleared alias to be free again");
+    }
+
+    #[test]
+    fn test_set_alias_is_refused_in_read_only_mode() {
+        let db = database_with_identities(&[([1; 32], Some("alice"), "testnet")]);
+
+        db.set_read_only(true);
+        let result = db.set_alias(&Identifier::new([1; 32]), Some("bob"));
+        assert!(matches!(result, Err(SetAliasError::Database(_))));
+        let aliases = db
+            .query_rows("SELECT alias FROM identity", [], |row| {
+                row.get::<_, Option<String>>(0)
+            })
+            .expect("expected to read aliases");
+        assert_eq!(aliases, vec![Some("alice".to_string())]);
+
+        db.set_read_only(false);
+        db.set_alias(&Identifier::new([1; 32]), Some("bob"))
+            .expect("expected to set alias");
+    }
+
+    #[test]
+    fn test_count_local_qualified_identities_ignores_rows_without_data() {
+        let db = database_with_identities(&[
+            ([1; 32], None, "testnet"),
+            ([2; 32], None, "testnet"),
+            ([3; 32], None, "testnet"),
+            ([4; 32], None, "dash"),
+        ]);
+        db.execute(
+            "UPDATE identity SET data = X'00' WHERE id != ?",
+            params![vec![3u8; 32]],
+        )
+        .unwrap();
+        db.execute(
+            "UPDATE identity SET is_deleted = 1 WHERE id = ?",
+            params![vec![2u8; 32]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            db.count_local_qualified_identities_for_network("testnet")
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.count_local_qualified_identities_for_network("dash")
+                .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_favorite_identities_are_listed_first() {
+        let db = database_with_identities(&[
+            ([1; 32], Some("carol"), "testnet"),
+            ([2; 32], Some("alice"), "testnet"),
+            ([3; 32], None, "testnet"),
+            ([4; 32], Some("bob"), "testnet"),
+        ]);
+        let listed_ids = || {
+            db.query_rows(
+                &format!(
+                    "SELECT id FROM identity WHERE network = 'testnet' {}",
+                    LOCAL_IDENTITIES_ORDER
+                ),
+                [],
+                |row| Ok(row.get::<_, Vec<u8>>(0)?[0]),
+            )
+            .unwrap()
+        };
+        assert_eq!(listed_ids(), vec![2, 4, 1, 3]);
+
+        db.set_identity_favorite_for_network(&Identifier::new([3; 32]), true, "testnet")
+            .unwrap();
+        db.set_identity_favorite_for_network(&Identifier::new([1; 32]), true, "testnet")
+            .unwrap();
+        assert_eq!(listed_ids(), vec![1, 3, 2, 4]);
+
+        // Favorites are per network
+        db.set_identity_favorite_for_network(&Identifier::new([4; 32]), true, "dash")
+            .unwrap();
+        db.set_identity_favorite_for_network(&Identifier::new([3; 32]), false, "testnet")
+            .unwrap();
+        assert_eq!(listed_ids(), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_cancel_identity_in_creation_only_removes_unfinished_identities() {
+        let db =
+            database_with_identities(&[([1; 32], None, "testnet"), ([2; 32], None, "testnet")]);
+        db.execute(
+            "UPDATE identity SET is_in_creation = 1 WHERE id = ?",
+            params![vec![1u8; 32]],
+        )
+        .unwrap();
+
+        assert!(!db
+            .cancel_identity_in_creation_for_network(&Identifier::new([2; 32]), "testnet")
+            .unwrap());
+        assert!(!db
+            .cancel_identity_in_creation_for_network(&Identifier::new([1; 32]), "dash")
+            .unwrap());
+        assert!(db
+            .cancel_identity_in_creation_for_network(&Identifier::new([1; 32]), "testnet")
+            .unwrap());
+
+        let remaining = db
+            .query_rows("SELECT id FROM identity", [], |row| {
+                Ok(row.get::<_, Vec<u8>>(0)?[0])
+            })
+            .unwrap();
+        assert_eq!(remaining, vec![2]);
+    }
+
+    #[test]
+    fn test_skip_undecodable_identities_counts_corrupt_rows() {
+        let rows = vec![
+            (vec![1; 32], QualifiedIdentity::from_bytes(&[0xff, 0x01])),
+            (vec![2; 32], QualifiedIdentity::from_bytes(&[])),
+        ];
+        let (identities, skipped) = skip_undecodable_identities(rows);
+        assert!(identities.is_empty());
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn test_local_identity_summary_aggregates_current_network() {
+        let db = database_with_identities(&[
+            ([1; 32], None, "testnet"),
+            ([2; 32], None, "testnet"),
+            ([3; 32], None, "testnet"),
+            ([4; 32], None, "dash"),
+        ]);
+        db.execute(
+            "UPDATE identity SET identity_type = 'User' WHERE id IN (?, ?)",
+            params![vec![1u8; 32], vec![2u8; 32]],
+        )
+        .unwrap();
+        db.execute(
+            "UPDATE identity SET identity_type = 'Masternode' WHERE id = ?",
+            params![vec![3u8; 32]],
+        )
+        .unwrap();
+        db.insert_top_up(&[1; 32], 0, 1000).unwrap();
+        db.insert_top_up(&[1; 32], 1, 500).unwrap();
+        db.insert_top_up(&[4; 32], 0, 7000).unwrap();
+
+        let summary = db.local_identity_summary_for_network("testnet").unwrap();
+        assert_eq!(summary.total_count(), 3);
+        assert_eq!(summary.count_by_type.get("User"), Some(&2));
+        assert_eq!(summary.count_by_type.get("Masternode"), Some(&1));
+        assert_eq!(summary.total_top_up_amount, 1500);
+        assert_eq!(summary.with_private_keys, 0);
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("alice", "alice"), 0);
+    }
+
+    #[test]
+    fn test_suggest_aliases_ranks_by_edit_distance() {
+        let db = database_with_identities(&[
+            ([1; 32], Some("alice"), "testnet"),
+            ([2; 32], Some("Alicia"), "testnet"),
+            ([3; 32], Some("albert"), "testnet"),
+            ([4; 32], Some("bob"), "testnet"),
+            ([5; 32], Some("alice2"), "dash"),
+        ]);
+        db.execute("UPDATE identity SET data = x'00'", []).unwrap();
+
+        assert_eq!(
+            db.suggest_aliases_for_network("testnet", "Alise", 2)
+                .unwrap(),
+            vec!["alice", "Alicia"]
+        );
+        // No alias starts like the query, all of them are ranked
+        assert_eq!(
+            db.suggest_aliases_for_network("testnet", "cob", 1).unwrap(),
+            vec!["bob"]
+        );
+        assert!(db
+            .suggest_aliases_for_network("regtest", "alice", 3)
+            .unwrap()
+            .is_empty());
+        assert!(db
+            .suggest_aliases_for_network("testnet", " ", 3)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_mark_identity_synced_per_network() {
+        let db = database_with_identities(&[
+            ([1; 32], None, "testnet"),
+            ([2; 32], None, "testnet"),
+            ([3; 32], None, "dash"),
+        ]);
+
+        db.mark_identity_synced_for_network(&Identifier::new([1; 32]), "testnet", 1000)
+            .unwrap();
+        // The identity is not on this network
+        db.mark_identity_synced_for_network(&Identifier::new([3; 32]), "testnet", 1000)
+            .unwrap();
+
+        let synced = db
+            .get_identity_last_synced_times_for_network("testnet")
+            .unwrap();
+        assert_eq!(synced, HashMap::from([(Identifier::new([1; 32]), 1000)]));
+        assert!(db
+            .get_identity_last_synced_times_for_network("dash")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_local_qualified_identity_by_id() {
+        use dash_sdk::dpp::identity::v0::IdentityV0;
+        use dash_sdk::dpp::identity::Identity;
+
+        let db = database_with_identities(&[]);
+        let mut qualified_identity = QualifiedIdentity::from(Identity::V0(IdentityV0 {
+            id: Identifier::new([1; 32]),
+            public_keys: BTreeMap::new(),
+            balance: 5,
+            revision: 0,
+        }));
+        qualified_identity.alias = Some("stored".to_string());
+        db.execute(
+            "INSERT INTO identity (id, data, is_local, alias, network, wallet_index)
+             VALUES (?, ?, 1, 'stored', 'testnet', 3)",
+            params![vec![1u8; 32], qualified_identity.to_bytes()],
+        )
+        .unwrap();
+        db.execute(
+            "INSERT INTO top_up (identity_id, top_up_index, amount, created_at) VALUES (?, 0, 100, 1000)",
+            params![vec![1u8; 32]],
+        )
+        .unwrap();
+        let wallets = BTreeMap::new();
+
+        let identity = db
+            .get_local_qualified_identity_for_network(
+                &Identifier::new([1; 32]),
+                "testnet",
+                None,
+                &wallets,
+            )
+            .unwrap()
+            .expect("expected the identity to be found");
+        assert_eq!(identity.identity.balance(), 5);
+        assert_eq!(identity.alias.as_deref(), Some("stored"));
+        assert_eq!(identity.wallet_index, Some(3));
+        assert_eq!(
+            identity.top_ups.get(&0).map(|top_up| top_up.amount),
+            Some(100)
+        );
+
+        // Unknown id, and a known id looked up on another network
+        assert!(db
+            .get_local_qualified_identity_for_network(
+                &Identifier::new([2; 32]),
+                "testnet",
+                None,
+                &wallets,
+            )
+            .unwrap()
+            .is_none());
+        assert!(db
+            .get_local_qualified_identity_for_network(
+                &Identifier::new([1; 32]),
+                "dash",
+                None,
+                &wallets,
+            )
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_insert_local_identity_rows_rolls_back_on_error() {
+        let db = database_with_identities(&[]);
+        let row = |id: u8, wallet_and_identity_id_info| LocalIdentityRow {
+            id: vec![id; 32],
+            data: vec![id],
+            alias: None,
+            identity_type: "User".to_string(),
+            wallet_and_identity_id_info,
+        };
+        let count = || {
+            db.query_rows("SELECT id FROM identity", [], |row| {
+                row.get::<_, Vec<u8>>(0)
+            })
+            .unwrap()
+            .len()
+        };
+
+        // The second identity points at a wallet that doesn't exist
+        let missing_wallet = [9u8; 32];
+        assert!(db
+            .insert_local_identity_rows_for_network(
+                &[row(1, None), row(2, Some((&missing_wallet[..], 0)))],
+                "testnet",
+            )
+            .is_err());
+        assert_eq!(count(), 0);
+
+        db.insert_local_identity_rows_for_network(&[row(1, None), row(2, None)], "testnet")
+            .expect("expected to insert identities");
+        assert_eq!(count(), 2);
+    }
+
+    #[test]
+    fn test_import_aliases_skips_unknown_ids_and_taken_aliases() {
+        let db = database_with_identities(&[
+            ([1; 32], None, "testnet"),
+            ([2; 32], Some("bob"), "testnet"),
+            ([3; 32], Some("carol"), "testnet"),
+            ([4; 32], None, "dash"),
+        ]);
+        let entry =
+            |id: u8, alias: Option<&str>| (Identifier::new([id; 32]), alias.map(String::from));
+
+        let outcomes = db
+            .import_aliases_for_network(
+                &[
+                    entry(1, Some("alice")),
+                    entry(2, Some("bob")),
+                    entry(3, Some("alice")),
+                    entry(4, Some("dave")),
+                    entry(5, Some("eve")),
+                    entry(3, None),
+                ],
+                "testnet",
+            )
+            .expect("expected the import to run");
+
+        assert_eq!(
+            outcomes,
+            vec![
+                AliasImportOutcome::Set,
+                AliasImportOutcome::Unchanged,
+                AliasImportOutcome::AliasTaken("alice".to_string()),
+                // Only on another network
+                AliasImportOutcome::UnknownIdentity,
+                AliasImportOutcome::UnknownIdentity,
+                AliasImportOutcome::Set,
+            ]
+        );
+        let alias_of = |id: u8| -> Option<String> {
+            db.conn
+                .lock()
+                .unwrap()
+                .query_row(
+                    "SELECT alias FROM identity WHERE id = ?",
+                    params![vec![id; 32]],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        assert_eq!(alias_of(1).as_deref(), Some("alice"));
+        assert_eq!(alias_of(3), None);
+        assert_eq!(alias_of(4), None);
+        let unknown: bool = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT EXISTS(SELECT 1 FROM identity WHERE id = ?)",
+                params![vec![5u8; 32]],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert!(!unknown);
+    }
+
+    #[test]
+    fn test_encrypt_identity_private_keys_rewrites_only_clear_keys() {
+        use crate::model::qualified_identity::PrivateKeyTarget;
+        use dash_sdk::dpp::identity::identity_public_key::v0::IdentityPublicKeyV0;
+        use dash_sdk::dpp::identity::v0::IdentityV0;
+        use dash_sdk::dpp::identity::{
+            Identity, IdentityPublicKey, KeyType, Purpose, SecurityLevel,
+        };
+
+        let db = database_with_identities(&[]);
+        let mut with_key = QualifiedIdentity::from(Identity::V0(IdentityV0 {
+            id: Identifier::new([1; 32]),
+            public_keys: BTreeMap::new(),
+            balance: 0,
+            revision: 0,
+        }));
+        let without_key = with_key.clone();
+        let public_key = IdentityPublicKeyV0 {
+            id: 0,
+            purpose: Purpose::AUTHENTICATION,
+            security_level: SecurityLevel::MASTER,
+            contract_bounds: None,
+            key_type: KeyType::ECDSA_SECP256K1,
+            read_only: false,
+            data: vec![2; 33].into(),
+            disabled_at: None,
+        };
+        let key = (PrivateKeyTarget::PrivateKeyOnMainIdentity, 0);
+        with_key.private_keys.insert_non_encrypted(
+            key.clone(),
+            (IdentityPublicKey::V0(public_key).into(), [9; 32]),
+        );
+        // A deleted identity on another network is encrypted too
+        db.execute(
+            "INSERT INTO identity (id, data, is_local, network, is_deleted) VALUES (?, ?, 1, 'dash', 1)",
+            params![vec![1u8; 32], with_key.to_bytes()],
+        )
+        .unwrap();
+        db.execute(
+            "INSERT INTO identity (id, data, is_local, network) VALUES (?, ?, 1, 'testnet')",
+            params![vec![2u8; 32], without_key.to_bytes()],
+        )
+        .unwrap();
+        let stored = |id: u8| {
+            db.query_rows(
+                "SELECT data FROM identity WHERE id = ?",
+                params![vec![id; 32]],
+                |row| row.get::<_, Vec<u8>>(0),
+            )
+            .unwrap()
+            .remove(0)
+        };
+        let unchanged = stored(2);
+
+        let encryption_key = [5u8; 32];
+        assert_eq!(
+            db.encrypt_identity_private_keys(&encryption_key).unwrap(),
+            1
+        );
+        let encrypted = QualifiedIdentity::from_bytes(&stored(1)).unwrap();
+        assert!(!encrypted.private_keys.has_clear_keys());
+        assert!(encrypted.private_keys.get(&key).is_err());
+        let decrypted =
+            QualifiedIdentity::from_stored_bytes(&stored(1), Some(&encryption_key)).unwrap();
+        assert_eq!(
+            decrypted.private_keys.get(&key).unwrap().unwrap().1,
+            [9; 32]
+        );
+        assert_eq!(stored(2), unchanged);
+
+        // Nothing is left to encrypt, and the user changed nothing worth auditing
+        assert_eq!(
+            db.encrypt_identity_private_keys(&encryption_key).unwrap(),
+            0
+        );
+        assert!(db.get_audit_log(10).unwrap().is_empty());
+    }
+}
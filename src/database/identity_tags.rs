@@ -0,0 +1,137 @@
+use crate::context::AppContext;
+use crate::database::Database;
+use crate::model::qualified_identity::QualifiedIdentity;
+use dash_sdk::platform::Identifier;
+use rusqlite::params;
+
+/// Tags are compared without regard to case or surrounding whitespace.
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+impl Database {
+    pub fn initialize_identity_tags_table(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS identity_tags (
+                identity_id BLOB NOT NULL,
+                network TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (identity_id, tag),
+                FOREIGN KEY (identity_id) REFERENCES identity(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        self.execute(
+            "CREATE INDEX IF NOT EXISTS idx_identity_tags_network_tag
+             ON identity_tags (network, tag)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Tags an identity. Adding a tag the identity already has does nothing, empty tags are
+    /// ignored.
+    pub fn add_identity_tag(
+        &self,
+        identifier: &Identifier,
+        tag: &str,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        let tag = normalize_tag(tag);
+        if tag.is_empty() {
+            return Ok(());
+        }
+        self.execute(
+            "INSERT OR IGNORE INTO identity_tags (identity_id, network, tag) VALUES (?, ?, ?)",
+            params![identifier.to_vec(), app_context.network_string(), tag],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_identity_tag(&self, identifier: &Identifier, tag: &str) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "DELETE FROM identity_tags WHERE identity_id = ? AND tag = ?",
+            params![identifier.to_vec(), normalize_tag(tag)],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_identity_tags(&self, identifier: &Identifier) -> rusqlite::Result<Vec<String>> {
+        self.query_rows(
+            "SELECT tag FROM identity_tags WHERE identity_id = ? ORDER BY tag",
+            params![identifier.to_vec()],
+            |row| row.get(0),
+        )
+    }
+
+    /// Every tag used by a local identity of the current network.
+    pub fn get_all_identity_tags(&self, app_context: &AppContext) -> rusqlite::Result<Vec<String>> {
+        self.query_rows(
+            "SELECT DISTINCT identity_tags.tag FROM identity_tags
+             JOIN identity ON identity.id = identity_tags.identity_id
+             WHERE identity_tags.network = ? AND identity.is_local = 1 AND identity.is_deleted = 0
+             ORDER BY identity_tags.tag",
+            params![app_context.network_string()],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn list_identities_by_tag(
+        &self,
+        tag: &str,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<QualifiedIdentity>> {
+        let wallets = app_context.wallets.read().unwrap();
+        self.query_local_qualified_identities(
+            app_context,
+            &wallets,
+            "SELECT id, data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ?1 AND data IS NOT NULL
+             AND id IN (SELECT identity_id FROM identity_tags WHERE network = ?1 AND tag = ?2)",
+            params![app_context.network_string(), normalize_tag(tag)],
+        )
+        .map(|(identities, _)| identities)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_identity_tags_are_normalized_and_deduplicated() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'testnet')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+        let identifier = Identifier::new([1; 32]);
+
+        for tag in ["Cold", " cold ", "voting"] {
+            db.execute(
+                "INSERT OR IGNORE INTO identity_tags (identity_id, network, tag) VALUES (?, 'testnet', ?)",
+                params![identifier.to_vec(), normalize_tag(tag)],
+            )
+            .expect("expected to tag identity");
+        }
+        assert_eq!(
+            db.get_identity_tags(&identifier).unwrap(),
+            vec!["cold".to_string(), "voting".to_string()]
+        );
+
+        db.remove_identity_tag(&identifier, "VOTING").unwrap();
+        assert_eq!(
+            db.get_identity_tags(&identifier).unwrap(),
+            vec!["cold".to_string()]
+        );
+
+        // Tags go away with their identity
+        db.execute("DELETE FROM identity", []).unwrap();
+        assert!(db.get_identity_tags(&identifier).unwrap().is_empty());
+    }
+}
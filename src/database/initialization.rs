@@ -1,10 +1,10 @@
-use crate::database::Database;
+use crate::database::{open_connection, Database};
 use chrono::Utc;
 use rusqlite::{params, Connection};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub const DEFAULT_DB_VERSION: u16 = 5;
+pub const DEFAULT_DB_VERSION: u16 = 20;
 
 pub const DEFAULT_NETWORK: &str = "dash";
 
@@ -34,6 +34,52 @@ impl Database {
 
     fn apply_version_changes(&self, version: u16) -> rusqlite::Result<()> {
         match version {
+            20 => {
+                self.initialize_alias_history_table()?;
+            }
+            19 => {
+                self.add_identity_last_synced_at_column()?;
+            }
+            18 => {
+                self.add_theme_mode_column()?;
+            }
+            17 => {
+                self.initialize_audit_log_table()?;
+            }
+            16 => {
+                self.normalize_network_columns()?;
+            }
+            15 => {
+                self.add_identity_is_favorite_column()?;
+            }
+            14 => {
+                self.add_dash_price_columns()?;
+            }
+            13 => {
+                self.initialize_identity_tags_table()?;
+            }
+            12 => {
+                self.add_identity_is_deleted_column()?;
+            }
+            11 => {
+                self.initialize_vote_rules_tables()?;
+                self.add_auto_vote_fully_automatic_column()?;
+            }
+            10 => {
+                self.add_identity_network_wallet_index()?;
+            }
+            9 => {
+                self.add_top_up_created_at_column()?;
+            }
+            8 => {
+                self.add_identities_column_widths_column()?;
+            }
+            7 => {
+                self.add_auto_refresh_on_open_column()?;
+            }
+            6 => {
+                self.add_table_display_columns()?;
+            }
             5 => {
                 self.initialize_scheduled_votes_table()?;
             }
@@ -57,8 +103,21 @@ impl Database {
         to_version: u16,
     ) -> rusqlite::Result<()> {
         for version in (original_version + 1)..=to_version {
-            self.apply_version_changes(version)?;
-            self.update_database_version(version)?;
+            // Each step is applied together with its version bump, so a step that fails
+            // halfway leaves the database at the previous version rather than half migrated.
+            self.begin_transaction()?;
+            let result = self
+                .apply_version_changes(version)
+                .and_then(|_| self.update_database_version(version));
+            match result {
+                Ok(()) => {
+                    self.commit_transaction()?;
+                }
+                Err(e) => {
+                    self.rollback_transaction()?;
+                    return Err(e);
+                }
+            }
         }
         Ok(())
     }
@@ -122,29 +181,47 @@ impl Database {
             let backup_filename = format!("data_backup_{}.db", timestamp);
             let backup_path = backups_dir.join(backup_filename);
 
-            // Copy `data.db` to the unique backup file
-            fs::copy(db_file_path, &backup_path)
-                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+            // Copying `data.db` would miss what is still in the write-ahead log, so SQLite
+            // writes a consistent copy instead
+            let backup_path_str = backup_path.to_str().ok_or_else(|| {
+                rusqlite::Error::ToSqlConversionFailure(
+                    format!("Invalid db backup path: {:?}", backup_path).into(),
+                )
+            })?;
+            self.execute("VACUUM INTO ?", params![backup_path_str])?;
             println!("Old database backed up to {:?}", backup_path);
         }
 
         Ok(())
     }
 
-    /// Recreates `data.db`, and refreshes the connection.
+    /// Recreates `data.db`, and refreshes the connections.
     fn recreate_db(&self, db_file_path: &Path) -> rusqlite::Result<()> {
-        // Remove the existing database file if it exists
-        if db_file_path.exists() {
-            fs::remove_file(db_file_path).map_err(|e| {
-                rusqlite::Error::FromSqlConversionFailure(
-                    0,
-                    rusqlite::types::Type::Text,
-                    Box::new(e),
-                )
-            })?;
+        // Close every connection to the old file first, so none of them keeps using it or
+        // writes its log back next to the new one
+        let mut conn_lock = self.conn.lock().unwrap();
+        *conn_lock = Connection::open_in_memory()?;
+        for reader in &self.readers {
+            *reader.lock().unwrap() = Connection::open_in_memory()?;
+        }
+
+        // Remove the existing database file and its write-ahead log if they exist
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = db_file_path.as_os_str().to_owned();
+            path.push(suffix);
+            let path = PathBuf::from(path);
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        Box::new(e),
+                    )
+                })?;
+            }
         }
         // Create a new empty `data.db` file and set up the initial schema
-        let new_conn = Connection::open(db_file_path)?;
+        let new_conn = open_connection(db_file_path)?;
 
         // Initialize the `settings` table in the new database
         new_conn.execute(
@@ -168,8 +245,9 @@ impl Database {
         )?;
 
         // Update the connection in `self.conn` to use the new `data.db` file
-        let mut conn_lock = self.conn.lock().unwrap();
         *conn_lock = new_conn;
+        drop(conn_lock);
+        self.reopen_read_connections(db_file_path)?;
 
         Ok(())
     }
@@ -187,6 +265,14 @@ impl Database {
             start_root_screen INTEGER NOT NULL,
             custom_dash_qt_path TEXT,
             overwrite_dash_conf INTEGER,
+            table_font_size REAL,
+            monospace_ids INTEGER,
+            identities_auto_refresh_on_open INTEGER,
+            identities_column_widths TEXT,
+            auto_vote_fully_automatic INTEGER,
+            dash_price REAL,
+            dash_price_currency TEXT,
+            theme_mode INTEGER,
             database_version INTEGER NOT NULL
         )",
             [],
@@ -284,6 +370,9 @@ impl Database {
                         wallet_index INTEGER,
                         identity_type TEXT,
                         network TEXT NOT NULL,
+                        is_deleted INTEGER NOT NULL DEFAULT 0,
+                        is_favorite INTEGER NOT NULL DEFAULT 0,
+                        last_synced_at INTEGER,
                         CHECK ((wallet IS NOT NULL AND wallet_index IS NOT NULL) OR (wallet IS NULL AND wallet_index IS NULL)),
                         FOREIGN KEY (wallet) REFERENCES wallet(seed_hash) ON DELETE CASCADE
                     )",
@@ -296,6 +385,7 @@ impl Database {
              ON identity (is_local, network, identity_type)",
             [],
         )?;
+        self.add_identity_network_wallet_index()?;
 
         // Create the contested names table
         self.execute(
@@ -351,7 +441,12 @@ impl Database {
 
         self.initialize_proof_log_table()?;
         self.initialize_top_up_table()?;
+        self.add_top_up_created_at_column()?;
         self.initialize_scheduled_votes_table()?;
+        self.initialize_vote_rules_tables()?;
+        self.initialize_identity_tags_table()?;
+        self.initialize_audit_log_table()?;
+        self.initialize_alias_history_table()?;
 
         Ok(())
     }
@@ -367,3 +462,137 @@ impl Database {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database_version(db: &Database) -> u16 {
+        db.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT database_version FROM settings WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("expected a database version")
+    }
+
+    #[test]
+    fn test_migrates_version_11_database_forward() {
+        let path = Path::new(":memory:");
+        let db = Database::new(path).expect("expected to open database");
+        db.initialize(path).expect("expected to create tables");
+
+        // Turn the database into a version 11 one, from before identities could be soft-deleted
+        db.execute("DROP TABLE identity", [])
+            .expect("expected to drop identity table");
+        db.execute(
+            "CREATE TABLE identity (
+                id BLOB PRIMARY KEY,
+                data BLOB,
+                is_in_creation INTEGER NOT NULL DEFAULT 0,
+                is_local INTEGER NOT NULL,
+                alias TEXT,
+                info TEXT,
+                wallet BLOB,
+                wallet_index INTEGER,
+                identity_type TEXT,
+                network TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("expected to create version 11 identity table");
+        // Remove what later versions add outside of the identity table
+        for table in ["identity_tags", "audit_log", "alias_history"] {
+            db.execute(&format!("DROP TABLE {}", table), [])
+                .expect("expected to drop table");
+        }
+        for column in ["dash_price", "dash_price_currency", "theme_mode"] {
+            db.execute(&format!("ALTER TABLE settings DROP COLUMN {}", column), [])
+                .expect("expected to drop settings column");
+        }
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'testnet')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+        db.update_database_version(11)
+            .expect("expected to set version");
+
+        db.initialize(path).expect("expected to migrate");
+        assert_eq!(database_version(&db), DEFAULT_DB_VERSION);
+        let is_deleted: bool = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT is_deleted FROM identity", [], |row| row.get(0))
+            .expect("expected migrated column");
+        assert!(!is_deleted);
+
+        // Opening a fully migrated database again changes nothing
+        db.initialize(path).expect("expected no-op initialization");
+        assert_eq!(database_version(&db), DEFAULT_DB_VERSION);
+    }
+
+    #[test]
+    fn test_failed_migration_step_is_rolled_back() {
+        let path = Path::new(":memory:");
+        let db = Database::new(path).expect("expected to open database");
+        db.initialize(path).expect("expected to create tables");
+        db.update_database_version(11)
+            .expect("expected to set version");
+
+        // Version 12 adds a column that already exists, so it fails
+        assert!(db.try_perform_migration(11, DEFAULT_DB_VERSION).is_err());
+        assert_eq!(database_version(&db), 11);
+        // No transaction is left open
+        assert!(db.conn.lock().unwrap().is_autocommit());
+    }
+
+    #[test]
+    fn test_backup_and_recreate_wal_database() {
+        let dir = std::env::temp_dir().join(format!(
+            "dash-evo-tool-recreate-test-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("expected to create test directory");
+        let path = dir.join("data.db");
+        let db = Database::new(&path).expect("expected to open database");
+        db.initialize(&path).expect("expected to create tables");
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'dash')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert identity");
+
+        // The backup holds the row even though it may still only be in the write-ahead log
+        db.backup_db(&path).expect("expected to back up");
+        let backup = fs::read_dir(dir.join("backups"))
+            .expect("expected backups directory")
+            .next()
+            .expect("expected a backup")
+            .expect("expected a backup entry")
+            .path();
+        let backed_up: i64 = Connection::open(&backup)
+            .expect("expected to open backup")
+            .query_row("SELECT COUNT(*) FROM identity", [], |row| row.get(0))
+            .expect("expected to count backed up identities");
+        assert_eq!(backed_up, 1);
+
+        // Readers and the writer both see the new, empty database
+        db.recreate_db(&path).expect("expected to recreate");
+        db.create_tables().expect("expected to create tables");
+        let identities = db
+            .query_rows("SELECT id FROM identity", [], |row| {
+                row.get::<_, Vec<u8>>(0)
+            })
+            .expect("expected to read identities");
+        assert!(identities.is_empty());
+        assert_eq!(database_version(&db), DEFAULT_DB_VERSION);
+
+        drop(db);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
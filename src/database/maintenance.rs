@@ -0,0 +1,266 @@
+use crate::context::AppContext;
+use crate::database::Database;
+use dash_sdk::dpp::data_contract::accessors::v0::DataContractV0Getters;
+use rusqlite::params;
+use std::collections::HashSet;
+
+/// The identity loader queries whose plans are reported by `analyze_and_reindex`.
+const IDENTITY_LOADER_QUERIES: [(&str, &str); 4] = [
+    (
+        "Local identities",
+        "SELECT data, alias, wallet_index FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND data IS NOT NULL",
+    ),
+    (
+        "Local masternode identities",
+        "SELECT data FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND identity_type != 'User' AND data IS NOT NULL",
+    ),
+    (
+        "Local user identities",
+        "SELECT data FROM identity WHERE is_local = 1 AND is_deleted = 0 AND network = ? AND identity_type = 'User' AND data IS NOT NULL",
+    ),
+    (
+        "Wallet identities",
+        "SELECT data, wallet, wallet_index FROM identity WHERE network = ? AND wallet IS NOT NULL AND wallet_index IS NOT NULL",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlanReport {
+    pub query_name: &'static str,
+    pub plan_before: String,
+    pub plan_after: String,
+}
+
+impl QueryPlanReport {
+    pub fn changed(&self) -> bool {
+        self.plan_before != self.plan_after
+    }
+}
+
+impl Database {
+    /// Indexes identities by wallet so loading a wallet's identities, and cascading wallet
+    /// deletes, don't scan the whole table.
+    pub fn add_identity_network_wallet_index(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "CREATE INDEX IF NOT EXISTS idx_identity_network_wallet
+             ON identity (network, wallet)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    fn identity_loader_query_plans(&self, network: &str) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        IDENTITY_LOADER_QUERIES
+            .iter()
+            .map(|(_, sql)| {
+                let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+                let details = stmt
+                    .query_map(params![network], |row| row.get::<_, String>(3))?
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok(details.join("; "))
+            })
+            .collect()
+    }
+
+    /// Refreshes the query planner statistics and rebuilds all indexes, returning the plans
+    /// of the identity loader queries before and after.
+    pub fn analyze_and_reindex(&self, network: &str) -> rusqlite::Result<Vec<QueryPlanReport>> {
+        self.ensure_writable()?;
+        let plans_before = self.identity_loader_query_plans(network)?;
+
+        {
+            let conn = self.conn.lock().unwrap();
+            conn.execute_batch("ANALYZE; REINDEX;")?;
+        }
+
+        let plans_after = self.identity_loader_query_plans(network)?;
+
+        Ok(IDENTITY_LOADER_QUERIES
+            .iter()
+            .zip(plans_before.into_iter().zip(plans_after))
+            .map(
+                |((query_name, _), (plan_before, plan_after))| QueryPlanReport {
+                    query_name,
+                    plan_before,
+                    plan_after,
+                },
+            )
+            .collect())
+    }
+
+    /// Deletes the remote identities of the current network that no contestant, awarded
+    /// contested name, scheduled vote, asset lock or stored contract refers to. Local
+    /// identities are never removed. Returns the number of identities deleted.
+    pub fn prune_orphan_remote_identities(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<usize> {
+        self.ensure_writable()?;
+        // The contract table has no owner column, so the owners are read from the contracts
+        let contract_owners: HashSet<Vec<u8>> = self
+            .get_contracts(app_context, None, None)?
+            .iter()
+            .map(|contract| contract.contract.owner_id().to_vec())
+            .collect();
+        self.prune_orphan_remote_identities_for_network(
+            &app_context.network_string(),
+            &contract_owners,
+        )
+    }
+
+    fn prune_orphan_remote_identities_for_network(
+        &self,
+        network: &str,
+        contract_owners: &HashSet<Vec<u8>>,
+    ) -> rusqlite::Result<usize> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let orphan_ids = {
+            let mut stmt = tx.prepare(
+                "SELECT id FROM identity WHERE is_local = 0 AND network = ?1
+                 AND id NOT IN (SELECT identity_id FROM contestant WHERE network = ?1)
+                 AND id NOT IN (SELECT awarded_to FROM contested_name WHERE network = ?1 AND awarded_to IS NOT NULL)
+                 AND id NOT IN (SELECT identity_id FROM scheduled_votes WHERE network = ?1)
+                 AND id NOT IN (SELECT identity_id FROM asset_lock_transaction WHERE identity_id IS NOT NULL)
+                 AND id NOT IN (SELECT identity_id_potentially_in_creation FROM asset_lock_transaction
+                                WHERE identity_id_potentially_in_creation IS NOT NULL)",
+            )?;
+            let ids = stmt
+                .query_map(params![network], |row| row.get::<_, Vec<u8>>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            ids
+        };
+
+        let mut deleted = 0;
+        for id in orphan_ids
+            .iter()
+            .filter(|id| !contract_owners.contains(*id))
+        {
+            deleted += tx.execute(
+                "DELETE FROM identity WHERE id = ? AND network = ?",
+                params![id, network],
+            )?;
+        }
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// Runs `PRAGMA integrity_check`, returning whether the database is intact.
+    pub fn integrity_check(&self) -> rusqlite::Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        Ok(result == "ok")
+    }
+
+    /// Size of the database in bytes, counted in pages so it reflects what `vacuum` reclaims.
+    pub fn size_in_bytes(&self) -> rusqlite::Result<u64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    /// Rebuilds the database file without its free pages. `VACUUM` fails inside a transaction,
+    /// so the writing connection is held for the whole run and an open transaction is refused.
+    pub fn vacuum(&self) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        let conn = self.conn.lock().unwrap();
+        if !conn.is_autocommit() {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISUSE),
+                Some("Cannot vacuum while a transaction is open".to_string()),
+            ));
+        }
+        conn.execute_batch("VACUUM")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_prune_orphan_remote_identities() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        for (id, is_local, network) in [
+            (1u8, 1, "testnet"),
+            (2, 0, "testnet"),
+            (3, 0, "testnet"),
+            (4, 0, "testnet"),
+            (5, 0, "dash"),
+            (6, 0, "testnet"),
+        ] {
+            db.execute(
+                "INSERT INTO identity (id, is_local, network) VALUES (?, ?, ?)",
+                params![vec![id; 32], is_local, network],
+            )
+            .expect("expected to insert identity");
+        }
+        db.execute(
+            "INSERT INTO contested_name (normalized_contested_name, awarded_to, network)
+             VALUES ('name', ?, 'testnet')",
+            params![vec![3u8; 32]],
+        )
+        .expect("expected to insert contested name");
+        db.execute(
+            "INSERT INTO contestant (normalized_contested_name, identity_id, network)
+             VALUES ('name', ?, 'testnet')",
+            params![vec![4u8; 32]],
+        )
+        .expect("expected to insert contestant");
+
+        // Identity 6 owns a stored contract
+        let contract_owners = HashSet::from([vec![6u8; 32]]);
+
+        assert_eq!(
+            db.prune_orphan_remote_identities_for_network("testnet", &contract_owners)
+                .expect("expected to prune identities"),
+            1
+        );
+
+        let remaining = db
+            .query_rows("SELECT id FROM identity ORDER BY id", [], |row| {
+                row.get::<_, Vec<u8>>(0)
+            })
+            .unwrap();
+        assert_eq!(
+            remaining,
+            vec![
+                vec![1u8; 32],
+                vec![3; 32],
+                vec![4; 32],
+                vec![5; 32],
+                vec![6; 32]
+            ]
+        );
+
+        // Nothing is left to prune
+        assert_eq!(
+            db.prune_orphan_remote_identities_for_network("testnet", &contract_owners)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_integrity_check_and_vacuum() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        assert!(db.integrity_check().expect("expected to check integrity"));
+
+        db.execute("BEGIN", []).unwrap();
+        assert!(db.vacuum().is_err());
+        db.execute("COMMIT", []).unwrap();
+
+        let size_before = db.size_in_bytes().expect("expected a database size");
+        db.vacuum().expect("expected to vacuum");
+        assert!(db.size_in_bytes().unwrap() <= size_before);
+    }
+}
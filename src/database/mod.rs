@@ -1,33 +1,298 @@
+mod alias_history;
 mod asset_lock_transaction;
+mod audit_log;
 mod contested_names;
 mod contracts;
 mod identities;
+mod identity_tags;
 mod initialization;
+mod maintenance;
+mod networks;
 mod proof_log;
 mod scheduled_votes;
 mod settings;
+mod state_archive;
 mod top_ups;
 mod utxo;
+mod vote_rules;
 mod wallet;
 
-use rusqlite::{Connection, Params};
-use std::sync::Mutex;
+pub use alias_history::AliasChange;
+pub use audit_log::{IdentityAuditAction, IdentityAuditEntry};
+pub use identities::{AliasImportOutcome, IdentitySummary, SetAliasError};
+pub use maintenance::QueryPlanReport;
+pub use networks::{network_db_name, network_from_db_name};
+use rusqlite::{Connection, OpenFlags, Params, Row};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+/// Number of read-only connections kept next to the writing one.
+const READ_CONNECTIONS: usize = 4;
+
+pub const READ_ONLY_MODE_ERROR: &str =
+    "The app is in read-only mode, turn it off in the settings to make changes";
 
 #[derive(Debug)]
 pub struct Database {
     conn: Mutex<Connection>,
+    /// Read-only connections used by [`Database::query_rows`], so reads don't wait on each other
+    /// or on a write. Empty for in-memory databases, which can't be shared between connections.
+    readers: Vec<Mutex<Connection>>,
+    next_reader: AtomicUsize,
+    /// When set, writes made on behalf of the user fail with [`READ_ONLY_MODE_ERROR`]. Shared by
+    /// every network, since they all use this database.
+    read_only: AtomicBool,
+    /// Thread that opened the transaction running on the writing connection with
+    /// [`Database::begin_transaction`], whose reads must see its uncommitted changes.
+    transaction_owner: Mutex<Option<ThreadId>>,
 }
 
 impl Database {
-    pub fn new<P: AsRef<std::path::Path>>(path: P) -> rusqlite::Result<Self> {
-        let conn = Connection::open(path)?;
+    pub fn new<P: AsRef<Path>>(path: P) -> rusqlite::Result<Self> {
+        let conn = open_connection(&path)?;
+        let readers = open_read_connections(path.as_ref())?
+            .into_iter()
+            .map(Mutex::new)
+            .collect();
         Ok(Self {
             conn: Mutex::new(conn),
+            readers,
+            next_reader: AtomicUsize::new(0),
+            read_only: AtomicBool::new(false),
+            transaction_owner: Mutex::new(None),
         })
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed)
+    }
+
+    /// Fails with an error explaining the app is in read-only mode, if it is.
+    pub fn ensure_writable(&self) -> rusqlite::Result<()> {
+        if self.is_read_only() {
+            return Err(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+                Some(READ_ONLY_MODE_ERROR.to_string()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Points the read connections at the file at `path`, after it was replaced.
+    fn reopen_read_connections(&self, path: &Path) -> rusqlite::Result<()> {
+        for (reader, conn) in self.readers.iter().zip(open_read_connections(path)?) {
+            *reader.lock().unwrap() = conn;
+        }
+        Ok(())
+    }
+
+    /// Opens a transaction on the writing connection that spans the following calls made from
+    /// this thread, until [`Database::commit_transaction`] or
+    /// [`Database::rollback_transaction`]. Reads of this thread see its uncommitted changes,
+    /// other threads keep reading the last committed state.
+    pub fn begin_transaction(&self) -> rusqlite::Result<()> {
+        self.execute("BEGIN", [])?;
+        *self.transaction_owner.lock().unwrap() = Some(thread::current().id());
+        Ok(())
+    }
+
+    pub fn commit_transaction(&self) -> rusqlite::Result<()> {
+        self.execute("COMMIT", [])?;
+        *self.transaction_owner.lock().unwrap() = None;
+        Ok(())
+    }
+
+    pub fn rollback_transaction(&self) -> rusqlite::Result<()> {
+        let result = self.execute("ROLLBACK", []);
+        *self.transaction_owner.lock().unwrap() = None;
+        result.map(|_| ())
+    }
+
+    /// Returns a free read connection if there is one, otherwise waits for the next one in
+    /// turn. Falls back to the writing connection when there are no read connections, or for
+    /// the thread that opened a transaction on it, since read connections can't see its
+    /// uncommitted changes.
+    fn read_connection(&self) -> MutexGuard<'_, Connection> {
+        if self.readers.is_empty() {
+            return self.conn.lock().unwrap();
+        }
+        if *self.transaction_owner.lock().unwrap() == Some(thread::current().id()) {
+            return self.conn.lock().unwrap();
+        }
+        let start = self.next_reader.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.readers.len() {
+            if let Ok(conn) = self.readers[(start + offset) % self.readers.len()].try_lock() {
+                return conn;
+            }
+        }
+        self.readers[start % self.readers.len()].lock().unwrap()
+    }
+
     pub fn execute<P: Params>(&self, sql: &str, params: P) -> rusqlite::Result<usize> {
         let conn = self.conn.lock().unwrap();
         conn.execute(sql, params)
     }
+
+    /// Runs a query and maps every returned row with `f`.
+    pub fn query_rows<T, P, F>(&self, sql: &str, params: P, f: F) -> rusqlite::Result<Vec<T>>
+    where
+        P: Params,
+        F: FnMut(&Row<'_>) -> rusqlite::Result<T>,
+    {
+        let conn = self.read_connection();
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt.query_map(params, f)?.collect();
+        rows
+    }
+}
+
+/// Opens a connection with the settings every connection of the app should use.
+fn open_connection<P: AsRef<Path>>(path: P) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    // WAL lets readers proceed while a write is in progress, and the busy timeout makes
+    // SQLite wait for a lock instead of failing right away with SQLITE_BUSY.
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| {
+        row.get::<_, String>(0)
+    })?;
+    conn.pragma_update(None, "busy_timeout", 5000)?;
+    conn.pragma_update(None, "foreign_keys", "ON")?;
+    Ok(conn)
+}
+
+fn open_read_connections(path: &Path) -> rusqlite::Result<Vec<Connection>> {
+    if path.as_os_str().is_empty() || path == Path::new(":memory:") {
+        return Ok(Vec::new());
+    }
+    (0..READ_CONNECTIONS)
+        .map(|_| {
+            let conn = Connection::open_with_flags(
+                path,
+                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+            )?;
+            conn.pragma_update(None, "busy_timeout", 5000)?;
+            Ok(conn)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_enables_wal_mode() {
+        let path = std::env::temp_dir()
+            .join(format!("dash-evo-tool-wal-test-{}.db", std::process::id()));
+
+        for _ in 0..2 {
+            let db = Database::new(&path).expect("expected to open database");
+            let conn = db.conn.lock().unwrap();
+            let journal_mode: String = conn
+                .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+                .expect("expected to read journal mode");
+            assert_eq!(journal_mode.to_lowercase(), "wal");
+            let foreign_keys: bool = conn
+                .query_row("PRAGMA foreign_keys", [], |row| row.get(0))
+                .expect("expected to read foreign keys setting");
+            assert!(foreign_keys);
+        }
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[test]
+    fn test_query_rows_sees_open_transaction() {
+        let path = std::env::temp_dir().join(format!(
+            "dash-evo-tool-transaction-read-test-{}.db",
+            std::process::id()
+        ));
+        let db = Database::new(&path).expect("expected to open database");
+        db.execute("CREATE TABLE numbers (value INTEGER NOT NULL)", [])
+            .expect("expected to create table");
+
+        db.begin_transaction().expect("expected to begin");
+        db.execute("INSERT INTO numbers (value) VALUES (1)", [])
+            .expect("expected to insert");
+        let values = db
+            .query_rows("SELECT value FROM numbers", [], |row| row.get::<_, i64>(0))
+            .expect("expected to read");
+        assert_eq!(values, vec![1]);
+        // Other threads don't see the uncommitted row
+        let other_values = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    db.query_rows("SELECT value FROM numbers", [], |row| row.get::<_, i64>(0))
+                })
+                .join()
+                .unwrap()
+        })
+        .expect("expected to read from another thread");
+        assert!(other_values.is_empty());
+        db.rollback_transaction().expect("expected to roll back");
+
+        let values = db
+            .query_rows("SELECT value FROM numbers", [], |row| row.get::<_, i64>(0))
+            .expect("expected to read");
+        assert!(values.is_empty());
+
+        drop(db);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[test]
+    fn test_concurrent_reads_while_writing() {
+        let path = std::env::temp_dir().join(format!(
+            "dash-evo-tool-concurrent-read-test-{}.db",
+            std::process::id()
+        ));
+        let db = std::sync::Arc::new(Database::new(&path).expect("expected to open database"));
+        assert_eq!(db.readers.len(), READ_CONNECTIONS);
+        db.execute("CREATE TABLE numbers (value INTEGER NOT NULL)", [])
+            .expect("expected to create table");
+
+        let writer = {
+            let db = db.clone();
+            std::thread::spawn(move || {
+                for value in 0..200 {
+                    db.execute("INSERT INTO numbers (value) VALUES (?)", [value])
+                        .expect("expected to insert");
+                }
+            })
+        };
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let db = db.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..200 {
+                        db.query_rows("SELECT value FROM numbers", [], |row| row.get::<_, i64>(0))
+                            .expect("expected to read");
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().expect("writer panicked");
+        for reader in readers {
+            reader.join().expect("reader panicked");
+        }
+        let values = db
+            .query_rows("SELECT value FROM numbers", [], |row| row.get::<_, i64>(0))
+            .unwrap();
+        assert_eq!(values.len(), 200);
+
+        drop(db);
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
 }
@@ -0,0 +1,260 @@
+use crate::database::Database;
+use dash_sdk::dpp::dashcore::Network;
+use rusqlite::{params, Connection};
+
+/// The value stored in the `network` columns for `network`. Every insert and select goes
+/// through this, so stored rows don't depend on how `Network` happens to be displayed.
+pub fn network_db_name(network: Network, devnet_name: Option<&str>) -> String {
+    match network {
+        Network::Dash => "dash".to_string(),
+        Network::Testnet => "testnet".to_string(),
+        Network::Devnet => match devnet_name {
+            Some(name) if !name.is_empty() => format!("devnet:{}", name),
+            _ => "devnet".to_string(),
+        },
+        Network::Regtest => "regtest".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Reads back a value written by `network_db_name`, along with the devnet name. Labels with
+/// other casing or surrounding whitespace, and `mainnet` for `dash`, are accepted too.
+pub fn network_from_db_name(name: &str) -> Option<(Network, Option<String>)> {
+    let name = name.trim();
+    let (base, devnet_name) = match name.split_once(':') {
+        Some((base, devnet_name)) => (base, Some(devnet_name.trim())),
+        None => (name, None),
+    };
+    let network = match base.to_lowercase().as_str() {
+        "dash" | "mainnet" => Network::Dash,
+        "testnet" => Network::Testnet,
+        "devnet" => Network::Devnet,
+        "regtest" => Network::Regtest,
+        _ => return None,
+    };
+    let devnet_name = match network {
+        Network::Devnet => devnet_name
+            .filter(|name| !name.is_empty())
+            .map(str::to_string),
+        _ => None,
+    };
+    Some((network, devnet_name))
+}
+
+/// Tables that have a `network` column, other than `settings` whose `network` is the network
+/// the app was last used on rather than the network the row belongs to.
+fn tables_with_network_column(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let tables = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name != 'settings'")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut with_network = Vec::new();
+    for table in tables {
+        let has_network: bool = conn.query_row(
+            &format!(
+                "SELECT EXISTS(SELECT 1 FROM pragma_table_info('{}') WHERE name = 'network')",
+                table
+            ),
+            [],
+            |row| row.get(0),
+        )?;
+        if has_network {
+            with_network.push(table);
+        }
+    }
+    Ok(with_network)
+}
+
+/// Moves the rows of `table` stored under `network` to `canonical`, one row at a time so a
+/// row colliding with one already stored under `canonical` can be told apart. The row
+/// already stored under `canonical` was written by a version using that value, so it is kept.
+fn normalize_network_rows(
+    conn: &Connection,
+    table: &str,
+    network: &str,
+    canonical: &str,
+) -> rusqlite::Result<()> {
+    let rowids = conn
+        .prepare(&format!(
+            "SELECT rowid FROM \"{}\" WHERE network = ?",
+            table
+        ))?
+        .query_map(params![network], |row| row.get::<_, i64>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for rowid in rowids {
+        let result = conn.execute(
+            &format!("UPDATE \"{}\" SET network = ? WHERE rowid = ?", table),
+            params![canonical, rowid],
+        );
+        match result {
+            Ok(_) => {}
+            Err(rusqlite::Error::SqliteFailure(e, _))
+                if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY
+                    || e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE =>
+            {
+                conn.execute(
+                    &format!("DELETE FROM \"{}\" WHERE rowid = ?", table),
+                    params![rowid],
+                )?;
+                tracing::warn!(
+                    "Dropped row {} of {} stored under network \"{}\", a row for \"{}\" already exists",
+                    rowid,
+                    table,
+                    network,
+                    canonical
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+impl Database {
+    /// Rewrites every `network` column to the value `network_db_name` gives for it. A row
+    /// whose rewritten value would collide with a row already stored under that value is
+    /// dropped in favor of it, and logged. Labels that name no known network are kept.
+    pub fn normalize_network_columns(&self) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        // Tables are rewritten one at a time, so the foreign keys between them only hold
+        // again once all of them are done.
+        conn.pragma_update(None, "defer_foreign_keys", "ON")?;
+
+        let mut tables = tables_with_network_column(&conn)?;
+        tables.push("settings".to_string());
+        for table in tables {
+            let stored = conn
+                .prepare(&format!(
+                    "SELECT DISTINCT network FROM \"{}\" WHERE network IS NOT NULL",
+                    table
+                ))?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            for network in stored {
+                let Some((parsed, devnet_name)) = network_from_db_name(&network) else {
+                    continue;
+                };
+                let canonical = network_db_name(parsed, devnet_name.as_deref());
+                if canonical != network {
+                    normalize_network_rows(&conn, &table, &network, &canonical)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_network_db_name_round_trip() {
+        for (network, devnet_name) in [
+            (Network::Dash, None),
+            (Network::Testnet, None),
+            (Network::Devnet, None),
+            (Network::Devnet, Some("ouzo".to_string())),
+            (Network::Regtest, None),
+        ] {
+            let name = network_db_name(network, devnet_name.as_deref());
+            assert_eq!(
+                network_from_db_name(&name),
+                Some((network, devnet_name)),
+                "{} did not round trip",
+                name
+            );
+        }
+        assert_eq!(
+            network_from_db_name(" Mainnet "),
+            Some((Network::Dash, None))
+        );
+        assert_eq!(network_from_db_name("unknown"), None);
+    }
+
+    #[test]
+    fn test_normalize_network_columns() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        for (id, network) in [
+            (1u8, "Testnet"),
+            (2, "testnet"),
+            (3, "DASH "),
+            (4, "devnet:"),
+        ] {
+            db.execute(
+                "INSERT INTO identity (id, is_local, network) VALUES (?, 1, ?)",
+                params![vec![id; 32], network],
+            )
+            .expect("expected to insert identity");
+        }
+        db.execute(
+            "INSERT INTO contested_name (normalized_contested_name, network) VALUES ('name', 'TESTNET')",
+            [],
+        )
+        .expect("expected to insert contested name");
+        db.execute(
+            "INSERT INTO contestant (normalized_contested_name, identity_id, network)
+             VALUES ('name', ?, 'TESTNET')",
+            params![vec![1u8; 32]],
+        )
+        .expect("expected to insert contestant");
+
+        db.begin_transaction().unwrap();
+        db.normalize_network_columns()
+            .expect("expected to normalize networks");
+        db.commit_transaction().unwrap();
+
+        let identity_networks = db
+            .query_rows("SELECT network FROM identity ORDER BY id", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .unwrap();
+        assert_eq!(
+            identity_networks,
+            vec!["testnet", "testnet", "dash", "devnet"]
+        );
+        let contestant_networks = db
+            .query_rows("SELECT network FROM contestant", [], |row| {
+                row.get::<_, String>(0)
+            })
+            .unwrap();
+        assert_eq!(contestant_networks, vec!["testnet"]);
+    }
+
+    #[test]
+    fn test_normalize_network_columns_drops_colliding_rows() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        for (network, end_time) in [("testnet", 2), ("Testnet", 1), ("DASH", 3)] {
+            db.execute(
+                "INSERT INTO contested_name (normalized_contested_name, end_time, network)
+                 VALUES ('name', ?, ?)",
+                params![end_time, network],
+            )
+            .expect("expected to insert contested name");
+        }
+
+        db.begin_transaction().unwrap();
+        db.normalize_network_columns()
+            .expect("expected to normalize networks");
+        db.commit_transaction().unwrap();
+
+        // The row already stored under the canonical value wins, nothing keeps a legacy value
+        let contested_names = db
+            .query_rows(
+                "SELECT network, end_time FROM contested_name ORDER BY network",
+                [],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .unwrap();
+        assert_eq!(
+            contested_names,
+            vec![("dash".to_string(), 3), ("testnet".to_string(), 2)]
+        );
+    }
+}
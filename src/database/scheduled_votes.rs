@@ -10,6 +10,31 @@ use dash_sdk::{
 };
 use rusqlite::params;
 
+/// Parses a vote choice stored with its `Display` representation.
+pub(super) fn vote_choice_from_string(value: &str) -> rusqlite::Result<ResourceVoteChoice> {
+    match value {
+        "Abstain" => Ok(ResourceVoteChoice::Abstain),
+        "Lock" => Ok(ResourceVoteChoice::Lock),
+        other => {
+            let Some(towards_id) = other
+                .strip_prefix("TowardsIdentity(")
+                .and_then(|inner| inner.strip_suffix(')'))
+            else {
+                return Err(rusqlite::Error::InvalidQuery);
+            };
+            Identifier::from_string(towards_id, Encoding::Base58)
+                .map(ResourceVoteChoice::TowardsIdentity)
+                .map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Blob,
+                        Box::new(e),
+                    )
+                })
+        }
+    }
+}
+
 impl Database {
     pub fn initialize_scheduled_votes_table(&self) -> rusqlite::Result<()> {
         // Create the scheduled_votes table
@@ -95,32 +120,7 @@ impl Database {
                 _ => unreachable!(),
             };
 
-            let vote_choice = match vote_choice_string.as_str() {
-                "Abstain" => ResourceVoteChoice::Abstain,
-                "Lock" => ResourceVoteChoice::Lock,
-                other => {
-                    if let Some(inner) = other.strip_prefix("TowardsIdentity(") {
-                        if let Some(inner) = inner.strip_suffix(')') {
-                            let towards_id = inner.to_string();
-                            ResourceVoteChoice::TowardsIdentity(
-                                Identifier::from_string(&towards_id, Encoding::Base58).map_err(
-                                    |e| {
-                                        rusqlite::Error::FromSqlConversionFailure(
-                                            0,
-                                            rusqlite::types::Type::Blob,
-                                            Box::new(e),
-                                        )
-                                    },
-                                )?,
-                            )
-                        } else {
-                            return Err(rusqlite::Error::InvalidQuery);
-                        }
-                    } else {
-                        return Err(rusqlite::Error::InvalidQuery);
-                    }
-                }
-            };
+            let vote_choice = vote_choice_from_string(&vote_choice_string)?;
 
             let scheduled_vote = ScheduledDPNSVote {
                 voter_id: Identifier::from_bytes(&voter_id_bytes).map_err(|e| {
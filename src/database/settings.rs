@@ -1,10 +1,14 @@
-use crate::database::Database;
+use crate::database::{network_db_name, network_from_db_name, Database};
+use crate::model::dash_price::DashPrice;
 use crate::model::password_info::PasswordInfo;
+use crate::ui::theme::ThemeMode;
 use crate::ui::RootScreenType;
 use dash_sdk::dpp::dashcore::Network;
 use rusqlite::{params, Result};
 use std::path::PathBuf;
-use std::str::FromStr;
+
+/// Default font size used by data tables when no preference has been stored.
+pub const DEFAULT_TABLE_FONT_SIZE: f32 = 14.0;
 
 impl Database {
     /// Inserts or updates the settings in the database. This method ensures that only one row exists.
@@ -13,7 +17,8 @@ impl Database {
         network: Network,
         start_root_screen: RootScreenType,
     ) -> Result<()> {
-        let network_str = network.to_string();
+        self.ensure_writable()?;
+        let network_str = network_db_name(network, None);
         let screen_type_int = start_root_screen.to_int();
         self.execute(
             "INSERT INTO settings (id, network, start_root_screen, database_version)
@@ -32,6 +37,7 @@ impl Database {
         nonce: &[u8],
         password_check: &[u8],
     ) -> Result<()> {
+        self.ensure_writable()?;
         // Update the settings table with the provided salt, nonce, and password_check
         self.execute(
             "UPDATE settings
@@ -50,6 +56,7 @@ impl Database {
         custom_dash_path: Option<String>,
         overwrite_dash_conf: bool,
     ) -> Result<()> {
+        self.ensure_writable()?;
         self.execute(
             "UPDATE settings
             SET custom_dash_qt_path = ?,
@@ -74,6 +81,274 @@ impl Database {
         Ok(())
     }
 
+    pub fn add_table_display_columns(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN table_font_size REAL DEFAULT NULL;",
+            (),
+        )?;
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN monospace_ids INTEGER DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_table_display_settings(
+        &self,
+        table_font_size: f32,
+        monospace_ids: bool,
+    ) -> Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE settings
+            SET table_font_size = ?,
+                monospace_ids = ?
+            WHERE id = 1",
+            rusqlite::params![table_font_size, monospace_ids],
+        )?;
+
+        Ok(())
+    }
+
+    /// Retrieves the table font size and whether IDs should be rendered in a monospace font.
+    /// Falls back to the defaults if nothing has been stored yet.
+    pub fn get_table_display_settings(&self) -> Result<(f32, bool)> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT table_font_size, monospace_ids FROM settings WHERE id = 1",
+            [],
+            |row| {
+                let table_font_size: Option<f32> = row.get(0)?;
+                let monospace_ids: Option<bool> = row.get(1)?;
+                Ok((
+                    table_font_size.unwrap_or(DEFAULT_TABLE_FONT_SIZE),
+                    monospace_ids.unwrap_or(false),
+                ))
+            },
+        );
+
+        match result {
+            Ok(settings) => Ok(settings),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok((DEFAULT_TABLE_FONT_SIZE, false)),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn add_auto_refresh_on_open_column(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN identities_auto_refresh_on_open INTEGER DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_identities_auto_refresh_on_open(&self, auto_refresh: bool) -> Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE settings
+            SET identities_auto_refresh_on_open = ?
+            WHERE id = 1",
+            rusqlite::params![auto_refresh],
+        )?;
+
+        Ok(())
+    }
+
+    /// Whether the identities screen should refresh from Platform every time it is opened.
+    pub fn get_identities_auto_refresh_on_open(&self) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT identities_auto_refresh_on_open FROM settings WHERE id = 1",
+            [],
+            |row| row.get::<_, Option<bool>>(0),
+        );
+
+        match result {
+            Ok(auto_refresh) => Ok(auto_refresh.unwrap_or(false)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn add_auto_vote_fully_automatic_column(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN auto_vote_fully_automatic INTEGER DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_auto_vote_fully_automatic(&self, fully_automatic: bool) -> Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE settings
+            SET auto_vote_fully_automatic = ?
+            WHERE id = 1",
+            rusqlite::params![fully_automatic],
+        )?;
+
+        Ok(())
+    }
+
+    /// Whether votes produced by contested-name vote rules are cast without confirmation.
+    pub fn get_auto_vote_fully_automatic(&self) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT auto_vote_fully_automatic FROM settings WHERE id = 1",
+            [],
+            |row| row.get::<_, Option<bool>>(0),
+        );
+
+        match result {
+            Ok(fully_automatic) => Ok(fully_automatic.unwrap_or(false)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn add_identities_column_widths_column(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN identities_column_widths TEXT DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    /// Stores the identities table column widths, or clears them when `None` is passed so the
+    /// table falls back to its initial widths.
+    pub fn update_identities_column_widths(&self, widths: Option<&[f32]>) -> Result<()> {
+        self.ensure_writable()?;
+        let widths = widths.map(|widths| {
+            widths
+                .iter()
+                .map(|width| width.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        });
+        self.execute(
+            "UPDATE settings
+            SET identities_column_widths = ?
+            WHERE id = 1",
+            rusqlite::params![widths],
+        )?;
+
+        Ok(())
+    }
+
+    /// Retrieves the user-adjusted identities table column widths, if any were saved.
+    pub fn get_identities_column_widths(&self) -> Result<Option<Vec<f32>>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT identities_column_widths FROM settings WHERE id = 1",
+            [],
+            |row| row.get::<_, Option<String>>(0),
+        );
+
+        match result {
+            Ok(widths) => Ok(widths.and_then(|widths| {
+                widths
+                    .split(',')
+                    .map(|width| width.parse::<f32>().ok())
+                    .collect::<Option<Vec<_>>>()
+            })),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn add_dash_price_columns(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN dash_price REAL DEFAULT NULL;",
+            (),
+        )?;
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN dash_price_currency TEXT DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    /// Stores the manual DASH price, or clears it when `None` is passed so amounts are shown in
+    /// DASH only.
+    pub fn update_dash_price(&self, price: Option<&DashPrice>) -> Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE settings
+            SET dash_price = ?,
+                dash_price_currency = ?
+            WHERE id = 1",
+            rusqlite::params![
+                price.map(|price| price.price),
+                price.map(|price| price.currency.clone())
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn get_dash_price(&self) -> Result<Option<DashPrice>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT dash_price, dash_price_currency FROM settings WHERE id = 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, Option<f64>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                ))
+            },
+        );
+
+        match result {
+            Ok((Some(price), currency)) => Ok(Some(DashPrice {
+                price,
+                currency: currency.unwrap_or_default(),
+            })),
+            Ok((None, _)) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn add_theme_mode_column(&self) -> Result<()> {
+        self.execute(
+            "ALTER TABLE settings ADD COLUMN theme_mode INTEGER DEFAULT NULL;",
+            (),
+        )?;
+
+        Ok(())
+    }
+
+    pub fn update_theme_mode(&self, theme_mode: ThemeMode) -> Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE settings
+            SET theme_mode = ?
+            WHERE id = 1",
+            rusqlite::params![theme_mode.to_int()],
+        )?;
+
+        Ok(())
+    }
+
+    /// The chosen color scheme, following the system when none was chosen yet.
+    pub fn get_theme_mode(&self) -> Result<ThemeMode> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row("SELECT theme_mode FROM settings WHERE id = 1", [], |row| {
+            row.get::<_, Option<u32>>(0)
+        });
+
+        match result {
+            Ok(theme_mode) => Ok(theme_mode.and_then(ThemeMode::from_int).unwrap_or_default()),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(ThemeMode::default()),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Updates the database version in the settings table.
     pub fn update_database_version(&self, new_version: u16) -> Result<()> {
         // Ensure the database version is updated
@@ -124,12 +399,12 @@ impl Database {
             };
 
             // Convert network from string to enum
-            let parsed_network =
-                Network::from_str(&network).map_err(|_| rusqlite::Error::InvalidQuery)?;
+            let (parsed_network, _) =
+                network_from_db_name(&network).ok_or(rusqlite::Error::InvalidQuery)?;
 
-            // Convert start_root_screen from int to enum
-            let root_screen_type = RootScreenType::from_int(start_root_screen)
-                .ok_or_else(|| rusqlite::Error::InvalidQuery)?;
+            // Convert start_root_screen from int to enum, a screen that was removed since it
+            // was saved falls back to the default one
+            let root_screen_type = RootScreenType::from_int(start_root_screen).unwrap_or_default();
 
             Ok((
                 parsed_network,
@@ -147,3 +422,74 @@ impl Database {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_start_root_screen_is_restored() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+
+        db.insert_or_update_settings(
+            Network::Testnet,
+            RootScreenType::RootScreenDPNSScheduledVotes,
+        )
+        .expect("expected to save settings");
+        let (network, root_screen_type, ..) = db.get_settings().unwrap().unwrap();
+        assert_eq!(network, Network::Testnet);
+        assert_eq!(
+            root_screen_type,
+            RootScreenType::RootScreenDPNSScheduledVotes
+        );
+
+        // A screen number no longer in use
+        db.execute("UPDATE settings SET start_root_screen = 8 WHERE id = 1", [])
+            .unwrap();
+        let (_, root_screen_type, ..) = db.get_settings().unwrap().unwrap();
+        assert_eq!(root_screen_type, RootScreenType::default());
+    }
+
+    #[test]
+    fn test_theme_mode_is_restored() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        assert_eq!(db.get_theme_mode().unwrap(), ThemeMode::System);
+
+        db.update_theme_mode(ThemeMode::Light)
+            .expect("expected to save theme");
+        assert_eq!(db.get_theme_mode().unwrap(), ThemeMode::Light);
+
+        // A value no version ever wrote
+        db.execute("UPDATE settings SET theme_mode = 7 WHERE id = 1", [])
+            .unwrap();
+        assert_eq!(db.get_theme_mode().unwrap(), ThemeMode::System);
+    }
+
+    #[test]
+    fn test_settings_are_not_saved_in_read_only_mode() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+
+        db.set_read_only(true);
+        assert!(db.update_theme_mode(ThemeMode::Dark).is_err());
+        assert!(db.update_identities_auto_refresh_on_open(true).is_err());
+        assert_eq!(db.get_theme_mode().unwrap(), ThemeMode::System);
+        assert!(!db.get_identities_auto_refresh_on_open().unwrap());
+
+        // Migrations still record the version they reached
+        let version = db.database_version().unwrap();
+        db.update_database_version(version)
+            .expect("expected to save version");
+
+        db.set_read_only(false);
+        db.update_theme_mode(ThemeMode::Dark)
+            .expect("expected to save theme");
+        assert_eq!(db.get_theme_mode().unwrap(), ThemeMode::Dark);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::database::Database;
+use crate::model::state_archive::{ArchiveValue, TableDump};
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params, params_from_iter, Connection};
+
+impl Database {
+    /// Returns the schema version recorded in the settings table.
+    pub fn database_version(&self) -> rusqlite::Result<u16> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT database_version FROM settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    /// Reads every row of `table`. A table that doesn't exist yields an empty dump.
+    pub fn dump_table(&self, table: &str) -> rusqlite::Result<TableDump> {
+        let conn = self.conn.lock().unwrap();
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name=?)",
+            params![table],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Ok(TableDump::default());
+        }
+
+        let mut stmt = conn.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let rows = stmt
+            .query_map([], |row| {
+                (0..columns.len())
+                    .map(|i| {
+                        Ok(match row.get_ref(i)? {
+                            ValueRef::Null => ArchiveValue::Null,
+                            ValueRef::Integer(v) => ArchiveValue::Integer(v),
+                            ValueRef::Real(v) => ArchiveValue::Real(v),
+                            ValueRef::Text(v) => {
+                                ArchiveValue::Text(String::from_utf8_lossy(v).into_owned())
+                            }
+                            ValueRef::Blob(v) => ArchiveValue::Blob(v.to_vec()),
+                        })
+                    })
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(TableDump { columns, rows })
+    }
+
+    /// Replaces the contents of each table with its dump in a single transaction. Only columns
+    /// present in both the dump and the current schema are written, so archives from older
+    /// database versions can still be restored. The settings row keeps the current
+    /// `database_version`.
+    pub fn restore_tables(&self, dumps: &[(String, TableDump)]) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        let mut conn = self.conn.lock().unwrap();
+        let current_version: i64 = conn.query_row(
+            "SELECT database_version FROM settings WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )?;
+
+        // Tables are replaced wholesale, so foreign key actions would only wipe rows of tables
+        // that were not selected. The pragma has no effect inside a transaction.
+        conn.pragma_update(None, "foreign_keys", "OFF")?;
+        let result = Self::restore_tables_in_transaction(&mut conn, dumps, current_version);
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        result
+    }
+
+    fn restore_tables_in_transaction(
+        conn: &mut Connection,
+        dumps: &[(String, TableDump)],
+        current_version: i64,
+    ) -> rusqlite::Result<()> {
+        let tx = conn.transaction()?;
+
+        for (table, _) in dumps.iter().rev() {
+            tx.execute(&format!("DELETE FROM \"{}\"", table), [])?;
+        }
+
+        for (table, dump) in dumps {
+            let schema_columns = tx
+                .prepare(&format!("PRAGMA table_info(\"{}\")", table))?
+                .query_map([], |row| row.get::<_, String>(1))?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            let kept: Vec<usize> = dump
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| schema_columns.contains(column))
+                .map(|(i, _)| i)
+                .collect();
+            if kept.is_empty() {
+                continue;
+            }
+
+            let sql = format!(
+                "INSERT INTO \"{}\" ({}) VALUES ({})",
+                table,
+                kept.iter()
+                    .map(|&i| format!("\"{}\"", dump.columns[i]))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                vec!["?"; kept.len()].join(", ")
+            );
+            let mut stmt = tx.prepare(&sql)?;
+            for row in &dump.rows {
+                let values = kept.iter().map(|&i| match &row[i] {
+                    ArchiveValue::Null => Value::Null,
+                    ArchiveValue::Integer(v) => Value::Integer(*v),
+                    ArchiveValue::Real(v) => Value::Real(*v),
+                    ArchiveValue::Text(v) => Value::Text(v.clone()),
+                    ArchiveValue::Blob(v) => Value::Blob(v.clone()),
+                });
+                stmt.execute(params_from_iter(values))?;
+            }
+        }
+
+        tx.execute(
+            "UPDATE settings SET database_version = ? WHERE id = 1",
+            params![current_version],
+        )?;
+
+        tx.commit()
+    }
+}
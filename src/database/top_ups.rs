@@ -1,4 +1,5 @@
 use crate::database::Database;
+use chrono::Utc;
 use rusqlite::{params, OptionalExtension};
 
 impl Database {
@@ -17,6 +18,16 @@ impl Database {
         Ok(())
     }
 
+    /// Records when each top up happened. Added separately from the table creation so that
+    /// databases created before this column existed get it through the migration.
+    pub fn add_top_up_created_at_column(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "ALTER TABLE top_up ADD COLUMN created_at INTEGER DEFAULT NULL",
+            [],
+        )?;
+        Ok(())
+    }
+
     pub fn get_next_top_up_index(&self, identity_id: &[u8]) -> rusqlite::Result<u64> {
         let conn = self.conn.lock().unwrap();
         let max_index: Option<u64> = conn
@@ -36,8 +47,8 @@ impl Database {
         amount: u64,
     ) -> rusqlite::Result<()> {
         self.execute(
-            "INSERT INTO top_up (identity_id, top_up_index, amount) VALUES (?, ?, ?)",
-            params![identity_id, top_up_index, amount],
+            "INSERT INTO top_up (identity_id, top_up_index, amount, created_at) VALUES (?, ?, ?, ?)",
+            params![identity_id, top_up_index, amount, Utc::now().timestamp()],
         )?;
         Ok(())
     }
@@ -1,4 +1,4 @@
-use crate::database::Database;
+use crate::database::{network_db_name, Database};
 use dash_sdk::dashcore_rpc::dashcore::{OutPoint, ScriptBuf, TxOut, Txid};
 use dash_sdk::dpp::dashcore::hashes::Hash;
 use dash_sdk::dpp::dashcore::{Address, Network};
@@ -6,13 +6,13 @@ use rusqlite::params;
 
 impl Database {
     /// Deletes a UTXO from the database given its OutPoint and network.
-    pub fn drop_utxo(&self, outpoint: &OutPoint, network: &str) -> rusqlite::Result<()> {
+    pub fn drop_utxo(&self, outpoint: &OutPoint, network: Network) -> rusqlite::Result<()> {
         let txid_bytes = outpoint.txid.as_byte_array(); // &[u8; 32]
         let vout = outpoint.vout as i64; // i64
 
         self.execute(
             "DELETE FROM utxos WHERE txid = ? AND vout = ? AND network = ?",
-            params![txid_bytes, vout, network],
+            params![txid_bytes, vout, network_db_name(network, None)],
         )?;
 
         Ok(())
@@ -36,7 +36,7 @@ impl Database {
                 address.to_string(),
                 value,
                 script_pubkey,
-                network.to_string()
+                network_db_name(network, None)
             ],
         )?;
         Ok(())
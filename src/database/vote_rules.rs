@@ -0,0 +1,162 @@
+use crate::context::AppContext;
+use crate::database::scheduled_votes::vote_choice_from_string;
+use crate::database::Database;
+use crate::model::vote_rule::{AutoVoteLogEntry, AutoVoteOutcome, VoteRule};
+use dash_sdk::dpp::voting::vote_choices::resource_vote_choice::ResourceVoteChoice;
+use dash_sdk::platform::Identifier;
+use rusqlite::params;
+
+impl Database {
+    pub fn initialize_vote_rules_tables(&self) -> rusqlite::Result<()> {
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS vote_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name_pattern TEXT NOT NULL,
+                vote_choice TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                network TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Audit trail of every vote produced by a rule
+        self.execute(
+            "CREATE TABLE IF NOT EXISTS auto_vote_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                time INTEGER NOT NULL,
+                contested_name TEXT NOT NULL,
+                vote_choice TEXT NOT NULL,
+                rule_pattern TEXT NOT NULL,
+                voter_id BLOB,
+                outcome TEXT NOT NULL,
+                error TEXT,
+                network TEXT NOT NULL
+            )",
+            [],
+        )?;
+        self.execute(
+            "CREATE INDEX IF NOT EXISTS idx_auto_vote_log_network_name
+             ON auto_vote_log (network, contested_name)",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn insert_vote_rule(
+        &self,
+        app_context: &AppContext,
+        name_pattern: &str,
+        choice: ResourceVoteChoice,
+    ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "INSERT INTO vote_rules (name_pattern, vote_choice, enabled, network) VALUES (?, ?, 1, ?)",
+            params![name_pattern, choice.to_string(), app_context.network_string()],
+        )?;
+        Ok(())
+    }
+
+    pub fn set_vote_rule_enabled(&self, rule_id: i64, enabled: bool) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.execute(
+            "UPDATE vote_rules SET enabled = ? WHERE id = ?",
+            params![enabled, rule_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_vote_rule(&self, rule_id: i64) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
+        self.execute("DELETE FROM vote_rules WHERE id = ?", params![rule_id])?;
+        Ok(())
+    }
+
+    pub fn get_vote_rules(&self, app_context: &AppContext) -> rusqlite::Result<Vec<VoteRule>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, name_pattern, vote_choice, enabled FROM vote_rules WHERE network = ? ORDER BY id",
+        )?;
+        let rules = stmt
+            .query_map(params![app_context.network_string()], |row| {
+                let vote_choice: String = row.get(2)?;
+                Ok(VoteRule {
+                    id: row.get(0)?,
+                    name_pattern: row.get(1)?,
+                    choice: vote_choice_from_string(&vote_choice)?,
+                    enabled: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rules)
+    }
+
+    pub fn insert_auto_vote_log_entry(
+        &self,
+        app_context: &AppContext,
+        entry: &AutoVoteLogEntry,
+    ) -> rusqlite::Result<()> {
+        self.execute(
+            "INSERT INTO auto_vote_log (time, contested_name, vote_choice, rule_pattern, voter_id, outcome, error, network)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                entry.time,
+                entry.contested_name,
+                entry.choice.to_string(),
+                entry.rule_pattern,
+                entry.voter_id.as_ref().map(|id| id.to_vec()),
+                entry.outcome.as_str(),
+                entry.error,
+                app_context.network_string()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Names for which a rule vote was already cast or the proposal dismissed, so they are not
+    /// proposed again.
+    pub fn get_settled_auto_vote_names(
+        &self,
+        app_context: &AppContext,
+    ) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT contested_name FROM auto_vote_log
+             WHERE network = ? AND outcome IN ('Cast', 'Dismissed')",
+        )?;
+        let names = stmt
+            .query_map(params![app_context.network_string()], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(names)
+    }
+
+    pub fn get_auto_vote_log(
+        &self,
+        app_context: &AppContext,
+        limit: u32,
+    ) -> rusqlite::Result<Vec<AutoVoteLogEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT time, contested_name, vote_choice, rule_pattern, voter_id, outcome, error
+             FROM auto_vote_log WHERE network = ? ORDER BY id DESC LIMIT ?",
+        )?;
+        let entries = stmt
+            .query_map(params![app_context.network_string(), limit], |row| {
+                let vote_choice: String = row.get(2)?;
+                let voter_id: Option<Vec<u8>> = row.get(4)?;
+                let outcome: String = row.get(5)?;
+                Ok(AutoVoteLogEntry {
+                    time: row.get(0)?,
+                    contested_name: row.get(1)?,
+                    choice: vote_choice_from_string(&vote_choice)?,
+                    rule_pattern: row.get(3)?,
+                    voter_id: voter_id.and_then(|bytes| Identifier::from_bytes(&bytes).ok()),
+                    outcome: AutoVoteOutcome::parse(&outcome)
+                        .ok_or(rusqlite::Error::InvalidQuery)?,
+                    error: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::database::Database;
+use crate::database::{network_db_name, Database};
 use crate::model::qualified_identity::QualifiedIdentity;
 use crate::model::wallet::{
     AddressInfo, ClosedKeyItem, DerivationPathReference, DerivationPathType, OpenWalletSeed,
@@ -23,7 +23,8 @@ use std::str::FromStr;
 impl Database {
     /// Insert a new wallet into the wallet table
     pub fn store_wallet(&self, wallet: &Wallet, network: &Network) -> rusqlite::Result<()> {
-        let network_str = network.to_string();
+        self.ensure_writable()?;
+        let network_str = network_db_name(*network, None);
 
         // Serialize the extended public keys
         let master_ecdsa_bip44_account_0_epk_bytes =
@@ -55,6 +56,7 @@ impl Database {
         seed_hash: &[u8; 32],
         new_alias: Option<String>,
     ) -> rusqlite::Result<()> {
+        self.ensure_writable()?;
         let conn = self.conn.lock().unwrap();
 
         conn.execute(
@@ -162,8 +164,13 @@ impl Database {
     }
 
     /// Retrieve all wallets for a specific network, including their addresses, balances, and known addresses.
-    pub fn get_wallets(&self, network: &Network) -> rusqlite::Result<Vec<Wallet>> {
-        let network_str = network.to_string();
+    /// The private keys of their identities are decrypted with `encryption_key` when one is given.
+    pub fn get_wallets(
+        &self,
+        network: &Network,
+        encryption_key: Option<&[u8; 32]>,
+    ) -> rusqlite::Result<Vec<Wallet>> {
+        let network_str = network_db_name(*network, None);
         let conn = self.conn.lock().unwrap();
 
         // Step 1: Retrieve all wallets for the given network.
@@ -351,7 +358,7 @@ impl Database {
             "SELECT wallet, amount, transaction_data, instant_lock_data, chain_locked_height FROM asset_lock_transaction where identity_id IS NULL AND network = ?",
         )?;
 
-        let asset_lock_rows = asset_lock_stmt.query_map([network.to_string()], |row| {
+        let asset_lock_rows = asset_lock_stmt.query_map([network_db_name(*network, None)], |row| {
             let wallet_seed: Vec<u8> = row.get(0)?;
             let amount: Duffs = row.get(1)?;
             let tx_data: Vec<u8> = row.get(2)?;
@@ -418,7 +425,7 @@ impl Database {
 
         // Step 8: Retrieve identities for each wallet and add them to the wallets.
         let mut identity_stmt = conn.prepare(
-            "SELECT data, wallet, wallet_index FROM identity WHERE network = ? AND wallet IS NOT NULL AND wallet_index IS NOT NULL",
+            "SELECT data, wallet, wallet_index FROM identity WHERE network = ? AND wallet IS NOT NULL AND wallet_index IS NOT NULL AND is_deleted = 0",
         )?;
 
         let identity_rows = identity_stmt.query_map([network_str.clone()], |row| {
@@ -438,7 +445,19 @@ impl Database {
             let (identity_data, wallet_seed_hash_array, wallet_index) = row?;
 
             if let Some(wallet) = wallets_map.get_mut(&wallet_seed_hash_array) {
-                let mut identity: QualifiedIdentity = QualifiedIdentity::from_bytes(&identity_data);
+                let mut identity: QualifiedIdentity =
+                    match QualifiedIdentity::from_stored_bytes(&identity_data, encryption_key) {
+                        Ok(identity) => identity,
+                        Err(e) => {
+                            tracing::warn!(
+                                "Skipping identity at index {} of wallet {}: {}",
+                                wallet_index,
+                                hex::encode(wallet_seed_hash_array),
+                                e
+                            );
+                            continue;
+                        }
+                    };
                 identity.wallet_index = Some(wallet_index);
 
                 // Insert the identity into the wallet's identities HashMap with wallet_index as the key
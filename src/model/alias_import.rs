@@ -0,0 +1,118 @@
+use dash_sdk::dpp::platform_value::string_encoding::Encoding;
+use dash_sdk::platform::Identifier;
+use std::collections::BTreeMap;
+
+/// An identity id with the alias to give it, `None` to clear its alias.
+pub type AliasImportEntry = (Identifier, Option<String>);
+
+fn parse_identity_id(identity_id: &str) -> Result<Identifier, String> {
+    Identifier::from_string_try_encodings(identity_id.trim(), &[Encoding::Base58, Encoding::Hex])
+        .map_err(|_| format!("\"{}\" is not an identity id", identity_id.trim()))
+}
+
+fn alias_from(alias: &str) -> Option<String> {
+    let alias = alias.trim().trim_matches('"').trim();
+    (!alias.is_empty()).then(|| alias.to_string())
+}
+
+/// Reads the entries of an alias import file. JSON files hold an object mapping identity ids to
+/// aliases, `null` clearing the alias. CSV files hold one `<identity id>,<alias>` pair per line,
+/// an empty alias clearing it, and may start with an `identity_id,alias` header. Ids may be
+/// base58 or hex. Each entry is parsed on its own so a bad line doesn't prevent importing the
+/// others.
+pub fn parse_alias_import_file(
+    contents: &str,
+) -> Result<Vec<(String, Result<AliasImportEntry, String>)>, String> {
+    if contents.trim_start().starts_with('{') {
+        let entries: BTreeMap<String, Option<String>> = serde_json::from_str(contents)
+            .map_err(|e| format!("Invalid JSON alias file: {}", e))?;
+        return Ok(entries
+            .into_iter()
+            .map(|(identity_id, alias)| {
+                let entry = parse_identity_id(&identity_id)
+                    .map(|id| (id, alias.as_deref().and_then(alias_from)));
+                (identity_id.trim().to_string(), entry)
+            })
+            .collect());
+    }
+
+    Ok(contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .filter(|(index, line)| {
+            *index != 0
+                || !line
+                    .trim()
+                    .trim_matches('"')
+                    .to_lowercase()
+                    .starts_with("identity_id")
+        })
+        .map(|(index, line)| {
+            let label = format!("Line {}", index + 1);
+            let entry = match line.split_once(',') {
+                Some((identity_id, alias)) => parse_identity_id(identity_id.trim_matches('"'))
+                    .map(|id| (id, alias_from(alias))),
+                None => {
+                    Err("Expected an identity id and an alias separated by a comma".to_string())
+                }
+            };
+            (label, entry)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_alias_import_file_reads_csv() {
+        let id = Identifier::new([1; 32]);
+        let base58 = id.to_string(Encoding::Base58);
+        let contents = format!(
+            "identity_id,alias\n{},alice\n\n\"{}\", \"bob, jr\"\n{},\nnot-an-id,carol\nmissing\n",
+            base58,
+            hex::encode([1u8; 32]),
+            base58
+        );
+
+        let entries = parse_alias_import_file(&contents).expect("expected a CSV file to parse");
+
+        assert_eq!(entries.len(), 5);
+        assert_eq!(
+            entries[0],
+            ("Line 2".to_string(), Ok((id, Some("alice".to_string()))))
+        );
+        assert_eq!(
+            entries[1],
+            ("Line 4".to_string(), Ok((id, Some("bob, jr".to_string()))))
+        );
+        assert_eq!(entries[2], ("Line 5".to_string(), Ok((id, None))));
+        assert!(entries[3].1.is_err());
+        assert!(entries[4].1.is_err());
+    }
+
+    #[test]
+    fn test_parse_alias_import_file_reads_json() {
+        let id = Identifier::new([2; 32]);
+        let contents = format!(
+            r#"{{"{}": " dave ", "{}": null, "bad": "x"}}"#,
+            id.to_string(Encoding::Base58),
+            hex::encode([3u8; 32])
+        );
+
+        let entries = parse_alias_import_file(&contents).expect("expected a JSON file to parse");
+
+        assert_eq!(entries.len(), 3);
+        assert!(entries
+            .iter()
+            .any(|(_, entry)| entry == &Ok((id, Some("dave".to_string())))));
+        assert!(entries
+            .iter()
+            .any(|(_, entry)| entry == &Ok((Identifier::new([3; 32]), None))));
+        assert!(entries
+            .iter()
+            .any(|(label, entry)| label == "bad" && entry.is_err()));
+    }
+}
@@ -0,0 +1,45 @@
+/// A manually entered DASH exchange rate, used to show amounts in a fiat currency next to DASH.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DashPrice {
+    /// Price of one DASH in `currency`.
+    pub price: f64,
+    /// Currency code shown after converted amounts, like "USD".
+    pub currency: String,
+}
+
+impl DashPrice {
+    pub fn fiat_value(&self, dash: f64) -> f64 {
+        dash * self.price
+    }
+
+    pub fn format_fiat(&self, dash: f64) -> String {
+        format!("{:.2} {}", self.fiat_value(dash), self.currency)
+    }
+}
+
+/// Formats a DASH amount with `decimals` decimals, followed by its fiat value when a price is
+/// set.
+pub fn format_dash_amount(dash: f64, decimals: usize, price: Option<&DashPrice>) -> String {
+    match price {
+        Some(price) => format!("{:.*} DASH (≈ {})", decimals, dash, price.format_fiat(dash)),
+        None => format!("{:.*} DASH", decimals, dash),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_dash_amount_with_and_without_price() {
+        let price = DashPrice {
+            price: 30.0,
+            currency: "USD".to_string(),
+        };
+        assert_eq!(format_dash_amount(1.5, 4, None), "1.5000 DASH");
+        assert_eq!(
+            format_dash_amount(1.5, 4, Some(&price)),
+            "1.5000 DASH (≈ 45.00 USD)"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::model::qualified_identity::encrypted_key_storage::PrivateKeyData;
+use crate::model::qualified_identity::QualifiedIdentity;
+use crate::model::wallet::encryption::{decrypt_message, encrypt_message};
+use bincode::{Decode, Encode};
+use chrono::Utc;
+
+pub const IDENTITY_BACKUP_FORMAT_VERSION: u16 = 1;
+
+pub const IDENTITY_BACKUP_EXTENSION: &str = "dashid";
+
+/// Written before the encoded backup so other files are rejected before decoding.
+const IDENTITY_BACKUP_MAGIC: &[u8; 6] = b"DASHID";
+
+/// A single identity with its stored private keys, encrypted with a passphrase. Only the
+/// network and creation time are readable without the passphrase. Keys derived from a wallet
+/// are stored as derivation paths, they can only be used where that wallet is loaded.
+#[derive(Debug, Clone, Encode, Decode, PartialEq)]
+pub struct IdentityBackup {
+    pub format_version: u16,
+    pub network: String,
+    pub created_at: i64,
+    ciphertext: Vec<u8>,
+    salt: Vec<u8>,
+    nonce: Vec<u8>,
+}
+
+impl IdentityBackup {
+    /// Encrypts `qualified_identity` for `network`. Its private keys have to be decrypted first,
+    /// keys still encrypted with the app password could not be used after an import elsewhere.
+    pub fn seal(
+        qualified_identity: &QualifiedIdentity,
+        network: &str,
+        passphrase: &str,
+    ) -> Result<Self, String> {
+        if passphrase.is_empty() {
+            return Err("A passphrase is required to export the identity".to_string());
+        }
+        let has_locked_keys = qualified_identity
+            .private_keys
+            .private_keys
+            .values()
+            .any(|(_, data)| matches!(data, PrivateKeyData::Encrypted(_)));
+        if has_locked_keys {
+            return Err(
+                "Some private keys of this identity are locked, unlock the app before exporting"
+                    .to_string(),
+            );
+        }
+
+        let (ciphertext, salt, nonce) =
+            encrypt_message(&qualified_identity.to_bytes(), passphrase)?;
+        Ok(Self {
+            format_version: IDENTITY_BACKUP_FORMAT_VERSION,
+            network: network.to_string(),
+            created_at: Utc::now().timestamp(),
+            ciphertext,
+            salt,
+            nonce,
+        })
+    }
+
+    pub fn open(&self, passphrase: &str) -> Result<QualifiedIdentity, String> {
+        let bytes = decrypt_message(&self.ciphertext, passphrase, &self.salt, &self.nonce)?;
+        QualifiedIdentity::from_bytes(&bytes)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = IDENTITY_BACKUP_MAGIC.to_vec();
+        bytes.extend(
+            bincode::encode_to_vec(self, bincode::config::standard()).map_err(|e| e.to_string())?,
+        );
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let encoded = bytes
+            .strip_prefix(IDENTITY_BACKUP_MAGIC.as_slice())
+            .ok_or_else(|| "Not an identity backup file".to_string())?;
+        let backup: Self = bincode::decode_from_slice(encoded, bincode::config::standard())
+            .map(|(backup, _)| backup)
+            .map_err(|e| format!("Not a valid identity backup: {}", e))?;
+        if backup.format_version > IDENTITY_BACKUP_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported identity backup format version {}",
+                backup.format_version
+            ));
+        }
+        Ok(backup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::qualified_identity::PrivateKeyTarget;
+    use dash_sdk::dpp::identity::identity_public_key::v0::IdentityPublicKeyV0;
+    use dash_sdk::dpp::identity::v0::IdentityV0;
+    use dash_sdk::dpp::identity::{Identity, IdentityPublicKey, KeyType, Purpose, SecurityLevel};
+    use dash_sdk::platform::Identifier;
+    use std::collections::BTreeMap;
+
+    fn identity_with_clear_key() -> QualifiedIdentity {
+        let mut qualified_identity = QualifiedIdentity::from(Identity::V0(IdentityV0 {
+            id: Identifier::new([7; 32]),
+            public_keys: BTreeMap::new(),
+            balance: 0,
+            revision: 0,
+        }));
+        qualified_identity.alias = Some("backup".to_string());
+        let public_key = IdentityPublicKeyV0 {
+            id: 0,
+            purpose: Purpose::AUTHENTICATION,
+            security_level: SecurityLevel::MASTER,
+            contract_bounds: None,
+            key_type: KeyType::ECDSA_SECP256K1,
+            read_only: false,
+            data: vec![2; 33].into(),
+            disabled_at: None,
+        };
+        qualified_identity.private_keys.insert_non_encrypted(
+            (PrivateKeyTarget::PrivateKeyOnMainIdentity, 0),
+            (IdentityPublicKey::V0(public_key).into(), [9; 32]),
+        );
+        qualified_identity
+    }
+
+    #[test]
+    fn test_identity_backup_round_trip() {
+        let qualified_identity = identity_with_clear_key();
+        let bytes = IdentityBackup::seal(&qualified_identity, "testnet", "passphrase")
+            .and_then(|backup| backup.to_bytes())
+            .expect("expected to export identity");
+
+        // The clear private key must not appear in the file
+        assert!(!bytes.windows(32).any(|window| window == [9; 32]));
+
+        let backup = IdentityBackup::from_bytes(&bytes).expect("expected to read backup");
+        assert_eq!(backup.network, "testnet");
+        assert!(backup.open("wrong passphrase").is_err());
+        assert_eq!(
+            backup.open("passphrase").expect("expected to decrypt"),
+            qualified_identity
+        );
+    }
+
+    #[test]
+    fn test_identity_backup_rejects_other_files() {
+        assert!(IdentityBackup::from_bytes(b"not a backup").is_err());
+        assert!(IdentityBackup::seal(&identity_with_clear_key(), "testnet", "").is_err());
+    }
+}
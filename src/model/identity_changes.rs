@@ -0,0 +1,175 @@
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::dpp::identity::identity_public_key::accessors::v0::IdentityPublicKeyGettersV0;
+use dash_sdk::dpp::identity::{Identity, IdentityPublicKey, KeyID};
+
+/// What differs between a stored identity and the same identity fetched again from Platform.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IdentityChanges {
+    pub added_keys: Vec<KeyID>,
+    pub removed_keys: Vec<KeyID>,
+    pub disabled_keys: Vec<KeyID>,
+    /// Keys kept under the same id but with different data, type, purpose or security level
+    pub changed_keys: Vec<KeyID>,
+    /// Balance change in credits
+    pub balance_delta: i128,
+}
+
+fn same_key_material(stored: &IdentityPublicKey, incoming: &IdentityPublicKey) -> bool {
+    stored.key_type() == incoming.key_type()
+        && stored.purpose() == incoming.purpose()
+        && stored.security_level() == incoming.security_level()
+        && stored.read_only() == incoming.read_only()
+        && stored.contract_bounds() == incoming.contract_bounds()
+        && stored.data() == incoming.data()
+}
+
+impl IdentityChanges {
+    pub fn between(stored: &Identity, incoming: &Identity) -> Self {
+        let stored_keys = stored.public_keys();
+        let incoming_keys = incoming.public_keys();
+        let mut changes = IdentityChanges {
+            balance_delta: incoming.balance() as i128 - stored.balance() as i128,
+            ..Default::default()
+        };
+
+        for (key_id, incoming_key) in incoming_keys {
+            match stored_keys.get(key_id) {
+                None => changes.added_keys.push(*key_id),
+                Some(stored_key) if !same_key_material(stored_key, incoming_key) => {
+                    changes.changed_keys.push(*key_id)
+                }
+                Some(stored_key) if stored_key.disabled_at() != incoming_key.disabled_at() => {
+                    if stored_key.disabled_at().is_none() {
+                        changes.disabled_keys.push(*key_id)
+                    } else {
+                        changes.changed_keys.push(*key_id)
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed_keys = stored_keys
+            .keys()
+            .filter(|key_id| !incoming_keys.contains_key(key_id))
+            .copied()
+            .collect();
+
+        changes
+    }
+
+    /// Whether any public key was added, removed, disabled or changed. Balance changes are
+    /// expected and don't count.
+    pub fn has_key_changes(&self) -> bool {
+        !self.added_keys.is_empty()
+            || !self.removed_keys.is_empty()
+            || !self.disabled_keys.is_empty()
+            || !self.changed_keys.is_empty()
+    }
+
+    /// One line per kind of change, for showing to the user.
+    pub fn summary(&self) -> Vec<String> {
+        let format_ids = |key_ids: &[KeyID]| {
+            key_ids
+                .iter()
+                .map(|key_id| key_id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut lines = Vec::new();
+        for (label, key_ids) in [
+            ("Added keys", &self.added_keys),
+            ("Removed keys", &self.removed_keys),
+            ("Disabled keys", &self.disabled_keys),
+            ("Changed keys", &self.changed_keys),
+        ] {
+            if !key_ids.is_empty() {
+                lines.push(format!("{}: {}", label, format_ids(key_ids)));
+            }
+        }
+        if self.balance_delta != 0 {
+            lines.push(format!(
+                "Balance: {:+.8} DASH",
+                self.balance_delta as f64 * 1e-11
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_sdk::dpp::identity::identity_public_key::v0::IdentityPublicKeyV0;
+    use dash_sdk::dpp::identity::v0::IdentityV0;
+    use dash_sdk::dpp::identity::{KeyType, Purpose, SecurityLevel};
+    use dash_sdk::platform::Identifier;
+    use std::collections::BTreeMap;
+
+    fn key(id: KeyID, data: u8, disabled_at: Option<u64>) -> (KeyID, IdentityPublicKey) {
+        (
+            id,
+            IdentityPublicKey::V0(IdentityPublicKeyV0 {
+                id,
+                purpose: Purpose::AUTHENTICATION,
+                security_level: SecurityLevel::HIGH,
+                contract_bounds: None,
+                key_type: KeyType::ECDSA_SECP256K1,
+                read_only: false,
+                data: vec![data; 33].into(),
+                disabled_at,
+            }),
+        )
+    }
+
+    fn identity(balance: u64, keys: Vec<(KeyID, IdentityPublicKey)>) -> Identity {
+        Identity::V0(IdentityV0 {
+            id: Identifier::new([1; 32]),
+            public_keys: BTreeMap::from_iter(keys),
+            balance,
+            revision: 0,
+        })
+    }
+
+    #[test]
+    fn test_identity_changes_between() {
+        let stored = identity(
+            1000,
+            vec![key(0, 1, None), key(1, 2, None), key(2, 3, None)],
+        );
+        let incoming = identity(
+            400,
+            vec![key(0, 1, None), key(1, 2, Some(5)), key(3, 4, None)],
+        );
+
+        let changes = IdentityChanges::between(&stored, &incoming);
+
+        assert_eq!(
+            changes,
+            IdentityChanges {
+                added_keys: vec![3],
+                removed_keys: vec![2],
+                disabled_keys: vec![1],
+                changed_keys: vec![],
+                balance_delta: -600,
+            }
+        );
+        assert!(changes.has_key_changes());
+
+        // Only the balance moved
+        let changes = IdentityChanges::between(
+            &stored,
+            &identity(2000, stored.public_keys().clone().into_iter().collect()),
+        );
+        assert!(!changes.has_key_changes());
+        assert_eq!(changes.summary(), vec!["Balance: +0.00000001 DASH"]);
+
+        let replaced = identity(
+            1000,
+            vec![key(0, 9, None), key(1, 2, None), key(2, 3, None)],
+        );
+        assert_eq!(
+            IdentityChanges::between(&stored, &replaced).changed_keys,
+            vec![0]
+        );
+    }
+}
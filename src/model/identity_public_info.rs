@@ -0,0 +1,150 @@
+use crate::model::qualified_identity::QualifiedIdentity;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::dpp::identity::identity_public_key::accessors::v0::IdentityPublicKeyGettersV0;
+use dash_sdk::dpp::identity::IdentityPublicKey;
+use serde::Serialize;
+
+/// What can be shared about a local identity without giving anything secret away. It is built
+/// field by field from the public parts of a [`QualifiedIdentity`], its key storage is never
+/// read, so private keys can't end up in it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IdentityPublicInfo {
+    pub id: String,
+    pub alias: Option<String>,
+    pub identity_type: String,
+    pub public_keys: Vec<PublicKeyInfo>,
+    pub top_ups: Vec<TopUpInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicKeyInfo {
+    pub id: u32,
+    pub purpose: String,
+    pub security_level: String,
+    pub key_type: String,
+    pub read_only: bool,
+    /// Public key data, hex encoded
+    pub data: String,
+    pub disabled_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopUpInfo {
+    pub index: u32,
+    /// Amount in duffs
+    pub amount: u64,
+    pub created_at: Option<i64>,
+}
+
+impl From<&IdentityPublicKey> for PublicKeyInfo {
+    fn from(key: &IdentityPublicKey) -> Self {
+        Self {
+            id: key.id(),
+            purpose: format!("{:?}", key.purpose()),
+            security_level: format!("{:?}", key.security_level()),
+            key_type: format!("{:?}", key.key_type()),
+            read_only: key.read_only(),
+            data: hex::encode(key.data().as_slice()),
+            disabled_at: key.disabled_at(),
+        }
+    }
+}
+
+impl From<&QualifiedIdentity> for IdentityPublicInfo {
+    fn from(qualified_identity: &QualifiedIdentity) -> Self {
+        Self {
+            id: qualified_identity
+                .identity
+                .id()
+                .to_string(qualified_identity.identity_type.default_encoding()),
+            alias: qualified_identity.alias.clone(),
+            identity_type: qualified_identity.identity_type.to_string(),
+            public_keys: qualified_identity
+                .identity
+                .public_keys()
+                .values()
+                .map(PublicKeyInfo::from)
+                .collect(),
+            top_ups: qualified_identity
+                .top_ups
+                .iter()
+                .map(|(index, top_up)| TopUpInfo {
+                    index: *index,
+                    amount: top_up.amount,
+                    created_at: top_up.created_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The public info of `identities` as a pretty printed JSON array.
+pub fn identities_public_info_json(identities: &[QualifiedIdentity]) -> Result<String, String> {
+    let public_info: Vec<IdentityPublicInfo> =
+        identities.iter().map(IdentityPublicInfo::from).collect();
+    serde_json::to_string_pretty(&public_info).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::qualified_identity::{PrivateKeyTarget, TopUp};
+    use dash_sdk::dpp::identity::identity_public_key::v0::IdentityPublicKeyV0;
+    use dash_sdk::dpp::identity::v0::IdentityV0;
+    use dash_sdk::dpp::identity::{Identity, KeyType, Purpose, SecurityLevel};
+    use dash_sdk::platform::Identifier;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_public_info_leaves_out_private_keys() {
+        let public_key = IdentityPublicKey::V0(IdentityPublicKeyV0 {
+            id: 0,
+            purpose: Purpose::AUTHENTICATION,
+            security_level: SecurityLevel::MASTER,
+            contract_bounds: None,
+            key_type: KeyType::ECDSA_SECP256K1,
+            read_only: false,
+            data: vec![2; 33].into(),
+            disabled_at: None,
+        });
+        let mut qualified_identity = QualifiedIdentity::from(Identity::V0(IdentityV0 {
+            id: Identifier::new([7; 32]),
+            public_keys: BTreeMap::from([(0, public_key.clone())]),
+            balance: 0,
+            revision: 0,
+        }));
+        qualified_identity.alias = Some("shared".to_string());
+        qualified_identity.top_ups.insert(
+            0,
+            TopUp {
+                amount: 100_000,
+                created_at: Some(1_700_000_000),
+            },
+        );
+        qualified_identity.private_keys.insert_non_encrypted(
+            (PrivateKeyTarget::PrivateKeyOnMainIdentity, 0),
+            (public_key.into(), [9; 32]),
+        );
+
+        let json = identities_public_info_json(&[qualified_identity])
+            .expect("expected to serialize public info");
+
+        assert!(json.contains("\"shared\""));
+        assert!(json.contains(&hex::encode([2u8; 33])));
+        assert!(json.contains("100000"));
+        // Neither the private key nor any field beyond the public ones is written
+        assert!(!json.contains(&hex::encode([9u8; 32])));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut fields: Vec<&str> = value[0]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        fields.sort();
+        assert_eq!(
+            fields,
+            vec!["alias", "id", "identity_type", "public_keys", "top_ups"]
+        );
+    }
+}
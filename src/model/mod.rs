@@ -1,6 +1,13 @@
+pub mod alias_import;
 pub mod contested_name;
+pub mod dash_price;
+pub mod identity_backup;
+pub mod identity_changes;
+pub mod identity_public_info;
 pub mod password_info;
 pub mod proof_log_item;
 pub mod qualified_contract;
 pub mod qualified_identity;
+pub mod state_archive;
+pub mod vote_rule;
 pub mod wallet;
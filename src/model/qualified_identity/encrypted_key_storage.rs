@@ -1,5 +1,6 @@
 use crate::model::qualified_identity::qualified_identity_public_key::QualifiedIdentityPublicKey;
 use crate::model::qualified_identity::PrivateKeyTarget;
+use crate::model::wallet::encryption::{decrypt_with_key, encrypt_with_key};
 use crate::model::wallet::{Wallet, WalletSeedHash};
 use bincode::de::{BorrowDecoder, Decoder};
 use bincode::enc::Encoder;
@@ -389,4 +390,46 @@ impl KeyStorage {
             }
         }
     }
+
+    pub fn remove(
+        &mut self,
+        key: &(PrivateKeyTarget, KeyID),
+    ) -> Option<(QualifiedIdentityPublicKey, PrivateKeyData)> {
+        self.private_keys.remove(key)
+    }
+
+    /// Whether any key would be encrypted by [`KeyStorage::with_clear_keys_encrypted`].
+    pub fn has_clear_keys(&self) -> bool {
+        self.private_keys
+            .values()
+            .any(|(_, private_key_data)| matches!(private_key_data, PrivateKeyData::Clear(_)))
+    }
+
+    /// Returns a copy with every `Clear` key encrypted with `encryption_key`. Keys that are
+    /// always kept in the clear and keys held by a wallet are left as they are.
+    pub fn with_clear_keys_encrypted(&self, encryption_key: &[u8]) -> Result<Self, String> {
+        let mut encrypted = self.clone();
+        for (_, private_key_data) in encrypted.private_keys.values_mut() {
+            if let PrivateKeyData::Clear(clear) = private_key_data {
+                *private_key_data =
+                    PrivateKeyData::Encrypted(encrypt_with_key(clear, encryption_key)?);
+            }
+        }
+        Ok(encrypted)
+    }
+
+    /// Decrypts every `Encrypted` key with `encryption_key`. Keys that fail to decrypt stay
+    /// encrypted.
+    pub fn decrypt_keys(&mut self, encryption_key: &[u8]) {
+        for (_, private_key_data) in self.private_keys.values_mut() {
+            if let PrivateKeyData::Encrypted(encrypted) = private_key_data {
+                if let Some(clear) = decrypt_with_key(encrypted, encryption_key)
+                    .ok()
+                    .and_then(|clear| <[u8; 32]>::try_from(clear).ok())
+                {
+                    *private_key_data = PrivateKeyData::Clear(clear);
+                }
+            }
+        }
+    }
 }
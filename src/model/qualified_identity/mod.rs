@@ -85,6 +85,15 @@ pub struct DPNSNameInfo {
     pub acquired_at: u64,
 }
 
+/// A top up recorded for an identity, keyed by its top up index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopUp {
+    /// Amount in duffs
+    pub amount: u64,
+    /// Unix timestamp, unknown for top ups recorded before it was tracked
+    pub created_at: Option<i64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct QualifiedIdentity {
     pub identity: Identity,
@@ -98,7 +107,7 @@ pub struct QualifiedIdentity {
     pub associated_wallets: BTreeMap<WalletSeedHash, Arc<RwLock<Wallet>>>,
     /// The index used to register the identity
     pub wallet_index: Option<u32>,
-    pub top_ups: BTreeMap<u32, u32>,
+    pub top_ups: BTreeMap<u32, TopUp>,
 }
 
 impl PartialEq for QualifiedIdentity {
@@ -234,10 +243,38 @@ impl QualifiedIdentity {
     }
 
     /// Deserializes a QualifiedIdentity from a vector of bytes.
-    pub fn from_bytes(bytes: &[u8]) -> Self {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
         bincode::decode_from_slice(bytes, bincode::config::standard())
-            .expect("Failed to decode QualifiedIdentity")
-            .0
+            .map(|(identity, _)| identity)
+            .map_err(|e| format!("Failed to decode QualifiedIdentity: {}", e))
+    }
+
+    /// Serializes the identity for storage, encrypting its clear private keys when an
+    /// encryption key is given.
+    pub fn to_stored_bytes(&self, encryption_key: Option<&[u8; 32]>) -> Result<Vec<u8>, String> {
+        match encryption_key {
+            Some(encryption_key) => {
+                let mut stored = self.clone();
+                stored.private_keys = self
+                    .private_keys
+                    .with_clear_keys_encrypted(encryption_key)?;
+                Ok(stored.to_bytes())
+            }
+            None => Ok(self.to_bytes()),
+        }
+    }
+
+    /// Deserializes a stored identity, decrypting its private keys when an encryption key is
+    /// given. Without one, encrypted keys stay encrypted.
+    pub fn from_stored_bytes(
+        bytes: &[u8],
+        encryption_key: Option<&[u8; 32]>,
+    ) -> Result<Self, String> {
+        let mut identity = Self::from_bytes(bytes)?;
+        if let Some(encryption_key) = encryption_key {
+            identity.private_keys.decrypt_keys(encryption_key);
+        }
+        Ok(identity)
     }
 
     pub fn display_string(&self) -> String {
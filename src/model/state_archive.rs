@@ -0,0 +1,228 @@
+use crate::database::Database;
+use crate::model::wallet::encryption::{decrypt_message, encrypt_message};
+use bincode::{Decode, Encode};
+use chrono::Utc;
+
+pub const STATE_ARCHIVE_FORMAT_VERSION: u16 = 1;
+
+/// Tables included in a full-state archive, in the order they are restored so that rows
+/// referenced by foreign keys are written first. The proof log is left out on purpose, it is
+/// a debugging aid and can grow very large.
+pub const STATE_ARCHIVE_TABLES: [&str; 17] = [
+    "settings",
+    "wallet",
+    "wallet_addresses",
+    "utxos",
+    "identity",
+    "identity_tags",
+    "asset_lock_transaction",
+    "identity_order",
+    "top_up",
+    "contract",
+    "contested_name",
+    "contestant",
+    "scheduled_votes",
+    "vote_rules",
+    "auto_vote_log",
+    "audit_log",
+    "alias_history",
+];
+
+/// Tables holding seeds, private keys or password checks. Their section payload is encrypted
+/// with the archive passphrase.
+const SENSITIVE_TABLES: [&str; 3] = ["settings", "wallet", "identity"];
+
+/// Identity private keys are encrypted with the main password whose check is kept in
+/// `settings`, so these tables are only restored together. Restoring one without the other
+/// would leave keys that no password decrypts.
+const PRIVATE_KEY_TABLES: [&str; 2] = ["settings", "identity"];
+
+#[derive(Debug, Clone, Encode, Decode, PartialEq)]
+pub enum ArchiveValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Default)]
+pub struct TableDump {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<ArchiveValue>>,
+}
+
+#[derive(Debug, Clone, Encode, Decode, PartialEq)]
+pub enum SectionPayload {
+    Plain(TableDump),
+    Encrypted {
+        ciphertext: Vec<u8>,
+        salt: Vec<u8>,
+        nonce: Vec<u8>,
+    },
+}
+
+#[derive(Debug, Clone, Encode, Decode, PartialEq)]
+pub struct ArchiveSection {
+    pub table: String,
+    pub row_count: u64,
+    pub payload: SectionPayload,
+}
+
+impl ArchiveSection {
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self.payload, SectionPayload::Encrypted { .. })
+    }
+
+    fn decrypt(&self, passphrase: &str) -> Result<TableDump, String> {
+        match &self.payload {
+            SectionPayload::Plain(dump) => Ok(dump.clone()),
+            SectionPayload::Encrypted {
+                ciphertext,
+                salt,
+                nonce,
+            } => {
+                let bytes = decrypt_message(ciphertext, passphrase, salt, nonce)
+                    .map_err(|e| format!("Section {}: {}", self.table, e))?;
+                bincode::decode_from_slice(&bytes, bincode::config::standard())
+                    .map(|(dump, _)| dump)
+                    .map_err(|e| format!("Section {} is corrupted: {}", self.table, e))
+            }
+        }
+    }
+}
+
+/// A single-file snapshot of everything the app keeps in its database: wallets, identities,
+/// contracts, top ups, contested names, scheduled votes and settings.
+#[derive(Debug, Clone, Encode, Decode, PartialEq)]
+pub struct StateArchive {
+    pub format_version: u16,
+    pub db_version: u16,
+    pub created_at: i64,
+    pub sections: Vec<ArchiveSection>,
+}
+
+impl StateArchive {
+    pub fn export(db: &Database, passphrase: &str) -> Result<Self, String> {
+        if passphrase.is_empty() {
+            return Err("A passphrase is required to export the archive".to_string());
+        }
+
+        let mut sections = Vec::with_capacity(STATE_ARCHIVE_TABLES.len());
+        for table in STATE_ARCHIVE_TABLES {
+            let dump = db.dump_table(table).map_err(|e| e.to_string())?;
+            let row_count = dump.rows.len() as u64;
+            let payload = if SENSITIVE_TABLES.contains(&table) {
+                let bytes = bincode::encode_to_vec(&dump, bincode::config::standard())
+                    .map_err(|e| e.to_string())?;
+                let (ciphertext, salt, nonce) = encrypt_message(&bytes, passphrase)?;
+                SectionPayload::Encrypted {
+                    ciphertext,
+                    salt,
+                    nonce,
+                }
+            } else {
+                SectionPayload::Plain(dump)
+            };
+            sections.push(ArchiveSection {
+                table: table.to_string(),
+                row_count,
+                payload,
+            });
+        }
+
+        Ok(Self {
+            format_version: STATE_ARCHIVE_FORMAT_VERSION,
+            db_version: db.database_version().map_err(|e| e.to_string())?,
+            created_at: Utc::now().timestamp(),
+            sections,
+        })
+    }
+
+    /// Restores the selected sections, replacing the current contents of those tables. Selecting
+    /// `settings` or `identity` restores both.
+    pub fn restore(
+        &self,
+        db: &Database,
+        passphrase: &str,
+        tables: &[String],
+    ) -> Result<(), String> {
+        let current_version = db.database_version().map_err(|e| e.to_string())?;
+        if self.db_version > current_version {
+            return Err(format!(
+                "This archive was created with database version {}, but this app only supports up to version {}",
+                self.db_version, current_version
+            ));
+        }
+
+        let restores_private_keys = tables
+            .iter()
+            .any(|selected| PRIVATE_KEY_TABLES.contains(&selected.as_str()));
+        let dumps = STATE_ARCHIVE_TABLES
+            .iter()
+            .filter(|table| {
+                tables.iter().any(|selected| selected == *table)
+                    || (restores_private_keys && PRIVATE_KEY_TABLES.contains(table))
+            })
+            .filter_map(|table| self.sections.iter().find(|s| s.table == *table))
+            .map(|section| Ok((section.table.clone(), section.decrypt(passphrase)?)))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        db.restore_tables(&dumps).map_err(|e| e.to_string())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        bincode::encode_to_vec(self, bincode::config::standard()).map_err(|e| e.to_string())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let archive: Self = bincode::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(archive, _)| archive)
+            .map_err(|e| format!("Not a valid state archive: {}", e))?;
+        if archive.format_version > STATE_ARCHIVE_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported archive format version {}",
+                archive.format_version
+            ));
+        }
+        Ok(archive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+    use std::path::Path;
+
+    #[test]
+    fn test_identities_are_restored_with_settings() {
+        let db = Database::new(":memory:").expect("expected to open database");
+        db.initialize(Path::new(":memory:"))
+            .expect("expected to create tables");
+        db.update_main_password(&[1], &[2], &[3])
+            .expect("expected to save main password");
+        db.execute(
+            "INSERT INTO identity (id, is_local, network) VALUES (?, 1, 'testnet')",
+            params![vec![1u8; 32]],
+        )
+        .unwrap();
+        let archive = StateArchive::export(&db, "passphrase").expect("expected to export");
+
+        db.update_main_password(&[4], &[5], &[6])
+            .expect("expected to save main password");
+        db.execute("DELETE FROM identity", []).unwrap();
+        archive
+            .restore(&db, "passphrase", &["identity".to_string()])
+            .expect("expected to restore");
+
+        let password_info = db.get_settings().unwrap().and_then(|settings| settings.2);
+        assert_eq!(password_info.map(|info| info.salt), Some(vec![1]));
+        let identities = db
+            .query_rows("SELECT id FROM identity", [], |row| {
+                row.get::<_, Vec<u8>>(0)
+            })
+            .unwrap();
+        assert_eq!(identities, vec![vec![1u8; 32]]);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::model::contested_name::ContestedName;
+use chrono::Utc;
+use dash_sdk::dpp::voting::vote_choices::resource_vote_choice::ResourceVoteChoice;
+use dash_sdk::platform::Identifier;
+
+/// A standing instruction to vote a certain way on every contested name matching a pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoteRule {
+    pub id: i64,
+    /// Glob pattern over normalized names, `*` matches any run of characters and `?` a single one.
+    pub name_pattern: String,
+    pub choice: ResourceVoteChoice,
+    pub enabled: bool,
+}
+
+impl VoteRule {
+    pub fn matches(&self, normalized_name: &str) -> bool {
+        glob_matches(
+            self.name_pattern.to_lowercase().as_bytes(),
+            normalized_name.as_bytes(),
+        )
+    }
+
+    /// Returns the vote this rule calls for on `contested_name`, if any. Rules voting towards an
+    /// identity only apply to contests that identity is taking part in.
+    pub fn vote_for(&self, contested_name: &ContestedName) -> Option<ResourceVoteChoice> {
+        if !self.enabled
+            || !contested_name.state.state_is_votable()
+            || !self.matches(&contested_name.normalized_contested_name)
+        {
+            return None;
+        }
+        if let ResourceVoteChoice::TowardsIdentity(identity_id) = self.choice {
+            let is_contestant = contested_name
+                .contestants
+                .as_ref()
+                .is_some_and(|contestants| contestants.iter().any(|c| c.id == identity_id));
+            if !is_contestant {
+                return None;
+            }
+        }
+        Some(self.choice)
+    }
+}
+
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_matches(&pattern[1..], text)
+                || (!text.is_empty() && glob_matches(pattern, &text[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_matches(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) if p == t => glob_matches(&pattern[1..], &text[1..]),
+        _ => false,
+    }
+}
+
+/// What happened to a vote produced by a rule, as recorded in the auto-vote audit trail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoVoteOutcome {
+    Cast,
+    Failed,
+    Dismissed,
+}
+
+impl AutoVoteOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoVoteOutcome::Cast => "Cast",
+            AutoVoteOutcome::Failed => "Failed",
+            AutoVoteOutcome::Dismissed => "Dismissed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Cast" => Some(AutoVoteOutcome::Cast),
+            "Failed" => Some(AutoVoteOutcome::Failed),
+            "Dismissed" => Some(AutoVoteOutcome::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutoVoteLogEntry {
+    pub time: i64,
+    pub contested_name: String,
+    pub choice: ResourceVoteChoice,
+    pub rule_pattern: String,
+    pub voter_id: Option<Identifier>,
+    pub outcome: AutoVoteOutcome,
+    pub error: Option<String>,
+}
+
+impl AutoVoteLogEntry {
+    pub fn now(
+        contested_name: &str,
+        choice: ResourceVoteChoice,
+        rule_pattern: &str,
+        voter_id: Option<Identifier>,
+        outcome: AutoVoteOutcome,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            time: Utc::now().timestamp(),
+            contested_name: contested_name.to_string(),
+            choice,
+            rule_pattern: rule_pattern.to_string(),
+            voter_id,
+            outcome,
+            error,
+        }
+    }
+}
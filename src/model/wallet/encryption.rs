@@ -56,6 +56,51 @@ pub fn encrypt_message(
     Ok((encrypted_seed, salt, nonce))
 }
 
+/// Decrypt a message encrypted with [`encrypt_message`].
+pub fn decrypt_message(
+    encrypted_message: &[u8],
+    password: &str,
+    salt: &[u8],
+    nonce: &[u8],
+) -> Result<Vec<u8>, String> {
+    let key = derive_password_key(password, salt)?;
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|e| e.to_string())?;
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), encrypted_message)
+        .map_err(|_| "Decryption failed, the password may be incorrect".to_string())
+}
+
+/// Encrypt a message with an already derived key. The random nonce is prepended to the output.
+pub fn encrypt_with_key(message: &[u8], key: &[u8]) -> Result<Vec<u8>, String> {
+    let mut nonce = vec![0u8; NONCE_SIZE];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| e.to_string())?;
+
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), message)
+        .map_err(|e| e.to_string())?;
+
+    nonce.extend(ciphertext);
+    Ok(nonce)
+}
+
+/// Decrypt a message encrypted with [`encrypt_with_key`].
+pub fn decrypt_with_key(encrypted_message: &[u8], key: &[u8]) -> Result<Vec<u8>, String> {
+    if encrypted_message.len() < NONCE_SIZE {
+        return Err("Encrypted message is too short".to_string());
+    }
+    let (nonce, ciphertext) = encrypted_message.split_at(NONCE_SIZE);
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|e| e.to_string())?;
+
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Decryption failed, the key may be incorrect".to_string())
+}
+
 impl ClosedKeyItem {
     pub fn compute_seed_hash(seed: &[u8]) -> [u8; 32] {
         let mut hasher = Sha256::new();
@@ -134,6 +179,36 @@ mod tests {
         assert_eq!(seed, decrypted_seed);
     }
 
+    #[test]
+    fn test_encrypt_decrypt_message() {
+        let message = b"some archived table contents";
+        let password = "securepassword";
+
+        let (ciphertext, salt, nonce) =
+            encrypt_message(message, password).expect("Encryption failed");
+
+        let decrypted =
+            decrypt_message(&ciphertext, password, &salt, &nonce).expect("Decryption failed");
+        assert_eq!(message.to_vec(), decrypted);
+
+        assert!(decrypt_message(&ciphertext, "wrongpassword", &salt, &nonce).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_with_key() {
+        let message = [7u8; 32];
+        let key = derive_password_key("securepassword", &[1u8; SALT_SIZE])
+            .expect("Key derivation failed");
+
+        let encrypted = encrypt_with_key(&message, &key).expect("Encryption failed");
+        let decrypted = decrypt_with_key(&encrypted, &key).expect("Decryption failed");
+        assert_eq!(message.to_vec(), decrypted);
+
+        let other_key =
+            derive_password_key("wrongpassword", &[1u8; SALT_SIZE]).expect("Key derivation failed");
+        assert!(decrypt_with_key(&encrypted, &other_key).is_err());
+    }
+
     #[test]
     fn test_incorrect_password() {
         let seed = [42u8; 64]; // A 64-byte seed
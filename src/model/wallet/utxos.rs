@@ -157,7 +157,7 @@ impl Wallet {
 
                     // Remove UTXOs that are no longer unspent
                     for outpoint in removed_outpoints {
-                        db.drop_utxo(&outpoint, &network.to_string())
+                        db.drop_utxo(&outpoint, network)
                             .map_err(|e| e.to_string())?;
                     }
 
@@ -0,0 +1,115 @@
+use crate::model::qualified_identity::encrypted_key_storage::PrivateKeyData;
+use crate::model::qualified_identity::QualifiedIdentity;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use egui::{Color32, Context, RichText};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationResponse {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+/// Modal asking the user to confirm the removal of a local identity. Identities holding
+/// private keys that can't be derived again from a wallet additionally require typing the
+/// identity's alias, or the start of its ID when it has none.
+pub struct IdentityRemovalConfirmation {
+    pub identity: QualifiedIdentity,
+    typed_confirmation: String,
+}
+
+impl IdentityRemovalConfirmation {
+    pub fn new(identity: QualifiedIdentity) -> Self {
+        Self {
+            identity,
+            typed_confirmation: String::new(),
+        }
+    }
+
+    fn stored_private_key_count(&self) -> usize {
+        self.identity
+            .private_keys
+            .private_keys
+            .values()
+            .filter(|(_, data)| !matches!(data, PrivateKeyData::AtWalletDerivationPath(_)))
+            .count()
+    }
+
+    fn identity_id_string(&self) -> String {
+        self.identity
+            .identity
+            .id()
+            .to_string(self.identity.identity_type.default_encoding())
+    }
+
+    fn expected_confirmation(&self) -> String {
+        match self.identity.alias.as_deref() {
+            Some(alias) if !alias.is_empty() => alias.to_string(),
+            _ => self.identity_id_string().chars().take(8).collect(),
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context, title: &str, message: &str) -> ConfirmationResponse {
+        let stored_keys = self.stored_private_key_count();
+        let expected = self.expected_confirmation();
+        let mut response = ConfirmationResponse::Pending;
+
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(message);
+                ui.add_space(5.0);
+                egui::Grid::new("identity_removal_confirmation_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Alias:").strong());
+                        ui.label(self.identity.alias.as_deref().unwrap_or("None"));
+                        ui.end_row();
+
+                        ui.label(RichText::new("Identity ID:").strong());
+                        ui.label(self.identity_id_string());
+                        ui.end_row();
+
+                        ui.label(RichText::new("Type:").strong());
+                        ui.label(self.identity.identity_type.to_string());
+                        ui.end_row();
+                    });
+                ui.add_space(5.0);
+
+                let can_confirm = if stored_keys > 0 {
+                    ui.colored_label(
+                        Color32::DARK_RED,
+                        format!(
+                            "This identity holds {} private key{} that can't be derived from a wallet.",
+                            stored_keys,
+                            if stored_keys == 1 { "" } else { "s" }
+                        ),
+                    );
+                    ui.label(format!("Type \"{}\" to confirm:", expected));
+                    ui.text_edit_singleline(&mut self.typed_confirmation);
+                    self.typed_confirmation.trim() == expected
+                } else {
+                    ui.label("This identity holds no private keys outside of a wallet.");
+                    true
+                };
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(can_confirm, egui::Button::new("Remove"))
+                        .clicked()
+                    {
+                        response = ConfirmationResponse::Confirmed;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        response = ConfirmationResponse::Cancelled;
+                    }
+                });
+            });
+
+        response
+    }
+}
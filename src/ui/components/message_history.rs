@@ -0,0 +1,96 @@
+use crate::context::AppContext;
+use crate::ui::MessageType;
+use chrono::{DateTime, Local, Utc};
+use egui::{Context, RichText};
+use std::collections::VecDeque;
+
+/// Number of messages kept, older ones are dropped.
+pub const MESSAGE_HISTORY_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageHistoryEntry {
+    pub time: DateTime<Utc>,
+    pub message: String,
+    pub message_type: MessageType,
+}
+
+/// The last messages shown by any screen, so they can still be read after the screen moved on.
+#[derive(Debug, Default)]
+pub struct MessageHistory {
+    entries: VecDeque<MessageHistoryEntry>,
+}
+
+impl MessageHistory {
+    pub fn push(&mut self, message: &str, message_type: MessageType) {
+        if self.entries.len() == MESSAGE_HISTORY_LIMIT {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(MessageHistoryEntry {
+            time: Utc::now(),
+            message: message.to_string(),
+            message_type,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The kept messages, newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &MessageHistoryEntry> {
+        self.entries.iter().rev()
+    }
+}
+
+/// Collapsible panel at the bottom of the window listing the message history of `app_context`.
+/// Nothing is shown until there is a message. Has to be added before the screen's central panel.
+pub fn add_message_history_panel(ctx: &Context, app_context: &AppContext) {
+    let mut history = app_context.message_history.lock().unwrap();
+    if history.is_empty() {
+        return;
+    }
+
+    egui::TopBottomPanel::bottom("message_history_panel").show(ctx, |ui| {
+        let error_count = history
+            .entries()
+            .filter(|entry| entry.message_type == MessageType::Error)
+            .count();
+        let header = format!(
+            "Message History ({} messages, {} errors)",
+            history.entries.len(),
+            error_count
+        );
+        egui::CollapsingHeader::new(header).show(ui, |ui| {
+            if ui.button("Clear").clicked() {
+                history.clear();
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for entry in history.entries() {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(
+                                    entry
+                                        .time
+                                        .with_timezone(&Local)
+                                        .format("%H:%M:%S")
+                                        .to_string(),
+                                )
+                                .monospace(),
+                            );
+                            ui.label(
+                                RichText::new(&entry.message)
+                                    .color(entry.message_type.color(ui.visuals())),
+                            );
+                        });
+                    }
+                });
+        });
+    });
+}
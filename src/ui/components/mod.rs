@@ -1,7 +1,13 @@
 pub mod contract_chooser_panel;
 pub mod dpns_subscreen_chooser_panel;
 pub mod entropy_grid;
+pub mod identity_removal_confirmation;
 pub mod left_panel;
+pub mod message_history;
+pub mod private_key_unlock;
+pub mod search_field;
+pub mod sort_header;
+pub mod sortable_table;
 pub mod tools_subscreen_chooser_panel;
 pub mod top_panel;
 pub mod wallet_unlock;
@@ -0,0 +1,102 @@
+use crate::context::AppContext;
+use crate::ui::MessageType;
+use egui::{Align2, Color32, Context};
+use zeroize::Zeroize;
+
+/// State of the main password prompt that unlocks the identity private keys for the session.
+#[derive(Debug, Default)]
+pub struct PrivateKeyUnlockPrompt {
+    password: String,
+    error_message: Option<String>,
+    /// Set when the user put the prompt off, or when there is no main password to ask for
+    pub(crate) dismissed: bool,
+}
+
+/// Window asking for the main password while the identity private keys of `app_context` are
+/// locked, so they can sign and are encrypted when saved. `other_app_context` is unlocked with
+/// the same password. Returns whether the keys were just unlocked.
+pub fn add_private_key_unlock_window(
+    ctx: &Context,
+    app_context: &AppContext,
+    other_app_context: Option<&AppContext>,
+) -> bool {
+    if app_context.private_key_encryption_key().is_some() {
+        return false;
+    }
+    let mut prompt = app_context.private_key_unlock_prompt.lock().unwrap();
+    if prompt.dismissed {
+        return false;
+    }
+    if !app_context.has_main_password() {
+        prompt.dismissed = true;
+        return false;
+    }
+
+    let mut unlock = false;
+    egui::Window::new("Unlock identity keys")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                "Enter your main password to sign with the private keys of your identities. \
+                 Keys are saved encrypted with it.",
+            );
+            ui.add_space(5.0);
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut prompt.password)
+                    .password(true)
+                    .hint_text("Main password"),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            ui.horizontal(|ui| {
+                if ui.button("Unlock").clicked() || submitted {
+                    unlock = true;
+                }
+                if ui
+                    .button("Later")
+                    .on_hover_text(
+                        "Identities with new keys can't be saved until the keys are unlocked",
+                    )
+                    .clicked()
+                {
+                    prompt.dismissed = true;
+                }
+            });
+            if let Some(error) = &prompt.error_message {
+                ui.colored_label(Color32::DARK_RED, error);
+            }
+        });
+    if !unlock {
+        return false;
+    }
+
+    let mut password = std::mem::take(&mut prompt.password);
+    // Unlocking saves every identity again, which must not wait on the prompt
+    drop(prompt);
+    let result = app_context.unlock_private_keys(&password);
+    // The keys are unlocked before the identities are saved again, which may still fail
+    let unlocked = app_context.private_key_encryption_key().is_some();
+    if unlocked {
+        if let Some(other_app_context) = other_app_context {
+            let _ = other_app_context.unlock_private_keys(&password);
+        }
+    }
+    password.zeroize();
+
+    match result {
+        Ok(()) => {}
+        Err(e) if unlocked => app_context.record_message(
+            &format!("Saving identities with encrypted keys failed: {}", e),
+            MessageType::Error,
+        ),
+        Err(e) => {
+            app_context
+                .private_key_unlock_prompt
+                .lock()
+                .unwrap()
+                .error_message = Some(e)
+        }
+    }
+    unlocked
+}
@@ -0,0 +1,27 @@
+use egui::{Key, Response, TextEdit, Ui};
+
+/// Single line search box followed by a ✕ button that clears it. Pressing Escape while the box
+/// has focus clears it as well and gives up the focus. The returned response is marked changed
+/// whenever the text changed, clearing included.
+pub fn search_field(ui: &mut Ui, text: &mut String, hint_text: &str) -> Response {
+    ui.horizontal(|ui| {
+        let mut response = ui.add(TextEdit::singleline(text).hint_text(hint_text));
+        // The text edit may already have given up its focus on Escape by the time we look
+        let escape_pressed = (response.has_focus() || response.lost_focus())
+            && ui.input(|i| i.key_pressed(Key::Escape));
+        if escape_pressed {
+            response.surrender_focus();
+        }
+        let clear_clicked = !text.is_empty()
+            && ui
+                .small_button("✕")
+                .on_hover_text("Clear the search")
+                .clicked();
+        if (escape_pressed || clear_clicked) && !text.is_empty() {
+            text.clear();
+            response.mark_changed();
+        }
+        response
+    })
+    .inner
+}
@@ -0,0 +1,12 @@
+use egui::{Response, RichText, Ui};
+
+/// Header button of a sortable table column. `sorted_ascending` is `Some` when the table is
+/// sorted by this column, the label is then bold and followed by an arrow.
+pub fn sortable_header(ui: &mut Ui, label: &str, sorted_ascending: Option<bool>) -> Response {
+    let text = match sorted_ascending {
+        Some(true) => RichText::new(format!("{} ▲", label)).strong(),
+        Some(false) => RichText::new(format!("{} ▼", label)).strong(),
+        None => RichText::new(label),
+    };
+    ui.button(text)
+}
@@ -0,0 +1,142 @@
+use crate::ui::components::sort_header::sortable_header;
+use egui::{Button, Grid, Ui};
+use std::cmp::Ordering;
+
+/// A column of a [`SortableTable`], with the comparison used when sorting by it.
+pub struct SortableColumn<T> {
+    pub title: &'static str,
+    pub compare: fn(&T, &T) -> Ordering,
+}
+
+impl<T> SortableColumn<T> {
+    pub fn new(title: &'static str, compare: fn(&T, &T) -> Ordering) -> Self {
+        Self { title, compare }
+    }
+}
+
+/// Table sorted by clicking a column header and shown one page at a time. The table keeps the
+/// sorting and paging state, the screen owns the rows and draws their cells.
+pub struct SortableTable<T> {
+    id: &'static str,
+    columns: Vec<SortableColumn<T>>,
+    sort_column: usize,
+    sort_ascending: bool,
+    page: usize,
+    rows_per_page: usize,
+}
+
+impl<T> SortableTable<T> {
+    pub fn new(id: &'static str, columns: Vec<SortableColumn<T>>, rows_per_page: usize) -> Self {
+        Self {
+            id,
+            columns,
+            sort_column: 0,
+            sort_ascending: true,
+            page: 0,
+            rows_per_page: rows_per_page.max(1),
+        }
+    }
+
+    /// Starts sorted by the column at `column`, in the given direction.
+    pub fn sorted_by(mut self, column: usize, ascending: bool) -> Self {
+        self.sort_column = column.min(self.columns.len().saturating_sub(1));
+        self.sort_ascending = ascending;
+        self
+    }
+
+    /// Sorts `rows` by the current column. The screen calls this after loading new rows.
+    pub fn sort(&self, rows: &mut [T]) {
+        let Some(column) = self.columns.get(self.sort_column) else {
+            return;
+        };
+        rows.sort_by(|a, b| {
+            let ordering = (column.compare)(a, b);
+            if self.sort_ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+
+    fn page_count(&self, row_count: usize) -> usize {
+        row_count.div_ceil(self.rows_per_page).max(1)
+    }
+
+    /// Shows the headers, the current page of `rows` and the paging buttons. `render_row` draws
+    /// the cells of one row and is given its index in `rows`. Clicking a header sorts `rows` in
+    /// place, clicking the sorted column again reverses the order. Returns whether `rows` were
+    /// sorted, so indexes the screen holds into them can be reset.
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        rows: &mut [T],
+        mut render_row: impl FnMut(&mut Ui, usize, &T),
+    ) -> bool {
+        let page_count = self.page_count(rows.len());
+        self.page = self.page.min(page_count - 1);
+        let start = self.page * self.rows_per_page;
+        let end = (start + self.rows_per_page).min(rows.len());
+
+        let mut clicked_column = None;
+        Grid::new(self.id)
+            .num_columns(self.columns.len())
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, column) in self.columns.iter().enumerate() {
+                    let sorted_ascending =
+                        (index == self.sort_column).then_some(self.sort_ascending);
+                    if sortable_header(ui, column.title, sorted_ascending)
+                        .on_hover_text(format!("Click to sort by {}", column.title))
+                        .clicked()
+                    {
+                        clicked_column = Some(index);
+                    }
+                }
+                ui.end_row();
+
+                for (index, row) in rows.iter().enumerate().take(end).skip(start) {
+                    render_row(ui, index, row);
+                    ui.end_row();
+                }
+            });
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.page > 0, Button::new("Previous"))
+                .clicked()
+            {
+                self.page -= 1;
+            }
+            if ui
+                .add_enabled(self.page + 1 < page_count, Button::new("Next"))
+                .clicked()
+            {
+                self.page += 1;
+            }
+            if rows.is_empty() {
+                ui.label("No rows");
+            } else {
+                ui.label(format!(
+                    "Showing rows {} to {} of {}",
+                    start + 1,
+                    end,
+                    rows.len()
+                ));
+            }
+        });
+
+        let Some(column) = clicked_column else {
+            return false;
+        };
+        if column == self.sort_column {
+            self.sort_ascending = !self.sort_ascending;
+        } else {
+            self.sort_column = column;
+            self.sort_ascending = true;
+        }
+        self.page = 0;
+        self.sort(rows);
+        true
+    }
+}
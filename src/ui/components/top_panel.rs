@@ -1,8 +1,10 @@
 use crate::app::{AppAction, DesiredAppAction};
 use crate::backend_task::core::CoreTask;
+use crate::backend_task::platform_status::PlatformConnection;
 use crate::backend_task::BackendTask;
 use crate::components::core_zmq_listener::ZMQConnectionEvent;
 use crate::context::AppContext;
+use crate::ui::theme::ThemeMode;
 use dash_sdk::dashcore_rpc::dashcore::Network;
 use egui::{Align, Color32, Context, Frame, Layout, Margin, RichText, Stroke, TopBottomPanel, Ui};
 use std::sync::Arc;
@@ -120,6 +122,86 @@ fn add_connection_indicator(ui: &mut Ui, app_context: &Arc<AppContext>) -> AppAc
     action
 }
 
+/// Button testing the connection to Platform, labelled with the result of the last test.
+fn add_platform_status(ui: &mut Ui, app_context: &Arc<AppContext>) -> AppAction {
+    let status = app_context
+        .platform_connection_status
+        .lock()
+        .unwrap()
+        .clone();
+    let (text, hover_text) = match &status {
+        None => (
+            "Test Connection".to_string(),
+            "Check that Platform can be reached".to_string(),
+        ),
+        Some(status) => {
+            let checked_at = status
+                .checked_at
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S");
+            let details = match &status.connection {
+                PlatformConnection::Online {
+                    latency,
+                    platform_height,
+                    core_height,
+                } => format!(
+                    "Answered in {} ms at {}, platform height {}, core height {}",
+                    latency.as_millis(),
+                    checked_at,
+                    platform_height,
+                    core_height
+                ),
+                PlatformConnection::TimedOut => format!("Timed out at {}", checked_at),
+                PlatformConnection::Failed(e) => format!("Failed at {}: {}", checked_at, e),
+            };
+            let text = if status.is_online() {
+                "Platform: online"
+            } else {
+                "Platform: offline"
+            };
+            (
+                text.to_string(),
+                format!("{}. Click to test again.", details),
+            )
+        }
+    };
+
+    let fill = match &status {
+        Some(status) if status.is_online() => Color32::DARK_GREEN,
+        Some(_) => Color32::DARK_RED,
+        None => Color32::from_rgb(0, 128, 255),
+    };
+    let button = egui::Button::new(RichText::new(text).color(Color32::WHITE))
+        .fill(fill)
+        .rounding(3.0)
+        .stroke(Stroke::new(1.0, Color32::WHITE))
+        .min_size(egui::vec2(0.0, 30.0));
+    if ui.add(button).on_hover_text(hover_text).clicked() {
+        AppAction::BackendTask(BackendTask::TestPlatformConnection)
+    } else {
+        AppAction::None
+    }
+}
+
+fn add_theme_menu(ui: &mut Ui, app_context: &Arc<AppContext>) -> AppAction {
+    let mut action = AppAction::None;
+    let current = app_context.db.get_theme_mode().unwrap_or_default();
+
+    ui.menu_button(RichText::new("Theme").color(Color32::WHITE), |ui| {
+        for theme_mode in ThemeMode::ALL {
+            if ui
+                .radio(theme_mode == current, theme_mode.label())
+                .clicked()
+            {
+                action = AppAction::SetTheme(theme_mode);
+                ui.close_menu();
+            }
+        }
+    });
+
+    action
+}
+
 pub fn add_top_panel(
     ctx: &Context,
     app_context: &Arc<AppContext>,
@@ -177,13 +259,50 @@ pub fn add_top_panel(
                             .stroke(Stroke::new(1.0, Color32::WHITE)) // Border stroke
                             .min_size(egui::vec2(button_width, 30.0));
 
-                        if ui.add(button).clicked() {
+                        // Screens opened from the top panel create or import things
+                        let writes = match &right_button_action {
+                            DesiredAppAction::AddScreenType(_) => true,
+                            DesiredAppAction::BackendTask(task) => !task.is_read_only(),
+                            DesiredAppAction::BackendTasks(tasks, _) => {
+                                tasks.iter().any(|task| !task.is_read_only())
+                            }
+                            _ => false,
+                        };
+                        if ui
+                            .add_enabled(!(writes && app_context.is_read_only()), button)
+                            .on_disabled_hover_text("Not available in read-only mode")
+                            .clicked()
+                        {
                             action = right_button_action.create_action(app_context);
                         }
                     }
+
+                    ui.add_space(3.0);
+                    if app_context.is_read_only() {
+                        ui.label(
+                            RichText::new("READ-ONLY")
+                                .strong()
+                                .color(Color32::WHITE)
+                                .background_color(Color32::from_rgb(200, 120, 0)),
+                        )
+                        .on_hover_text(
+                            "Nothing is saved or broadcast, turn it off in the settings",
+                        );
+                        ui.add_space(3.0);
+                    }
+                    action |= add_platform_status(ui, app_context);
+                    ui.add_space(3.0);
+                    action |= add_theme_menu(ui, app_context);
                 });
             });
         });
 
+    // Escape goes back, unless it is already closing a popup or leaving a focused text field
+    let escape_pressed = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+    let escape_handled = ctx.memory(|m| m.focused().is_some() || m.any_popup_open());
+    if escape_pressed && !escape_handled && action == AppAction::None {
+        action = AppAction::Back;
+    }
+
     action
 }
@@ -193,6 +193,10 @@ impl ScreenLike for AddContractsScreen {
         }
     }
 
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
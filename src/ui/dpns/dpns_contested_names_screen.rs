@@ -13,12 +13,15 @@ use egui_extras::{Column, TableBuilder};
 use itertools::Itertools;
 
 use crate::app::{AppAction, BackendTasksExecutionMode, DesiredAppAction};
-use crate::backend_task::contested_names::{ContestedResourceTask, ScheduledDPNSVote};
+use crate::backend_task::contested_names::{
+    ContestedResourceTask, ProposedDPNSVote, ScheduledDPNSVote,
+};
 use crate::backend_task::identity::IdentityTask;
 use crate::backend_task::BackendTask;
 use crate::context::AppContext;
 use crate::model::contested_name::{ContestState, ContestedName};
 use crate::model::qualified_identity::{DPNSNameInfo, QualifiedIdentity};
+use crate::model::vote_rule::{AutoVoteLogEntry, VoteRule};
 use crate::ui::components::dpns_subscreen_chooser_panel::add_dpns_subscreen_chooser_panel;
 use crate::ui::components::left_panel::add_left_panel;
 use crate::ui::components::top_panel::add_top_panel;
@@ -77,6 +80,17 @@ pub enum VoteHandlingStatus {
     Failed(String),
 }
 
+/// The kind of vote a new vote rule casts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RuleChoiceKind {
+    TowardsIdentity,
+    Abstain,
+    Lock,
+}
+
+/// Number of audit trail entries shown in the vote rules window.
+const AUTO_VOTE_LOG_LIMIT: u32 = 50;
+
 #[derive(PartialEq)]
 pub enum RefreshingStatus {
     Refreshing(u64),
@@ -134,6 +148,17 @@ pub struct DPNSScreen {
     bulk_schedule_message: Option<(MessageType, String)>,
     bulk_vote_handling_status: VoteHandlingStatus,
     set_all_option: VoteOption,
+
+    /// Vote rules
+    proposed_votes: Vec<ProposedDPNSVote>,
+    show_vote_rules: bool,
+    vote_rules: Vec<VoteRule>,
+    auto_vote_log: Vec<AutoVoteLogEntry>,
+    auto_vote_fully_automatic: bool,
+    new_rule_pattern: String,
+    new_rule_choice_kind: RuleChoiceKind,
+    new_rule_towards_identity: String,
+    vote_rules_error: Option<String>,
 }
 
 impl DPNSScreen {
@@ -205,6 +230,20 @@ impl DPNSScreen {
             bulk_schedule_message: None,
             bulk_vote_handling_status: VoteHandlingStatus::NotStarted,
             set_all_option: VoteOption::CastNow,
+
+            // Vote rules
+            proposed_votes: Vec::new(),
+            show_vote_rules: false,
+            vote_rules: Vec::new(),
+            auto_vote_log: Vec::new(),
+            auto_vote_fully_automatic: app_context
+                .db
+                .get_auto_vote_fully_automatic()
+                .unwrap_or_default(),
+            new_rule_pattern: String::new(),
+            new_rule_choice_kind: RuleChoiceKind::TowardsIdentity,
+            new_rule_towards_identity: String::new(),
+            vote_rules_error: None,
         }
     }
 
@@ -1705,6 +1744,268 @@ impl DPNSScreen {
     }
 }
 
+// ---------------------------
+// Vote rules
+// ---------------------------
+impl DPNSScreen {
+    fn vote_choice_label(choice: &ResourceVoteChoice) -> String {
+        match choice {
+            ResourceVoteChoice::TowardsIdentity(id) => id.to_string(Encoding::Base58),
+            other => other.to_string(),
+        }
+    }
+
+    fn reload_vote_rules(&mut self) {
+        self.vote_rules = self
+            .app_context
+            .db
+            .get_vote_rules(&self.app_context)
+            .unwrap_or_default();
+        self.auto_vote_log = self
+            .app_context
+            .db
+            .get_auto_vote_log(&self.app_context, AUTO_VOTE_LOG_LIMIT)
+            .unwrap_or_default();
+    }
+
+    fn add_vote_rule(&mut self) -> Result<(), String> {
+        let pattern = self.new_rule_pattern.trim().to_lowercase();
+        if pattern.is_empty() {
+            return Err("Enter a name pattern, for example \"satoshi*\"".to_string());
+        }
+        let choice = match self.new_rule_choice_kind {
+            RuleChoiceKind::Abstain => ResourceVoteChoice::Abstain,
+            RuleChoiceKind::Lock => ResourceVoteChoice::Lock,
+            RuleChoiceKind::TowardsIdentity => ResourceVoteChoice::TowardsIdentity(
+                Identifier::from_string(self.new_rule_towards_identity.trim(), Encoding::Base58)
+                    .map_err(|_| "Invalid identity ID".to_string())?,
+            ),
+        };
+        self.app_context
+            .db
+            .insert_vote_rule(&self.app_context, &pattern, choice)
+            .map_err(|e| format!("Error saving vote rule: {}", e))?;
+        self.new_rule_pattern.clear();
+        self.new_rule_towards_identity.clear();
+        Ok(())
+    }
+
+    fn show_vote_rules_window(&mut self, ctx: &Context) {
+        let mut open = self.show_vote_rules;
+        let mut rules_changed = false;
+        let writable = !self.app_context.is_read_only();
+        egui::Window::new("Vote Rules")
+            .collapsible(false)
+            .resizable(true)
+            .vscroll(true)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Rules are checked every time contests are refreshed. The first enabled rule matching a contested name decides the vote, cast by all loaded masternode identities.");
+                ui.add_space(10.0);
+
+                egui::Grid::new("vote_rules_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Pattern");
+                        ui.strong("Vote");
+                        ui.strong("Enabled");
+                        ui.label("");
+                        ui.end_row();
+                        for rule in &mut self.vote_rules {
+                            ui.label(&rule.name_pattern);
+                            ui.label(Self::vote_choice_label(&rule.choice));
+                            if ui
+                                .add_enabled(writable, egui::Checkbox::new(&mut rule.enabled, ""))
+                                .changed()
+                            {
+                                let _ = self
+                                    .app_context
+                                    .db
+                                    .set_vote_rule_enabled(rule.id, rule.enabled);
+                            }
+                            if ui
+                                .add_enabled(writable, egui::Button::new("Delete"))
+                                .clicked()
+                            {
+                                let _ = self.app_context.db.delete_vote_rule(rule.id);
+                                rules_changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Names matching:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_rule_pattern)
+                            .hint_text("satoshi*")
+                            .desired_width(120.0),
+                    );
+                    ComboBox::from_id_salt("new_rule_choice_kind")
+                        .selected_text(match self.new_rule_choice_kind {
+                            RuleChoiceKind::TowardsIdentity => "Vote towards",
+                            RuleChoiceKind::Abstain => "Abstain",
+                            RuleChoiceKind::Lock => "Lock",
+                        })
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.new_rule_choice_kind,
+                                RuleChoiceKind::TowardsIdentity,
+                                "Vote towards",
+                            );
+                            ui.selectable_value(
+                                &mut self.new_rule_choice_kind,
+                                RuleChoiceKind::Abstain,
+                                "Abstain",
+                            );
+                            ui.selectable_value(
+                                &mut self.new_rule_choice_kind,
+                                RuleChoiceKind::Lock,
+                                "Lock",
+                            );
+                        });
+                    if self.new_rule_choice_kind == RuleChoiceKind::TowardsIdentity {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.new_rule_towards_identity)
+                                .hint_text("Identity ID")
+                                .desired_width(200.0),
+                        );
+                    }
+                    if ui
+                        .add_enabled(writable, egui::Button::new("Add Rule"))
+                        .on_disabled_hover_text("Not available in read-only mode")
+                        .clicked()
+                    {
+                        match self.add_vote_rule() {
+                            Ok(()) => {
+                                self.vote_rules_error = None;
+                                rules_changed = true;
+                            }
+                            Err(e) => self.vote_rules_error = Some(e),
+                        }
+                    }
+                });
+                if let Some(error) = &self.vote_rules_error {
+                    ui.colored_label(Color32::DARK_RED, error);
+                }
+
+                ui.add_space(10.0);
+                if ui
+                    .add_enabled(
+                        writable,
+                        egui::Checkbox::new(
+                            &mut self.auto_vote_fully_automatic,
+                            "Fully automatic: cast rule votes without asking for confirmation",
+                        ),
+                    )
+                    .changed()
+                {
+                    let _ = self
+                        .app_context
+                        .db
+                        .update_auto_vote_fully_automatic(self.auto_vote_fully_automatic);
+                }
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.heading("Audit Trail");
+                if self.auto_vote_log.is_empty() {
+                    ui.label("No rule votes yet.");
+                } else {
+                    egui::Grid::new("auto_vote_log_grid")
+                        .num_columns(6)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for entry in &self.auto_vote_log {
+                                let time = Utc
+                                    .timestamp_opt(entry.time, 0)
+                                    .single()
+                                    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_default();
+                                ui.label(time);
+                                ui.label(&entry.contested_name);
+                                ui.label(Self::vote_choice_label(&entry.choice));
+                                ui.label(&entry.rule_pattern);
+                                ui.label(
+                                    entry
+                                        .voter_id
+                                        .as_ref()
+                                        .map(|id| id.to_string(Encoding::Base58))
+                                        .unwrap_or_default(),
+                                );
+                                match &entry.error {
+                                    Some(error) => {
+                                        ui.colored_label(Color32::DARK_RED, error)
+                                            .on_hover_text(error);
+                                    }
+                                    None => {
+                                        ui.label(entry.outcome.as_str());
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+        self.show_vote_rules = open;
+        if rules_changed {
+            self.reload_vote_rules();
+        }
+    }
+
+    fn show_proposed_votes_window(&mut self, ctx: &Context) -> AppAction {
+        let mut action = AppAction::None;
+        egui::Window::new("Proposed Votes")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("Your vote rules match these contests:");
+                ui.add_space(5.0);
+                egui::Grid::new("proposed_votes_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for proposal in &self.proposed_votes {
+                            ui.label(&proposal.contested_name);
+                            ui.label(Self::vote_choice_label(&proposal.choice));
+                            ui.label(format!("rule \"{}\"", proposal.rule_pattern));
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(5.0);
+                ui.label(format!(
+                    "Votes will be cast by {} masternode identities.",
+                    self.voting_identities.len()
+                ));
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Cast Votes").clicked() {
+                        action = AppAction::BackendTask(BackendTask::ContestedResourceTask(
+                            ContestedResourceTask::CastProposedDPNSVotes(
+                                std::mem::take(&mut self.proposed_votes),
+                                self.voting_identities.clone(),
+                            ),
+                        ));
+                    }
+                    if ui.button("Dismiss").clicked() {
+                        action = AppAction::BackendTask(BackendTask::ContestedResourceTask(
+                            ContestedResourceTask::DismissProposedDPNSVotes(std::mem::take(
+                                &mut self.proposed_votes,
+                            )),
+                        ));
+                    }
+                    if ui.button("Later").clicked() {
+                        self.proposed_votes.clear();
+                    }
+                });
+            });
+        action
+    }
+}
+
 // ---------------------------
 // ScreenLike implementation
 // ---------------------------
@@ -1857,6 +2158,9 @@ impl ScreenLike for DPNSScreen {
                         Some((MessageType::Success, "Votes scheduled".to_string()));
                 }
             }
+            BackendTaskSuccessResult::ProposedDPNSVotes(proposals) => {
+                self.proposed_votes = proposals;
+            }
             BackendTaskSuccessResult::CastScheduledVote(vote) => {
                 if let Ok(mut guard) = self.scheduled_votes.lock() {
                     if let Some((_, status)) = guard.iter_mut().find(|(v, _)| {
@@ -1887,16 +2191,21 @@ impl ScreenLike for DPNSScreen {
                         ContestedResourceTask::QueryDPNSContests,
                     )),
                 );
+                let vote_rules_button = (
+                    "Vote Rules",
+                    DesiredAppAction::Custom("VoteRules".to_string()),
+                );
                 if has_active_contests {
                     vec![
                         refresh_button,
+                        vote_rules_button,
                         (
                             "Cast/Schedule Votes",
                             DesiredAppAction::Custom("Vote".to_string()),
                         ),
                     ]
                 } else {
-                    vec![refresh_button]
+                    vec![refresh_button, vote_rules_button]
                 }
             }
             DPNSSubscreen::Past => {
@@ -1960,6 +2269,12 @@ impl ScreenLike for DPNSScreen {
             action = AppAction::None; // clear it out so we don't re-trigger
         }
 
+        if action == AppAction::Custom("VoteRules".to_string()) {
+            self.reload_vote_rules();
+            self.show_vote_rules = true;
+            action = AppAction::None;
+        }
+
         // Left panel
         match self.dpns_subscreen {
             DPNSSubscreen::Active => {
@@ -2071,11 +2386,7 @@ impl ScreenLike for DPNSScreen {
 
             // If there's a backend message, show it at the bottom
             if let Some((msg, msg_type, timestamp)) = self.message.clone() {
-                let color = match msg_type {
-                    MessageType::Error => Color32::DARK_RED,
-                    MessageType::Info => Color32::BLACK,
-                    MessageType::Success => Color32::DARK_GREEN,
-                };
+                let color = msg_type.color(ui.visuals());
                 ui.group(|ui| {
                     ui.horizontal_wrapped(|ui| {
                         ui.colored_label(color, &msg);
@@ -2092,6 +2403,13 @@ impl ScreenLike for DPNSScreen {
             }
         });
 
+        if self.show_vote_rules {
+            self.show_vote_rules_window(ctx);
+        }
+        if !self.proposed_votes.is_empty() {
+            action |= self.show_proposed_votes_window(ctx);
+        }
+
         // Extra handling for actions
         match action {
             // If refreshing contested names, set self.refreshing = true
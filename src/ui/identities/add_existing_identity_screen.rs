@@ -509,6 +509,10 @@ impl ScreenLike for AddExistingIdentityScreen {
         self.add_identity_status = AddIdentityStatus::Complete;
     }
 
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
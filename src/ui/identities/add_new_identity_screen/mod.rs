@@ -76,6 +76,8 @@ pub struct AddNewIdentityScreen {
     in_key_selection_advanced_mode: bool,
     pub app_context: Arc<AppContext>,
     successful_qualified_identity_id: Option<Identifier>,
+    /// Identity index kept when the wallet gets unlocked, set when resuming a registration
+    resume_identity_index: Option<u32>,
 }
 
 impl AddNewIdentityScreen {
@@ -172,9 +174,86 @@ impl AddNewIdentityScreen {
             in_key_selection_advanced_mode: false,
             app_context: app_context.clone(),
             successful_qualified_identity_id: None,
+            resume_identity_index: None,
         }
     }
 
+    /// Opens the screen on the wallet, identity index and alias of an identity whose
+    /// registration never completed, so it can be registered again. Unused asset locks, such
+    /// as the one left by the failed attempt, are offered first when the wallet has some.
+    pub fn new_resuming(
+        app_context: &Arc<AppContext>,
+        wallet: Arc<RwLock<Wallet>>,
+        identity_index: u32,
+        alias: Option<String>,
+    ) -> Self {
+        let mut screen = Self::new(app_context);
+        let (is_open, has_unused_asset_lock) = {
+            let wallet = wallet.read().unwrap();
+            (wallet.is_open(), wallet.has_unused_asset_lock())
+        };
+        screen.selected_wallet = Some(wallet);
+        screen.identity_id_number = identity_index;
+        screen.resume_identity_index = Some(identity_index);
+        screen.alias_input = alias.unwrap_or_default();
+        if is_open {
+            screen.reset_identity_keys();
+        }
+        if has_unused_asset_lock {
+            *screen.funding_method.write().unwrap() = FundingMethod::UseUnusedAssetLock;
+            *screen.step.write().unwrap() = WalletFundedScreenStep::ReadyToCreate;
+        }
+        screen
+    }
+
+    /// Sets the master key and the default authentication and transfer keys of the selected
+    /// wallet's identity at `identity_id_number`. The wallet must be open.
+    fn reset_identity_keys(&mut self) {
+        let Some(wallet_guard) = self.selected_wallet.as_ref() else {
+            return;
+        };
+        let mut wallet = wallet_guard.write().unwrap();
+        self.identity_keys.master_private_key = Some(
+            wallet
+                .identity_authentication_ecdsa_private_key(
+                    self.app_context.network,
+                    self.identity_id_number,
+                    0,
+                    Some(&self.app_context),
+                )
+                .expect("expected to have decrypted wallet"),
+        );
+        // Update the additional keys input
+        self.identity_keys.keys_input = vec![
+            (
+                wallet
+                    .identity_authentication_ecdsa_private_key(
+                        self.app_context.network,
+                        self.identity_id_number,
+                        1,
+                        Some(&self.app_context),
+                    )
+                    .expect("expected to have decrypted wallet"),
+                KeyType::ECDSA_HASH160,
+                Purpose::AUTHENTICATION,
+                SecurityLevel::HIGH,
+            ),
+            (
+                wallet
+                    .identity_authentication_ecdsa_private_key(
+                        self.app_context.network,
+                        self.identity_id_number,
+                        2,
+                        Some(&self.app_context),
+                    )
+                    .expect("expected to have decrypted wallet"),
+                KeyType::ECDSA_HASH160,
+                Purpose::TRANSFER,
+                SecurityLevel::CRITICAL,
+            ),
+        ];
+    }
+
     // // Start the balance checking process
     // pub fn start_balance_check(&mut self, check_address: &Address, ui_context: &Context) {
     //     let app_context = self.app_context.clone();
@@ -921,6 +1000,10 @@ impl ScreenLike for AddNewIdentityScreen {
             WalletFundedScreenStep::Success => {}
         }
     }
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
@@ -958,51 +1041,14 @@ impl ScreenLike for AddNewIdentityScreen {
 
                 if needed_unlock {
                     if just_unlocked {
-                        let wallet_guard = self.selected_wallet.as_ref().unwrap();
-                        let mut wallet = wallet_guard.write().unwrap();
-
-                        self.identity_id_number =
-                            wallet.identities.keys().copied().max().map(|max| max + 1).unwrap_or_default();
-
-                        self.identity_keys.master_private_key = Some(
-                            wallet
-                                .identity_authentication_ecdsa_private_key(
-                                    self.app_context.network,
-                                    self.identity_id_number,
-                                    0,
-                                    Some(&self.app_context),
-                                )
-                                .expect("expected to have decrypted wallet"),
-                        );
-                        // Update the additional keys input
-                        self.identity_keys.keys_input = vec![
-                            (
-                                wallet
-                                    .identity_authentication_ecdsa_private_key(
-                                        self.app_context.network,
-                                        self.identity_id_number,
-                                        1,
-                                        Some(&self.app_context),
-                                    )
-                                    .expect("expected to have decrypted wallet"),
-                                KeyType::ECDSA_HASH160,
-                                Purpose::AUTHENTICATION,
-                                SecurityLevel::HIGH,
-                            ),
-                            (
-                                wallet
-                                    .identity_authentication_ecdsa_private_key(
-                                        self.app_context.network,
-                                        self.identity_id_number,
-                                        2,
-                                        Some(&self.app_context),
-                                    )
-                                    .expect("expected to have decrypted wallet"),
-                                KeyType::ECDSA_HASH160,
-                                Purpose::TRANSFER,
-                                SecurityLevel::CRITICAL,
-                            ),
-                        ];
+                        self.identity_id_number = match self.resume_identity_index {
+                            Some(identity_index) => identity_index,
+                            None => {
+                                let wallet = self.selected_wallet.as_ref().unwrap().read().unwrap();
+                                wallet.identities.keys().copied().max().map(|max| max + 1).unwrap_or_default()
+                            }
+                        };
+                        self.reset_identity_keys();
                     } else {
                         return;
                     }
@@ -0,0 +1,444 @@
+use crate::app::AppAction;
+use crate::backend_task::identity::{
+    BulkTopUpOutcome, IdentityTask, IdentityTopUpInfo, TopUpIdentityFundingMethod,
+};
+use crate::backend_task::{BackendTask, BackendTaskSuccessResult};
+use crate::context::AppContext;
+use crate::model::qualified_identity::QualifiedIdentity;
+use crate::model::wallet::Wallet;
+use crate::ui::components::top_panel::add_top_panel;
+use crate::ui::components::wallet_unlock::ScreenWithWalletUnlock;
+use crate::ui::{MessageType, ScreenLike};
+use dash_sdk::dpp::balances::credits::Duffs;
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::platform::Identifier;
+use eframe::egui::{self, Context, Ui};
+use egui::{Color32, ComboBox, RichText, ScrollArea};
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq)]
+enum BulkTopUpAmountMode {
+    PerIdentity,
+    SplitTotal,
+}
+
+#[derive(PartialEq)]
+enum BulkTopUpStatus {
+    NotStarted,
+    WaitingForResult(u64),
+    Complete(Vec<BulkTopUpOutcome>),
+}
+
+pub struct BulkTopUpScreen {
+    identities: Vec<QualifiedIdentity>,
+    selected_identities: HashSet<Identifier>,
+    wallet: Option<Arc<RwLock<Wallet>>>,
+    amount_mode: BulkTopUpAmountMode,
+    amount: String,
+    status: BulkTopUpStatus,
+    error_message: Option<String>,
+    show_password: bool,
+    wallet_password: String,
+    pub app_context: Arc<AppContext>,
+}
+
+impl BulkTopUpScreen {
+    pub fn new(app_context: &Arc<AppContext>) -> Self {
+        let identities = app_context
+            .load_local_qualified_identities()
+            .unwrap_or_default();
+        let wallet = app_context.wallets.read().unwrap().values().next().cloned();
+
+        Self {
+            identities,
+            selected_identities: HashSet::new(),
+            wallet,
+            amount_mode: BulkTopUpAmountMode::PerIdentity,
+            amount: "0.1".to_string(),
+            status: BulkTopUpStatus::NotStarted,
+            error_message: None,
+            show_password: false,
+            wallet_password: String::new(),
+            app_context: app_context.clone(),
+        }
+    }
+
+    fn render_wallet_selection(&mut self, ui: &mut Ui) {
+        let wallets = self.app_context.wallets.read().unwrap();
+        let wallet_alias = |wallet: &Arc<RwLock<Wallet>>| {
+            wallet
+                .read()
+                .ok()
+                .and_then(|w| w.alias.clone())
+                .unwrap_or_else(|| "Unnamed Wallet".to_string())
+        };
+
+        let selected_wallet_alias = self
+            .wallet
+            .as_ref()
+            .map(wallet_alias)
+            .unwrap_or_else(|| "Select".to_string());
+
+        ComboBox::from_id_salt("bulk_top_up_select_wallet")
+            .selected_text(selected_wallet_alias)
+            .show_ui(ui, |ui| {
+                for wallet in wallets.values() {
+                    let is_selected = self
+                        .wallet
+                        .as_ref()
+                        .map_or(false, |selected| Arc::ptr_eq(selected, wallet));
+
+                    if ui
+                        .selectable_label(is_selected, wallet_alias(wallet))
+                        .clicked()
+                    {
+                        self.wallet = Some(wallet.clone());
+                    }
+                }
+            });
+
+        if let Some(wallet) = &self.wallet {
+            let balance = wallet.read().unwrap().max_balance();
+            ui.label(format!("Wallet Balance: {:.8} DASH", balance as f64 * 1e-8));
+        }
+    }
+
+    fn render_identity_selection(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("Select all").clicked() {
+                self.selected_identities =
+                    self.identities.iter().map(|qi| qi.identity.id()).collect();
+            }
+            if ui.button("Select none").clicked() {
+                self.selected_identities.clear();
+            }
+        });
+        ui.add_space(5.0);
+
+        ScrollArea::vertical()
+            .id_salt("bulk_top_up_identities")
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for qualified_identity in &self.identities {
+                    let identity_id = qualified_identity.identity.id();
+                    let mut selected = self.selected_identities.contains(&identity_id);
+                    let label = format!(
+                        "{} ({:.4} DASH)",
+                        qualified_identity.display_short_string(),
+                        qualified_identity.identity.balance() as f64 * 1e-11
+                    );
+                    if ui.checkbox(&mut selected, label).changed() {
+                        if selected {
+                            self.selected_identities.insert(identity_id);
+                        } else {
+                            self.selected_identities.remove(&identity_id);
+                        }
+                    }
+                }
+            });
+    }
+
+    fn render_amount_input(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.radio_value(
+                &mut self.amount_mode,
+                BulkTopUpAmountMode::PerIdentity,
+                "Amount per identity",
+            );
+            ui.radio_value(
+                &mut self.amount_mode,
+                BulkTopUpAmountMode::SplitTotal,
+                "Split a total equally",
+            );
+        });
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Amount (DASH):");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.amount)
+                    .hint_text("Enter amount (e.g., 0.1234)")
+                    .desired_width(100.0),
+            );
+        });
+
+        if let Ok(per_identity) = self.amount_per_identity() {
+            ui.label(format!(
+                "Each selected identity will be topped up with {:.8} DASH",
+                per_identity as f64 * 1e-8
+            ));
+        }
+    }
+
+    /// The amount in Duffs to fund each selected identity with.
+    fn amount_per_identity(&self) -> Result<Duffs, String> {
+        let amount = self
+            .amount
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| "Invalid amount. Please enter a valid number.".to_string())?;
+        let amount = (amount * 1e8) as Duffs;
+
+        let per_identity = match self.amount_mode {
+            BulkTopUpAmountMode::PerIdentity => amount,
+            BulkTopUpAmountMode::SplitTotal => {
+                if self.selected_identities.is_empty() {
+                    return Err("Please select at least one identity.".to_string());
+                }
+                amount / self.selected_identities.len() as u64
+            }
+        };
+
+        if per_identity == 0 {
+            return Err("Amount must be greater than 0.".to_string());
+        }
+        Ok(per_identity)
+    }
+
+    fn top_up_clicked(&mut self) -> AppAction {
+        let Some(wallet) = self.wallet.clone() else {
+            self.error_message = Some("Please select a wallet.".to_string());
+            return AppAction::None;
+        };
+        if self.selected_identities.is_empty() {
+            self.error_message = Some("Please select at least one identity.".to_string());
+            return AppAction::None;
+        }
+        let per_identity = match self.amount_per_identity() {
+            Ok(per_identity) => per_identity,
+            Err(e) => {
+                self.error_message = Some(e);
+                return AppAction::None;
+            }
+        };
+
+        let total = per_identity * self.selected_identities.len() as u64;
+        if total > wallet.read().unwrap().max_balance() {
+            self.error_message = Some(format!(
+                "Insufficient wallet balance: {:.8} DASH needed.",
+                total as f64 * 1e-8
+            ));
+            return AppAction::None;
+        }
+
+        let top_ups = self
+            .identities
+            .iter()
+            .filter(|qi| self.selected_identities.contains(&qi.identity.id()))
+            .map(|qi| IdentityTopUpInfo {
+                qualified_identity: qi.clone(),
+                wallet: wallet.clone(),
+                identity_funding_method: TopUpIdentityFundingMethod::FundWithWallet(
+                    per_identity,
+                    qi.wallet_index.unwrap_or(u32::MAX >> 1),
+                    qi.top_ups
+                        .keys()
+                        .max()
+                        .cloned()
+                        .map(|i| i + 1)
+                        .unwrap_or_default(),
+                ),
+            })
+            .collect();
+
+        self.error_message = None;
+        self.status = BulkTopUpStatus::WaitingForResult(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_secs(),
+        );
+
+        AppAction::BackendTask(BackendTask::IdentityTask(
+            IdentityTask::BulkTopUpIdentities(top_ups),
+        ))
+    }
+
+    fn show_results(&self, ui: &mut Ui, outcomes: &[BulkTopUpOutcome]) -> AppAction {
+        let mut action = AppAction::None;
+
+        let succeeded = outcomes.iter().filter(|o| o.result.is_ok()).count();
+        ui.heading(format!(
+            "Topped up {} of {} identities",
+            succeeded,
+            outcomes.len()
+        ));
+        ui.add_space(10.0);
+
+        egui::Grid::new("bulk_top_up_results")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for outcome in outcomes {
+                    ui.label(&outcome.display_name);
+                    match &outcome.result {
+                        Ok(balance) => {
+                            ui.colored_label(
+                                Color32::DARK_GREEN,
+                                format!("New balance: {:.4} DASH", *balance as f64 * 1e-11),
+                            );
+                        }
+                        Err(e) => {
+                            ui.colored_label(Color32::DARK_RED, format!("Failed: {}", e));
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(20.0);
+        if ui.button("Back to Identities").clicked() {
+            action = AppAction::PopScreenAndRefresh;
+        }
+
+        action
+    }
+}
+
+impl ScreenWithWalletUnlock for BulkTopUpScreen {
+    fn selected_wallet_ref(&self) -> &Option<Arc<RwLock<Wallet>>> {
+        &self.wallet
+    }
+
+    fn wallet_password_ref(&self) -> &String {
+        &self.wallet_password
+    }
+
+    fn wallet_password_mut(&mut self) -> &mut String {
+        &mut self.wallet_password
+    }
+
+    fn show_password(&self) -> bool {
+        self.show_password
+    }
+
+    fn show_password_mut(&mut self) -> &mut bool {
+        &mut self.show_password
+    }
+
+    fn set_error_message(&mut self, error_message: Option<String>) {
+        self.error_message = error_message;
+    }
+
+    fn error_message(&self) -> Option<&String> {
+        self.error_message.as_ref()
+    }
+}
+
+impl ScreenLike for BulkTopUpScreen {
+    fn refresh(&mut self) {
+        self.identities = self
+            .app_context
+            .load_local_qualified_identities()
+            .unwrap_or_default();
+    }
+
+    fn display_message(&mut self, message: &str, message_type: MessageType) {
+        if message_type == MessageType::Error {
+            self.status = BulkTopUpStatus::NotStarted;
+            self.error_message = Some(format!("Error topping up identities: {}", message));
+        }
+    }
+
+    fn display_task_result(&mut self, backend_task_success_result: BackendTaskSuccessResult) {
+        if let BackendTaskSuccessResult::BulkToppedUpIdentities(outcomes) =
+            backend_task_success_result
+        {
+            self.status = BulkTopUpStatus::Complete(outcomes);
+        }
+    }
+
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
+    fn ui(&mut self, ctx: &Context) -> AppAction {
+        let mut action = add_top_panel(
+            ctx,
+            &self.app_context,
+            vec![
+                ("Identities", AppAction::GoToMainScreen),
+                ("Bulk Top Up", AppAction::None),
+            ],
+            vec![],
+        );
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                if let BulkTopUpStatus::Complete(outcomes) = &self.status {
+                    action |= self.show_results(ui, outcomes);
+                    return;
+                }
+
+                ui.heading("Top up several identities at once");
+                ui.add_space(15.0);
+
+                ui.heading("1. Choose the wallet to fund the top ups from.");
+                ui.add_space(5.0);
+                self.render_wallet_selection(ui);
+
+                if self.wallet.is_none() {
+                    return;
+                }
+
+                let (needed_unlock, just_unlocked) = self.render_wallet_unlock_if_needed(ui);
+                if needed_unlock && !just_unlocked {
+                    return;
+                }
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.heading("2. Select the identities to top up.");
+                ui.add_space(5.0);
+                self.render_identity_selection(ui);
+
+                ui.add_space(10.0);
+                ui.separator();
+                ui.add_space(10.0);
+
+                ui.heading("3. Choose how much to top up.");
+                ui.add_space(5.0);
+                self.render_amount_input(ui);
+
+                ui.add_space(10.0);
+
+                let waiting = matches!(self.status, BulkTopUpStatus::WaitingForResult(_));
+                let mut new_style = (**ui.style()).clone();
+                new_style.spacing.button_padding = egui::vec2(10.0, 5.0);
+                ui.set_style(new_style);
+                let button =
+                    egui::Button::new(RichText::new("Top Up Identities").color(Color32::WHITE))
+                        .fill(Color32::from_rgb(0, 128, 255))
+                        .frame(true)
+                        .rounding(3.0);
+                if ui.add_enabled(!waiting, button).clicked() {
+                    action |= self.top_up_clicked();
+                }
+
+                ui.add_space(10.0);
+
+                if let BulkTopUpStatus::WaitingForResult(start_time) = self.status {
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("Time went backwards")
+                        .as_secs();
+                    ui.label(format!(
+                        "Topping up {} identities... Time taken so far: {} seconds",
+                        self.selected_identities.len(),
+                        now - start_time
+                    ));
+                }
+
+                if let Some(error_message) = &self.error_message {
+                    ui.colored_label(Color32::DARK_RED, error_message);
+                }
+            });
+        });
+
+        action
+    }
+}
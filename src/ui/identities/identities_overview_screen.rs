@@ -0,0 +1,125 @@
+use crate::app::AppAction;
+use crate::context::AppContext;
+use crate::database::IdentitySummary;
+use crate::model::dash_price::{format_dash_amount, DashPrice};
+use crate::ui::components::top_panel::add_top_panel;
+use crate::ui::{MessageType, ScreenLike};
+use eframe::egui::{self, Context, Frame, RichText, Ui};
+use std::sync::Arc;
+
+pub struct IdentitiesOverviewScreen {
+    pub app_context: Arc<AppContext>,
+    summary: Option<IdentitySummary>,
+    dash_price: Option<DashPrice>,
+    error_message: Option<String>,
+}
+
+impl IdentitiesOverviewScreen {
+    pub fn new(app_context: &Arc<AppContext>) -> Self {
+        let mut screen = Self {
+            app_context: app_context.clone(),
+            summary: None,
+            dash_price: None,
+            error_message: None,
+        };
+        screen.refresh();
+        screen
+    }
+
+    fn render_card(ui: &mut Ui, title: &str, value: String) {
+        Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_min_width(160.0);
+            ui.vertical(|ui| {
+                ui.label(RichText::new(title).color(egui::Color32::GRAY));
+                ui.label(RichText::new(value).size(24.0).strong());
+            });
+        });
+    }
+
+    fn render_summary(ui: &mut Ui, summary: &IdentitySummary, dash_price: Option<&DashPrice>) {
+        ui.horizontal_wrapped(|ui| {
+            Self::render_card(ui, "Identities", summary.total_count().to_string());
+            for (identity_type, count) in &summary.count_by_type {
+                let title = if identity_type.is_empty() {
+                    "Unknown type".to_string()
+                } else {
+                    format!("{} identities", identity_type)
+                };
+                Self::render_card(ui, &title, count.to_string());
+            }
+        });
+        ui.add_space(10.0);
+        ui.horizontal_wrapped(|ui| {
+            Self::render_card(
+                ui,
+                "Total topped up",
+                format_dash_amount(summary.total_top_up_amount as f64 / 1e8, 8, dash_price),
+            );
+            Self::render_card(
+                ui,
+                "With stored private keys",
+                summary.with_private_keys.to_string(),
+            );
+        });
+    }
+}
+
+impl ScreenLike for IdentitiesOverviewScreen {
+    fn refresh(&mut self) {
+        self.dash_price = self.app_context.db.get_dash_price().unwrap_or_default();
+        match self
+            .app_context
+            .db
+            .local_identity_summary(&self.app_context)
+        {
+            Ok(summary) => {
+                self.summary = Some(summary);
+                self.error_message = None;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Error loading identity summary: {}", e));
+            }
+        }
+    }
+
+    fn display_message(&mut self, message: &str, message_type: MessageType) {
+        if message_type == MessageType::Error {
+            self.error_message = Some(message.to_string());
+        }
+    }
+
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
+    fn ui(&mut self, ctx: &Context) -> AppAction {
+        let action = add_top_panel(
+            ctx,
+            &self.app_context,
+            vec![
+                ("Identities", AppAction::GoToMainScreen),
+                ("Overview", AppAction::None),
+            ],
+            vec![],
+        );
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Identities Overview");
+            ui.add_space(10.0);
+
+            if let Some(error_message) = &self.error_message {
+                ui.colored_label(egui::Color32::RED, error_message);
+                ui.add_space(10.0);
+            }
+
+            match &self.summary {
+                Some(summary) => Self::render_summary(ui, summary, self.dash_price.as_ref()),
+                None => {
+                    ui.label("No summary available.");
+                }
+            }
+        });
+
+        action
+    }
+}
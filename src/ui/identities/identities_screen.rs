@@ -1,34 +1,47 @@
 use super::withdraw_screen::WithdrawalScreen;
 use crate::app::{AppAction, BackendTasksExecutionMode, DesiredAppAction};
-use crate::backend_task::identity::IdentityTask;
-use crate::backend_task::BackendTask;
+use crate::backend_task::identity::{IdentityBalanceReconciliation, IdentityTask};
+use crate::backend_task::{BackendTask, BackendTaskSuccessResult};
 use crate::context::AppContext;
+use crate::database::{AliasChange, AliasImportOutcome, SetAliasError};
+use crate::model::alias_import::parse_alias_import_file;
+use crate::model::dash_price::DashPrice;
+use crate::model::identity_backup::{IdentityBackup, IDENTITY_BACKUP_EXTENSION};
+use crate::model::identity_changes::IdentityChanges;
+use crate::model::identity_public_info::identities_public_info_json;
 use crate::model::qualified_identity::encrypted_key_storage::{
     PrivateKeyData, WalletDerivationPath,
 };
 use crate::model::qualified_identity::PrivateKeyTarget::{
     PrivateKeyOnMainIdentity, PrivateKeyOnVoterIdentity,
 };
-use crate::model::qualified_identity::{IdentityType, QualifiedIdentity};
+use crate::model::qualified_identity::{IdentityType, PrivateKeyTarget, QualifiedIdentity};
 use crate::model::wallet::WalletSeedHash;
+use crate::ui::components::identity_removal_confirmation::{
+    ConfirmationResponse, IdentityRemovalConfirmation,
+};
 use crate::ui::components::left_panel::add_left_panel;
+use crate::ui::components::search_field::search_field;
+use crate::ui::components::sort_header::sortable_header;
 use crate::ui::components::top_panel::add_top_panel;
+use crate::ui::identities::add_new_identity_screen::AddNewIdentityScreen;
 use crate::ui::identities::keys::add_key_screen::AddKeyScreen;
 use crate::ui::identities::keys::key_info_screen::KeyInfoScreen;
 use crate::ui::identities::top_up_identity_screen::TopUpIdentityScreen;
 use crate::ui::identities::transfer_screen::TransferScreen;
 use crate::ui::{MessageType, RootScreenType, Screen, ScreenLike, ScreenType};
 use chrono::{DateTime, Utc};
-use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
+use dash_sdk::dpp::identity::accessors::{IdentityGettersV0, IdentitySettersV0};
+use dash_sdk::dpp::identity::hash::IdentityPublicKeyHashMethodsV0;
 use dash_sdk::dpp::identity::identity_public_key::accessors::v0::IdentityPublicKeyGettersV0;
-use dash_sdk::dpp::identity::Purpose;
+use dash_sdk::dpp::identity::{KeyID, Purpose};
 use dash_sdk::dpp::platform_value::string_encoding::Encoding;
 use dash_sdk::dpp::prelude::IdentityPublicKey;
 use dash_sdk::platform::Identifier;
 use dash_sdk::query_types::IndexMap;
 use eframe::egui::{self, Context};
 use eframe::emath::Align;
-use egui::{Color32, Frame, Margin, RichText, Ui};
+use egui::{Color32, ComboBox, Frame, Margin, RichText, TextStyle, Ui};
 use egui_extras::{Column, TableBuilder};
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::Ordering;
@@ -49,36 +62,169 @@ enum IdentitiesSortOrder {
     Descending,
 }
 
+impl IdentitiesSortColumn {
+    /// Order used when the column is first clicked, numbers start with the largest.
+    fn default_order(&self) -> IdentitiesSortOrder {
+        match self {
+            IdentitiesSortColumn::Balance => IdentitiesSortOrder::Descending,
+            IdentitiesSortColumn::Alias
+            | IdentitiesSortColumn::IdentityID
+            | IdentitiesSortColumn::InWallet
+            | IdentitiesSortColumn::Type => IdentitiesSortOrder::Ascending,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeySearchKind {
+    KeyId,
+    PublicKeyHex,
+    PublicKeyHash,
+}
+
+impl KeySearchKind {
+    fn label(&self) -> &'static str {
+        match self {
+            KeySearchKind::KeyId => "Key ID",
+            KeySearchKind::PublicKeyHex => "Public key (hex)",
+            KeySearchKind::PublicKeyHash => "Public key hash (hex)",
+        }
+    }
+
+    fn matches(&self, key: &IdentityPublicKey, query: &str) -> bool {
+        match self {
+            KeySearchKind::KeyId => query.parse::<KeyID>().map_or(false, |id| key.id() == id),
+            KeySearchKind::PublicKeyHex => hex::decode(query)
+                .map_or(false, |bytes| key.data().as_slice() == bytes.as_slice()),
+            KeySearchKind::PublicKeyHash => match (hex::decode(query), key.public_key_hash()) {
+                (Ok(bytes), Ok(hash)) => hash.as_slice() == bytes.as_slice(),
+                _ => false,
+            },
+        }
+    }
+}
+
+struct KeySearch {
+    kind: KeySearchKind,
+    query: String,
+}
+
+enum IdentityBackupDialog {
+    Export {
+        identity_id: Identifier,
+        passphrase: String,
+    },
+    Import {
+        backups: Vec<IdentityBackup>,
+        passphrase: String,
+    },
+}
+
 #[derive(PartialEq)]
 enum IdentitiesRefreshingStatus {
     Refreshing(u64),
     NotRefreshing,
 }
 
+/// After this long without a successful refresh from Platform the last refresh time is
+/// highlighted.
+const STALE_REFRESH_SECONDS: i64 = 10 * 60;
+
+/// Aliases offered when the alias search matches no identity.
+const MAX_ALIAS_SUGGESTIONS: usize = 5;
+
+/// Number of stored identities loaded at a time, the others are loaded on demand.
+const IDENTITIES_PAGE_SIZE: u32 = 100;
+
+/// Initial widths of the Name, Identity ID, In Wallet, Type, Keys, Balance and Actions columns.
+const DEFAULT_COLUMN_WIDTHS: [f32; 7] = [80.0, 330.0, 60.0, 80.0, 80.0, 140.0, 120.0];
+
 pub struct IdentitiesScreen {
     pub identities: Arc<Mutex<IndexMap<Identifier, QualifiedIdentity>>>,
     pub app_context: Arc<AppContext>,
     pub show_more_keys_popup: Option<QualifiedIdentity>,
-    pub identity_to_remove: Option<QualifiedIdentity>,
+    pub identity_to_remove: Option<IdentityRemovalConfirmation>,
     pub wallet_seed_hash_cache: HashMap<WalletSeedHash, String>,
     sort_column: IdentitiesSortColumn,
     sort_order: IdentitiesSortOrder,
     use_custom_order: bool,
     refreshing_status: IdentitiesRefreshingStatus,
     backend_message: Option<(String, MessageType, DateTime<Utc>)>,
+    table_font_size: f32,
+    monospace_ids: bool,
+    pending_auto_refresh: bool,
+    balance_reconciliations: Option<Vec<IdentityBalanceReconciliation>>,
+    identity_details: Option<Identifier>,
+    key_search: Option<KeySearch>,
+    recently_deleted: Option<Vec<QualifiedIdentity>>,
+    identity_nonces: HashMap<Identifier, u64>,
+    column_widths: Vec<f32>,
+    // Bumped whenever the column widths are reset so egui forgets the adjusted widths
+    column_widths_generation: u32,
+    all_tags: Vec<String>,
+    tag_filter: Option<String>,
+    tag_filter_ids: HashSet<Identifier>,
+    alias_search: String,
+    alias_search_ids: HashSet<Identifier>,
+    // Closest aliases offered when no alias matches the search
+    alias_suggestions: Vec<String>,
+    // Tags of the identity shown in the details window, loaded when the window opens
+    identity_details_tags: Option<(Identifier, Vec<String>)>,
+    // Alias changes of the identity shown in the details window, with the alias they were
+    // loaded for so a rename reloads them
+    identity_details_alias_history: Option<(Identifier, Option<String>, Vec<AliasChange>)>,
+    new_tag_input: String,
+    identity_backup: Option<IdentityBackupDialog>,
+    dash_price: Option<DashPrice>,
+    favorite_ids: HashSet<Identifier>,
+    last_refreshed: Option<DateTime<Utc>>,
+    in_creation_ids: HashSet<Identifier>,
+    // Wallet and identity index of the identities being created, to resume their registration
+    in_creation_wallets: HashMap<Identifier, (WalletSeedHash, u32)>,
+    // When each identity was last fetched from Platform, as a unix timestamp
+    last_synced: HashMap<Identifier, i64>,
+    // Refreshed identities whose keys changed on Platform, waiting for the user to save them
+    pending_identity_changes: Vec<(QualifiedIdentity, IdentityChanges)>,
+    // Outcome of each entry of the last alias import, shown until closed
+    alias_import_results: Option<Vec<(String, Result<String, String>)>>,
+    // Stored identity rows read so far, and how many there are in total
+    identities_loaded_rows: u32,
+    identity_count: u32,
+}
+
+fn skipped_identities_message(skipped: usize) -> String {
+    format!(
+        "{} stored identit{} could not be read and {} skipped, see the log for details",
+        skipped,
+        if skipped == 1 { "y" } else { "ies" },
+        if skipped == 1 { "was" } else { "were" }
+    )
 }
 
 impl IdentitiesScreen {
     pub fn new(app_context: &Arc<AppContext>) -> Self {
-        let initial_map: IndexMap<Identifier, QualifiedIdentity> = app_context
-            .load_local_qualified_identities()
-            .unwrap_or_default()
+        let (initial_identities, skipped) = app_context
+            .load_local_qualified_identities_page(IDENTITIES_PAGE_SIZE, 0)
+            .unwrap_or_default();
+        let initial_map: IndexMap<Identifier, QualifiedIdentity> = initial_identities
             .into_iter()
             .map(|qi| (qi.identity.id(), qi))
             .collect();
 
         let identities = Arc::new(Mutex::new(initial_map));
 
+        let (table_font_size, monospace_ids) = app_context
+            .db
+            .get_table_display_settings()
+            .expect("Failed to db get table display settings");
+
+        let column_widths = app_context
+            .db
+            .get_identities_column_widths()
+            .expect("Failed to db get identities column widths")
+            .filter(|widths| widths.len() == DEFAULT_COLUMN_WIDTHS.len())
+            .unwrap_or_else(|| DEFAULT_COLUMN_WIDTHS.to_vec());
+
         let mut screen = Self {
             identities,
             app_context: app_context.clone(),
@@ -89,7 +235,60 @@ impl IdentitiesScreen {
             sort_order: IdentitiesSortOrder::Ascending,
             use_custom_order: true,
             refreshing_status: IdentitiesRefreshingStatus::NotRefreshing,
-            backend_message: None,
+            backend_message: (skipped > 0).then(|| {
+                (
+                    skipped_identities_message(skipped),
+                    MessageType::Error,
+                    Utc::now(),
+                )
+            }),
+            table_font_size,
+            monospace_ids,
+            pending_auto_refresh: false,
+            balance_reconciliations: None,
+            identity_details: None,
+            key_search: None,
+            recently_deleted: None,
+            identity_nonces: HashMap::new(),
+            column_widths,
+            column_widths_generation: 0,
+            all_tags: Vec::new(),
+            tag_filter: None,
+            tag_filter_ids: HashSet::new(),
+            alias_search: String::new(),
+            alias_search_ids: HashSet::new(),
+            alias_suggestions: Vec::new(),
+            identity_details_tags: None,
+            identity_details_alias_history: None,
+            new_tag_input: String::new(),
+            identity_backup: None,
+            dash_price: app_context.db.get_dash_price().unwrap_or_default(),
+            favorite_ids: app_context
+                .db
+                .get_favorite_identity_ids(app_context)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            last_refreshed: None,
+            in_creation_ids: app_context
+                .db
+                .get_in_creation_identity_ids(app_context)
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            in_creation_wallets: Self::load_in_creation_wallets(app_context),
+            last_synced: app_context
+                .db
+                .get_identity_last_synced_times(app_context)
+                .unwrap_or_default(),
+
+            pending_identity_changes: Vec::new(),
+            alias_import_results: None,
+            identities_loaded_rows: IDENTITIES_PAGE_SIZE,
+            identity_count: app_context
+                .db
+                .count_local_qualified_identities(app_context)
+                .unwrap_or_default(),
         };
 
         if let Ok(saved_ids) = screen.app_context.db.load_identity_order() {
@@ -98,9 +297,425 @@ impl IdentitiesScreen {
             screen.use_custom_order = true;
         }
 
+        screen.reload_tags();
+
         screen
     }
 
+    /// Reloads the tags offered by the tag filter and the identities matching the selected one.
+    fn reload_tags(&mut self) {
+        self.all_tags = self
+            .app_context
+            .db
+            .get_all_identity_tags(&self.app_context)
+            .unwrap_or_default();
+        if self
+            .tag_filter
+            .as_ref()
+            .is_some_and(|tag| !self.all_tags.contains(tag))
+        {
+            self.tag_filter = None;
+        }
+        self.tag_filter_ids = match &self.tag_filter {
+            Some(tag) => self
+                .app_context
+                .db
+                .list_identities_by_tag(tag, &self.app_context)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|qi| qi.identity.id())
+                .collect(),
+            None => HashSet::new(),
+        };
+        self.identity_details_tags = None;
+        self.identity_details_alias_history = None;
+    }
+
+    fn render_last_refreshed(&self, ui: &mut Ui) {
+        let Some(last_refreshed) = self.last_refreshed else {
+            return;
+        };
+        let seconds_ago = (Utc::now() - last_refreshed).num_seconds().max(0);
+        let text = format!(
+            "Last updated: {} ({} seconds ago)",
+            last_refreshed
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S"),
+            seconds_ago
+        );
+        if seconds_ago > STALE_REFRESH_SECONDS {
+            ui.colored_label(Color32::from_rgb(200, 120, 0), text)
+                .on_hover_text("Refresh to fetch the latest state from Platform");
+        } else {
+            ui.label(text);
+        }
+    }
+
+    /// Loads the next `rows` stored identities after the ones already listed.
+    fn load_more_identities(&mut self, rows: u32) {
+        match self
+            .app_context
+            .load_local_qualified_identities_page(rows, self.identities_loaded_rows)
+        {
+            Ok((identities, skipped)) => {
+                self.identities
+                    .lock()
+                    .unwrap()
+                    .extend(identities.into_iter().map(|qi| (qi.identity.id(), qi)));
+                self.identities_loaded_rows += rows;
+                if skipped > 0 {
+                    self.backend_message = Some((
+                        skipped_identities_message(skipped),
+                        MessageType::Error,
+                        Utc::now(),
+                    ));
+                }
+                if let Ok(saved_ids) = self.app_context.db.load_identity_order() {
+                    self.reorder_map_to(saved_ids);
+                }
+            }
+            Err(e) => {
+                self.backend_message = Some((
+                    format!("Error loading identities: {}", e),
+                    MessageType::Error,
+                    Utc::now(),
+                ));
+            }
+        }
+    }
+
+    /// Tells how many of the stored identities are listed, with buttons to load the others.
+    fn render_load_more_identities(&mut self, ui: &mut Ui) {
+        if self.identities_loaded_rows >= self.identity_count {
+            return;
+        }
+        let mut rows_to_load = None;
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Showing {} of {} identities",
+                self.identities_loaded_rows, self.identity_count
+            ));
+            if ui.button("Load more").clicked() {
+                rows_to_load = Some(IDENTITIES_PAGE_SIZE);
+            }
+            if ui
+                .button("Load all")
+                .on_hover_text("Searching, filtering and sorting only apply to listed identities")
+                .clicked()
+            {
+                rows_to_load = Some(self.identity_count - self.identities_loaded_rows);
+            }
+        });
+        if let Some(rows) = rows_to_load {
+            self.load_more_identities(rows);
+        }
+    }
+
+    fn load_in_creation_wallets(
+        app_context: &AppContext,
+    ) -> HashMap<Identifier, (WalletSeedHash, u32)> {
+        app_context
+            .db
+            .get_in_creation_identities(app_context)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(qualified_identity, wallet, wallet_index)| {
+                let seed_hash = WalletSeedHash::try_from(wallet).ok()?;
+                Some((qualified_identity.identity.id(), (seed_hash, wallet_index)))
+            })
+            .collect()
+    }
+
+    /// Lists the identities whose registration never completed, each with a button to abandon
+    /// it and, when its wallet is loaded, one to register it again from the same wallet index.
+    fn render_identities_in_creation(
+        &mut self,
+        ui: &mut Ui,
+        identities: &[QualifiedIdentity],
+    ) -> AppAction {
+        let mut action = AppAction::None;
+        let in_creation: Vec<&QualifiedIdentity> = identities
+            .iter()
+            .filter(|qi| self.in_creation_ids.contains(&qi.identity.id()))
+            .collect();
+        if in_creation.is_empty() {
+            return action;
+        }
+
+        let mut canceled = None;
+        egui::CollapsingHeader::new(format!(
+            "Identities being created ({})",
+            in_creation.len()
+        ))
+        .id_salt("identities_in_creation")
+        .show(ui, |ui| {
+            for qualified_identity in in_creation {
+                let identity_id = qualified_identity.identity.id();
+                ui.horizontal(|ui| {
+                    ui.label(qualified_identity.alias.as_deref().unwrap_or("(no alias)"));
+                    ui.label(
+                        RichText::new(identity_id.to_string(
+                            qualified_identity.identity_type.default_encoding(),
+                        ))
+                        .monospace(),
+                    );
+                    let resume_wallet = self.in_creation_wallets.get(&identity_id).and_then(
+                        |(seed_hash, wallet_index)| {
+                            let wallets = self.app_context.wallets.read().unwrap();
+                            Some((wallets.get(seed_hash)?.clone(), *wallet_index))
+                        },
+                    );
+                    if let Some((wallet, wallet_index)) = resume_wallet {
+                        if ui
+                            .add_enabled(
+                                !self.app_context.is_read_only(),
+                                egui::Button::new("Continue"),
+                            )
+                            .on_hover_text(format!(
+                                "Register this identity again from identity index {} of its wallet",
+                                wallet_index
+                            ))
+                            .clicked()
+                        {
+                            action = AppAction::AddScreen(Screen::AddNewIdentityScreen(
+                                AddNewIdentityScreen::new_resuming(
+                                    &self.app_context,
+                                    wallet,
+                                    wallet_index,
+                                    qualified_identity.alias.clone(),
+                                ),
+                            ));
+                        }
+                    }
+                    if ui
+                        .button("Cancel")
+                        .on_hover_text(
+                            "Abandon this registration and remove the identity, its asset lock goes back to the wallet",
+                        )
+                        .clicked()
+                    {
+                        canceled = Some(identity_id);
+                    }
+                });
+            }
+        });
+
+        if let Some(identity_id) = canceled {
+            let result = self
+                .app_context
+                .db
+                .cancel_identity_in_creation(&identity_id, &self.app_context);
+            self.backend_message = Some(match result {
+                Ok(_) => (
+                    "Identity creation canceled".to_string(),
+                    MessageType::Success,
+                    Utc::now(),
+                ),
+                Err(e) => (
+                    format!("Could not cancel identity creation: {}", e),
+                    MessageType::Error,
+                    Utc::now(),
+                ),
+            });
+            self.refresh();
+        }
+        action
+    }
+
+    fn render_tag_filter(&mut self, ui: &mut Ui) {
+        if self.all_tags.is_empty() {
+            return;
+        }
+        let mut selected = self.tag_filter.clone();
+        ui.horizontal(|ui| {
+            ui.label("Tag:");
+            ComboBox::from_id_salt("identity_tag_filter")
+                .selected_text(selected.as_deref().unwrap_or("All"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut selected, None, "All");
+                    for tag in &self.all_tags {
+                        ui.selectable_value(&mut selected, Some(tag.clone()), tag);
+                    }
+                });
+        });
+        if selected != self.tag_filter {
+            self.tag_filter = selected;
+            self.reload_tags();
+        }
+        ui.add_space(5.0);
+    }
+
+    /// Reloads the identities matching the alias search, with suggestions when there are none.
+    fn reload_alias_search(&mut self) {
+        let query = self.alias_search.trim();
+        if query.is_empty() {
+            self.alias_search_ids.clear();
+            self.alias_suggestions.clear();
+            return;
+        }
+        self.alias_search_ids = self
+            .app_context
+            .db
+            .search_local_identities_by_alias(&self.app_context, query)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|qi| qi.identity.id())
+            .collect();
+        self.alias_suggestions = if self.alias_search_ids.is_empty() {
+            self.app_context
+                .db
+                .suggest_aliases(&self.app_context, query, MAX_ALIAS_SUGGESTIONS)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+    }
+
+    fn render_alias_search(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Alias:");
+            changed |= search_field(ui, &mut self.alias_search, "Search by alias").changed();
+        });
+        if !self.alias_suggestions.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("No identity has this alias. Did you mean:");
+                for suggestion in &self.alias_suggestions {
+                    if ui.link(suggestion).clicked() {
+                        self.alias_search = suggestion.clone();
+                        changed = true;
+                    }
+                }
+            });
+        }
+        if changed {
+            self.reload_alias_search();
+        }
+        ui.add_space(5.0);
+    }
+
+    fn show_alias_history(&mut self, ui: &mut Ui, qualified_identity: &QualifiedIdentity) {
+        let identity_id = qualified_identity.identity.id();
+        if self
+            .identity_details_alias_history
+            .as_ref()
+            .map_or(true, |(id, alias, _)| {
+                *id != identity_id || *alias != qualified_identity.alias
+            })
+        {
+            let history = self
+                .app_context
+                .db
+                .get_alias_history(&identity_id)
+                .unwrap_or_default();
+            self.identity_details_alias_history =
+                Some((identity_id, qualified_identity.alias.clone(), history));
+        }
+        let Some((_, _, history)) = &self.identity_details_alias_history else {
+            return;
+        };
+        if history.is_empty() {
+            return;
+        }
+
+        ui.add_space(5.0);
+        egui::CollapsingHeader::new(RichText::new("Previous Aliases").strong()).show(ui, |ui| {
+            egui::Grid::new("identity_alias_history_grid")
+                .num_columns(3)
+                .spacing([10.0, 5.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    let alias_label = |alias: &Option<String>| {
+                        alias.clone().unwrap_or_else(|| "(none)".to_string())
+                    };
+                    for change in history {
+                        ui.label(
+                            DateTime::from_timestamp(change.time, 0)
+                                .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                                .unwrap_or_default(),
+                        );
+                        ui.label(alias_label(&change.old_alias));
+                        ui.label(format!("→ {}", alias_label(&change.new_alias)));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn show_identity_tags(&mut self, ui: &mut Ui, identity_id: Identifier) {
+        if self
+            .identity_details_tags
+            .as_ref()
+            .map_or(true, |(id, _)| *id != identity_id)
+        {
+            let tags = self
+                .app_context
+                .db
+                .get_identity_tags(&identity_id)
+                .unwrap_or_default();
+            self.identity_details_tags = Some((identity_id, tags));
+        }
+        let tags = self
+            .identity_details_tags
+            .as_ref()
+            .map(|(_, tags)| tags.clone())
+            .unwrap_or_default();
+
+        let mut changed = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new("Tags:").strong());
+            if tags.is_empty() {
+                ui.label("None");
+            }
+            for tag in &tags {
+                if ui
+                    .small_button(format!("{} ✕", tag))
+                    .on_hover_text("Remove this tag")
+                    .clicked()
+                {
+                    if let Err(e) = self.app_context.db.remove_identity_tag(&identity_id, tag) {
+                        self.backend_message = Some((
+                            format!("Error removing tag: {}", e),
+                            MessageType::Error,
+                            Utc::now(),
+                        ));
+                    }
+                    changed = true;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_tag_input)
+                    .hint_text("New tag")
+                    .desired_width(120.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (ui.button("Add Tag").clicked() || submitted)
+                && !self.new_tag_input.trim().is_empty()
+            {
+                if let Err(e) = self.app_context.db.add_identity_tag(
+                    &identity_id,
+                    &self.new_tag_input,
+                    &self.app_context,
+                ) {
+                    self.backend_message = Some((
+                        format!("Error adding tag: {}", e),
+                        MessageType::Error,
+                        Utc::now(),
+                    ));
+                }
+                self.new_tag_input.clear();
+                changed = true;
+            }
+        });
+
+        if changed {
+            self.reload_tags();
+        }
+    }
+
     /// Reorders `self.identities` to match the order of the provided list of IDs.
     /// Any IDs not present in the provided list are left in their current position.
     fn reorder_map_to(&self, new_order: Vec<Identifier>) {
@@ -144,10 +759,16 @@ impl IdentitiesScreen {
             };
         } else {
             self.sort_column = column;
-            self.sort_order = IdentitiesSortOrder::Ascending;
+            self.sort_order = column.default_order();
         }
     }
 
+    /// Whether the table is sorted by `column` and in which direction, for the header arrows.
+    fn sorted_ascending(&self, column: IdentitiesSortColumn) -> Option<bool> {
+        (!self.use_custom_order && self.sort_column == column)
+            .then(|| self.sort_order == IdentitiesSortOrder::Ascending)
+    }
+
     /// Sorts a list of QIs
     fn sort_vec(&self, list: &mut [QualifiedIdentity]) {
         list.sort_by(|a, b| {
@@ -225,11 +846,27 @@ impl IdentitiesScreen {
 
         let mut alias = qualified_identity.alias.clone().unwrap_or_default();
 
-        let text_edit = egui::TextEdit::singleline(&mut alias)
+        let identity_id = qualified_identity.identity.id();
+        let alias_taken = !alias.is_empty()
+            && self.identities.lock().unwrap().values().any(|other| {
+                other.identity.id() != identity_id && other.alias.as_deref() == Some(&alias)
+            });
+
+        let mut text_edit = egui::TextEdit::singleline(&mut alias)
             .hint_text(placeholder_text)
             .desired_width(100.0);
+        if alias_taken {
+            text_edit = text_edit.text_color(Color32::RED);
+        }
+
+        let response = ui.add_enabled(!self.app_context.is_read_only(), text_edit);
+        let response = if alias_taken {
+            response.on_hover_text("This alias is already used by another identity")
+        } else {
+            response
+        };
 
-        if ui.add(text_edit).changed() {
+        if response.changed() {
             // If user edits alias, we do not necessarily turn on "custom order."
             // This is a separate property. But we do update the stored alias.
             let mut identities = self.identities.lock().unwrap();
@@ -247,6 +884,8 @@ impl IdentitiesScreen {
                 identity_to_update.alias.as_ref().map(|s| s.as_str()),
             ) {
                 Ok(_) => {}
+                // Shown on the alias field itself, the alias is kept unsaved until it is unique
+                Err(SetAliasError::AliasAlreadyTaken(_)) => {}
                 Err(e) => {
                     eprintln!("{}", e);
                 }
@@ -254,7 +893,7 @@ impl IdentitiesScreen {
         }
     }
 
-    fn show_identity_id(ui: &mut Ui, qualified_identity: &QualifiedIdentity) {
+    fn show_identity_id(ui: &mut Ui, qualified_identity: &QualifiedIdentity, monospace: bool) {
         let (encoding, helper) = match qualified_identity.identity_type {
             IdentityType::User => (Encoding::Base58, "UserId".to_string()),
             IdentityType::Masternode | IdentityType::Evonode => {
@@ -262,8 +901,13 @@ impl IdentitiesScreen {
             }
         };
         let identifier_as_string = qualified_identity.identity.id().to_string(encoding);
+        let text = if monospace {
+            RichText::new(identifier_as_string).monospace()
+        } else {
+            RichText::new(identifier_as_string)
+        };
         ui.add(
-            egui::Label::new(identifier_as_string)
+            egui::Label::new(text)
                 .sense(egui::Sense::hover())
                 .truncate(),
         )
@@ -297,7 +941,13 @@ impl IdentitiesScreen {
     // Save the current index order to DB
     fn save_current_order(&self) {
         let lock = self.identities.lock().unwrap();
-        let all_ids = lock.keys().cloned().collect::<Vec<_>>();
+        let mut all_ids = lock.keys().cloned().collect::<Vec<_>>();
+        // Identities that aren't loaded yet keep their order, after the loaded ones
+        if self.identities_loaded_rows < self.identity_count {
+            if let Ok(saved_ids) = self.app_context.db.load_identity_order() {
+                all_ids.extend(saved_ids.into_iter().filter(|id| !lock.contains_key(id)));
+            }
+        }
         drop(lock);
         self.app_context.db.save_identity_order(all_ids).ok();
     }
@@ -363,11 +1013,62 @@ impl IdentitiesScreen {
             .on_hover_text(format!("{}", qualified_identity.identity.balance()));
     }
 
-    fn show_balance(ui: &mut Ui, qualified_identity: &QualifiedIdentity) {
+    fn show_favorite_toggle(&mut self, ui: &mut Ui, qualified_identity: &QualifiedIdentity) {
+        let identity_id = qualified_identity.identity.id();
+        let is_favorite = self.favorite_ids.contains(&identity_id);
+        let star = if is_favorite {
+            RichText::new("★").color(Color32::from_rgb(230, 180, 0))
+        } else {
+            RichText::new("☆")
+        };
+        let response = ui
+            .add(egui::Button::new(star).frame(false))
+            .on_hover_text(if is_favorite {
+                "Remove from favorites"
+            } else {
+                "Add to favorites, favorites are listed first"
+            });
+        if response.clicked() {
+            match self.app_context.db.set_identity_favorite(
+                &identity_id,
+                !is_favorite,
+                &self.app_context,
+            ) {
+                Ok(()) => {
+                    if is_favorite {
+                        self.favorite_ids.remove(&identity_id);
+                    } else {
+                        self.favorite_ids.insert(identity_id);
+                    }
+                }
+                Err(e) => {
+                    self.backend_message = Some((
+                        format!("Error saving favorite: {}", e),
+                        MessageType::Error,
+                        Utc::now(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn show_balance(
+        ui: &mut Ui,
+        qualified_identity: &QualifiedIdentity,
+        dash_price: Option<&DashPrice>,
+    ) {
         let balance_in_dash = qualified_identity.identity.balance() as f64 * 1e-11;
         let formatted_balance = format!("{:.4} DASH", balance_in_dash);
+        let mut hover_text = format!("{}", qualified_identity.identity.balance());
+        if let Some(dash_price) = dash_price {
+            hover_text.push_str(&format!("\n≈ {}", dash_price.format_fiat(balance_in_dash)));
+        }
         ui.add(egui::Label::new(formatted_balance).sense(egui::Sense::hover()))
-            .on_hover_text(format!("{}", qualified_identity.identity.balance()));
+            .on_hover_text(hover_text);
+    }
+
+    fn format_credits(credits: u64) -> String {
+        format!("{:.4} DASH", credits as f64 * 1e-11)
     }
 
     fn show_public_key(
@@ -474,6 +1175,8 @@ impl IdentitiesScreen {
         let mut local_identities = identities.to_vec();
         if !self.use_custom_order {
             self.sort_vec(&mut local_identities);
+            // Favorites stay on top of sorted lists, the custom order is left as arranged
+            local_identities.sort_by_key(|qi| !self.favorite_ids.contains(&qi.identity.id()));
         }
 
         // Allocate space for refreshing status
@@ -500,221 +1203,279 @@ impl IdentitiesScreen {
                 ))
                 .inner_margin(Margin::same(8.0))
                 .show(ui, |ui| {
-                    TableBuilder::new(ui)
-                        .striped(true)
-                        .resizable(true)
-                        .cell_layout(egui::Layout::left_to_right(Align::Center))
-                        .column(Column::initial(80.0).resizable(true))   // Name
-                        .column(Column::initial(330.0).resizable(true))  // Identity ID
-                        .column(Column::initial(60.0).resizable(true))   // In Wallet
-                        .column(Column::initial(80.0).resizable(true))   // Type
-                        .column(Column::initial(80.0).resizable(true))   // Keys
-                        .column(Column::initial(140.0).resizable(true))  // Balance
-                        .column(Column::initial(120.0).resizable(true))  // Actions (wider for up/down)
-                        .header(30.0, |mut header| {
-                            header.col(|ui| {
-                                if ui.button("Name").clicked() {
-                                    self.toggle_sort(IdentitiesSortColumn::Alias);
-                                }
-                            });
-                            header.col(|ui| {
-                                if ui.button("Identity ID").clicked() {
-                                    self.toggle_sort(IdentitiesSortColumn::IdentityID);
-                                }
-                            });
-                            header.col(|ui| {
-                                if ui.button("In Wallet").clicked() {
-                                    self.toggle_sort(IdentitiesSortColumn::InWallet);
-                                }
-                            });
-                            header.col(|ui| {
-                                if ui.button("Type").clicked() {
-                                    self.toggle_sort(IdentitiesSortColumn::Type);
-                                }
-                            });
-                            header.col(|ui| {
-                                ui.heading("Keys");
-                            });
-                            header.col(|ui| {
-                                if ui.button("Balance").clicked() {
-                                    self.toggle_sort(IdentitiesSortColumn::Balance);
-                                }
-                            });
-                            header.col(|ui| {
-                                ui.heading("Actions");
-                            });
-                        })
-                        .body(|mut body| {
-                            for qualified_identity in &local_identities {
-                                let identity = &qualified_identity.identity;
-                                let public_keys = identity.public_keys();
-                                let voter_identity_public_keys = qualified_identity
-                                    .associated_voter_identity
-                                    .as_ref()
-                                    .map(|(id, _)| id.public_keys());
-
-                                body.row(25.0, |mut row| {
-                                    row.col(|ui| {
-                                        self.show_alias(ui, qualified_identity);
-                                    });
-                                    row.col(|ui| {
-                                        Self::show_identity_id(ui, qualified_identity);
-                                    });
-                                    row.col(|ui| {
-                                        self.show_in_wallet(ui, qualified_identity);
-                                    });
-                                    row.col(|ui| {
-                                        ui.label(format!("{}", qualified_identity.identity_type));
-                                    });
-                                    row.col(|ui| {
-                                        ui.horizontal(|ui| {
-                                            ui.spacing_mut().item_spacing.x = 3.0;
-
-                                            let mut total_keys_shown = 0;
-                                            let max_keys_to_show = 3;
-                                            let mut more_keys_available = false;
-
-                                            let public_keys_vec: Vec<_> = public_keys.iter().collect();
-                                            for (key_id, key) in public_keys_vec.iter() {
-                                                if total_keys_shown < max_keys_to_show {
-                                                    let holding_private_key = qualified_identity
-                                                        .private_keys
-                                                        .get_cloned_private_key_data_and_wallet_info(&(
-                                                            PrivateKeyOnMainIdentity,
-                                                            **key_id,
-                                                        ));
-                                                    action |= self.show_public_key(
-                                                        ui,
-                                                        qualified_identity,
-                                                        *key,
-                                                        holding_private_key,
-                                                    );
-                                                    total_keys_shown += 1;
-                                                } else {
-                                                    more_keys_available = true;
-                                                    break;
+                    // Apply the configured table font size
+                    for text_style in [TextStyle::Body, TextStyle::Button, TextStyle::Monospace] {
+                        if let Some(font_id) = ui.style_mut().text_styles.get_mut(&text_style) {
+                            font_id.size = self.table_font_size;
+                        }
+                    }
+
+                    let mut measured_widths = self.column_widths.clone();
+                    let column_widths = self.column_widths.clone();
+
+                    ui.push_id(("identities_table", self.column_widths_generation), |ui| {
+                        TableBuilder::new(ui)
+                            .striped(true)
+                            .resizable(true)
+                            .cell_layout(egui::Layout::left_to_right(Align::Center))
+                            .column(Column::initial(column_widths[0]).resizable(true)) // Name
+                            .column(Column::initial(column_widths[1]).resizable(true)) // Identity ID
+                            .column(Column::initial(column_widths[2]).resizable(true)) // In Wallet
+                            .column(Column::initial(column_widths[3]).resizable(true)) // Type
+                            .column(Column::initial(column_widths[4]).resizable(true)) // Keys
+                            .column(Column::initial(column_widths[5]).resizable(true)) // Balance
+                            .column(Column::initial(column_widths[6]).resizable(true)) // Actions (wider for up/down)
+                            .header(30.0, |mut header| {
+                                header.col(|ui| {
+                                    measured_widths[0] = ui.max_rect().width();
+                                    let sorted = self.sorted_ascending(IdentitiesSortColumn::Alias);
+                                    if sortable_header(ui, "Name", sorted).clicked() {
+                                        self.toggle_sort(IdentitiesSortColumn::Alias);
+                                    }
+                                });
+                                header.col(|ui| {
+                                    measured_widths[1] = ui.max_rect().width();
+                                    let sorted = self.sorted_ascending(IdentitiesSortColumn::IdentityID);
+                                    if sortable_header(ui, "Identity ID", sorted).clicked() {
+                                        self.toggle_sort(IdentitiesSortColumn::IdentityID);
+                                    }
+                                });
+                                header.col(|ui| {
+                                    measured_widths[2] = ui.max_rect().width();
+                                    let sorted = self.sorted_ascending(IdentitiesSortColumn::InWallet);
+                                    if sortable_header(ui, "In Wallet", sorted).clicked() {
+                                        self.toggle_sort(IdentitiesSortColumn::InWallet);
+                                    }
+                                });
+                                header.col(|ui| {
+                                    measured_widths[3] = ui.max_rect().width();
+                                    let sorted = self.sorted_ascending(IdentitiesSortColumn::Type);
+                                    if sortable_header(ui, "Type", sorted).clicked() {
+                                        self.toggle_sort(IdentitiesSortColumn::Type);
+                                    }
+                                });
+                                header.col(|ui| {
+                                    measured_widths[4] = ui.max_rect().width();
+                                    ui.heading("Keys");
+                                });
+                                header.col(|ui| {
+                                    measured_widths[5] = ui.max_rect().width();
+                                    let sorted = self.sorted_ascending(IdentitiesSortColumn::Balance);
+                                    if sortable_header(ui, "Balance", sorted).clicked() {
+                                        self.toggle_sort(IdentitiesSortColumn::Balance);
+                                    }
+                                });
+                                header.col(|ui| {
+                                    measured_widths[6] = ui.max_rect().width();
+                                    ui.heading("Actions");
+                                });
+                            })
+                            .body(|mut body| {
+                                for qualified_identity in &local_identities {
+                                    let identity = &qualified_identity.identity;
+                                    let public_keys = identity.public_keys();
+                                    let voter_identity_public_keys = qualified_identity
+                                        .associated_voter_identity
+                                        .as_ref()
+                                        .map(|(id, _)| id.public_keys());
+
+                                    body.row(25.0, |mut row| {
+                                        row.col(|ui| {
+                                            ui.horizontal(|ui| {
+                                                self.show_favorite_toggle(ui, qualified_identity);
+                                                self.show_alias(ui, qualified_identity);
+                                            });
+                                        });
+                                        row.col(|ui| {
+                                            Self::show_identity_id(
+                                                ui,
+                                                qualified_identity,
+                                                self.monospace_ids,
+                                            );
+                                        });
+                                        row.col(|ui| {
+                                            self.show_in_wallet(ui, qualified_identity);
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(format!("{}", qualified_identity.identity_type));
+                                        });
+                                        row.col(|ui| {
+                                            ui.horizontal(|ui| {
+                                                ui.spacing_mut().item_spacing.x = 3.0;
+
+                                                let mut total_keys_shown = 0;
+                                                let max_keys_to_show = 3;
+                                                let mut more_keys_available = false;
+
+                                                let public_keys_vec: Vec<_> = public_keys.iter().collect();
+                                                for (key_id, key) in public_keys_vec.iter() {
+                                                    if total_keys_shown < max_keys_to_show {
+                                                        let holding_private_key = qualified_identity
+                                                            .private_keys
+                                                            .get_cloned_private_key_data_and_wallet_info(&(
+                                                                PrivateKeyOnMainIdentity,
+                                                                **key_id,
+                                                            ));
+                                                        action |= self.show_public_key(
+                                                            ui,
+                                                            qualified_identity,
+                                                            *key,
+                                                            holding_private_key,
+                                                        );
+                                                        total_keys_shown += 1;
+                                                    } else {
+                                                        more_keys_available = true;
+                                                        break;
+                                                    }
                                                 }
-                                            }
 
-                                            if let Some(voting_identity_public_keys) =
-                                                voter_identity_public_keys
-                                            {
-                                                if total_keys_shown < max_keys_to_show {
-                                                    let voter_vec: Vec<_> = voting_identity_public_keys.iter().collect();
-                                                    for (key_id, key) in voter_vec.iter() {
-                                                        if total_keys_shown < max_keys_to_show {
-                                                            let holding_private_key =
-                                                                qualified_identity
-                                                                    .private_keys
-                                                                    .get_cloned_private_key_data_and_wallet_info(&(
-                                                                        PrivateKeyOnVoterIdentity,
-                                                                        **key_id,
-                                                                    ));
-                                                            action |= self.show_public_key(
-                                                                ui,
-                                                                qualified_identity,
-                                                                *key,
-                                                                holding_private_key,
-                                                            );
-                                                            total_keys_shown += 1;
-                                                        } else {
-                                                            more_keys_available = true;
-                                                            break;
+                                                if let Some(voting_identity_public_keys) =
+                                                    voter_identity_public_keys
+                                                {
+                                                    if total_keys_shown < max_keys_to_show {
+                                                        let voter_vec: Vec<_> = voting_identity_public_keys.iter().collect();
+                                                        for (key_id, key) in voter_vec.iter() {
+                                                            if total_keys_shown < max_keys_to_show {
+                                                                let holding_private_key =
+                                                                    qualified_identity
+                                                                        .private_keys
+                                                                        .get_cloned_private_key_data_and_wallet_info(&(
+                                                                            PrivateKeyOnVoterIdentity,
+                                                                            **key_id,
+                                                                        ));
+                                                                action |= self.show_public_key(
+                                                                    ui,
+                                                                    qualified_identity,
+                                                                    *key,
+                                                                    holding_private_key,
+                                                                );
+                                                                total_keys_shown += 1;
+                                                            } else {
+                                                                more_keys_available = true;
+                                                                break;
+                                                            }
                                                         }
+                                                    } else {
+                                                        more_keys_available = true;
                                                     }
-                                                } else {
-                                                    more_keys_available = true;
                                                 }
-                                            }
 
-                                            if more_keys_available {
-                                                if ui.button("...").on_hover_text("Show more keys").clicked() {
-                                                    self.show_more_keys_popup =
-                                                        Some(qualified_identity.clone());
+                                                if more_keys_available {
+                                                    if ui.button("...").on_hover_text("Show more keys").clicked() {
+                                                        self.show_more_keys_popup =
+                                                            Some(qualified_identity.clone());
+                                                    }
                                                 }
-                                            }
 
-                                            if qualified_identity.can_sign_with_master_key().is_some()
-                                                && ui.button("+").on_hover_text("Add key").clicked()
-                                            {
-                                                action = AppAction::AddScreen(Screen::AddKeyScreen(
-                                                    AddKeyScreen::new(
-                                                        qualified_identity.clone(),
-                                                        &self.app_context,
-                                                    ),
-                                                ));
-                                            }
+                                                if qualified_identity.can_sign_with_master_key().is_some()
+                                                    && ui.button("+").on_hover_text("Add key").clicked()
+                                                {
+                                                    action = AppAction::AddScreen(Screen::AddKeyScreen(
+                                                        AddKeyScreen::new(
+                                                            qualified_identity.clone(),
+                                                            &self.app_context,
+                                                        ),
+                                                    ));
+                                                }
+                                            });
                                         });
-                                    });
-                                    row.col(|ui| {
-                                        Self::show_balance(ui, qualified_identity);
+                                        row.col(|ui| {
+                                            Self::show_balance(ui, qualified_identity, self.dash_price.as_ref());
 
-                                        ui.spacing_mut().item_spacing.x = 3.0;
+                                            ui.spacing_mut().item_spacing.x = 3.0;
 
-                                        if ui.button("Withdraw").on_hover_text("Withdraw credits from this identity to a Dash Core address").clicked() {
-                                            action = AppAction::AddScreen(
-                                                Screen::WithdrawalScreen(WithdrawalScreen::new(
-                                                    qualified_identity.clone(),
-                                                    &self.app_context,
-                                                )),
-                                            );
-                                        }
-                                        if ui.button("Top up").on_hover_text("Increase this identity's balance by sending it Dash from the Core chain").clicked() {
-                                            action = AppAction::AddScreen(
-                                                Screen::TopUpIdentityScreen(TopUpIdentityScreen::new(
-                                                    qualified_identity.clone(),
-                                                    &self.app_context,
-                                                )),
-                                            );
-                                        }
-                                        if ui.button("Transfer").on_hover_text("Transfer credits from this identity to another identity").clicked() {
-                                            action = AppAction::AddScreen(
-                                                Screen::TransferScreen(TransferScreen::new(
-                                                    qualified_identity.clone(),
-                                                    &self.app_context,
-                                                )),
-                                            );
-                                        }
-                                    });
-                                    row.col(|ui| {
-                                        ui.spacing_mut().item_spacing.x = 3.0;
-
-                                        ui.horizontal(|ui| {
-                                            // Remove
-                                            if ui.button("Remove").on_hover_text("Remove this identity from Dash Evo Tool (it'll still exist on Dash Platform)").clicked() {
-                                                self.identity_to_remove =
-                                                    Some(qualified_identity.clone());
+                                            if ui.button("Withdraw").on_hover_text("Withdraw credits from this identity to a Dash Core address").clicked() {
+                                                action = AppAction::AddScreen(
+                                                    Screen::WithdrawalScreen(WithdrawalScreen::new(
+                                                        qualified_identity.clone(),
+                                                        &self.app_context,
+                                                    )),
+                                                );
+                                            }
+                                            if ui.button("Top up").on_hover_text("Increase this identity's balance by sending it Dash from the Core chain").clicked() {
+                                                action = AppAction::AddScreen(
+                                                    Screen::TopUpIdentityScreen(TopUpIdentityScreen::new(
+                                                        qualified_identity.clone(),
+                                                        &self.app_context,
+                                                    )),
+                                                );
+                                            }
+                                            if ui.button("Transfer").on_hover_text("Transfer credits from this identity to another identity").clicked() {
+                                                action = AppAction::AddScreen(
+                                                    Screen::TransferScreen(TransferScreen::new(
+                                                        qualified_identity.clone(),
+                                                        &self.app_context,
+                                                    )),
+                                                );
+                                            }
+                                            if ui.button("Check").on_hover_text("Compare the stored balance with the balance on Dash Platform").clicked() {
+                                                action = AppAction::BackendTask(BackendTask::IdentityTask(
+                                                    IdentityTask::ReconcileIdentityBalances(vec![
+                                                        qualified_identity.clone(),
+                                                    ]),
+                                                ));
                                             }
                                         });
+                                        row.col(|ui| {
+                                            ui.spacing_mut().item_spacing.x = 3.0;
 
-                                        ui.horizontal(|ui| {
-                                            // Up arrow
-                                            let up_btn = ui.button("⬆").on_hover_text("Move this identity up in the list");
-                                            // Down arrow
-                                            let down_btn = ui.button("⬇").on_hover_text("Move this identity down in the list");
-
-                                            if up_btn.clicked() {
-                                                // If we are currently sorted (not custom),
-                                                // unify the IndexMap to reflect that ephemeral sort
-                                                if !self.use_custom_order {
-                                                    self.update_index_map_to_current_ephemeral(local_identities.clone());
+                                            ui.horizontal(|ui| {
+                                                if ui.button("Details").on_hover_text("Show the revision and nonce of this identity").clicked() {
+                                                    self.identity_details = Some(identity.id());
+                                                    action = AppAction::BackendTask(BackendTask::IdentityTask(
+                                                        IdentityTask::FetchIdentityNonce(qualified_identity.clone()),
+                                                    ));
                                                 }
-                                                // Now do the swap
-                                                self.use_custom_order = true;
-                                                self.move_identity_up(&identity.id());
-                                            }
-                                            if down_btn.clicked() {
-                                                if !self.use_custom_order {
-                                                    self.update_index_map_to_current_ephemeral(local_identities.clone());
+                                                // Remove
+                                                if ui.button("Remove").on_hover_text("Remove this identity from Dash Evo Tool (it'll still exist on Dash Platform)").clicked() {
+                                                    self.identity_to_remove = Some(
+                                                        IdentityRemovalConfirmation::new(qualified_identity.clone()),
+                                                    );
                                                 }
-                                                self.use_custom_order = true;
-                                                self.move_identity_down(&identity.id());
-                                            }
+                                            });
+
+                                            ui.horizontal(|ui| {
+                                                // Up arrow
+                                                let up_btn = ui.button("⬆").on_hover_text("Move this identity up in the list");
+                                                // Down arrow
+                                                let down_btn = ui.button("⬇").on_hover_text("Move this identity down in the list");
+
+                                                if up_btn.clicked() {
+                                                    // If we are currently sorted (not custom),
+                                                    // unify the IndexMap to reflect that ephemeral sort
+                                                    if !self.use_custom_order {
+                                                        self.update_index_map_to_current_ephemeral(local_identities.clone());
+                                                    }
+                                                    // Now do the swap
+                                                    self.use_custom_order = true;
+                                                    self.move_identity_up(&identity.id());
+                                                }
+                                                if down_btn.clicked() {
+                                                    if !self.use_custom_order {
+                                                        self.update_index_map_to_current_ephemeral(local_identities.clone());
+                                                    }
+                                                    self.use_custom_order = true;
+                                                    self.move_identity_down(&identity.id());
+                                                }
+                                            });
                                         });
                                     });
-                                });
-                            }
-                        });
+                                }
+                            });
+                    });
+
+                    // Persist the widths once the user has finished dragging a column edge
+                    let widths_changed = measured_widths
+                        .iter()
+                        .zip(&self.column_widths)
+                        .any(|(measured, saved)| (measured - saved).abs() > 0.5);
+                    if widths_changed && !ui.input(|i| i.pointer.any_down()) {
+                        self.column_widths = measured_widths;
+                        self.app_context
+                            .db
+                            .update_identities_column_widths(Some(&self.column_widths))
+                            .ok();
+                    }
                 });
         });
 
@@ -722,53 +1483,879 @@ impl IdentitiesScreen {
     }
 
     fn show_identity_to_remove(&mut self, ctx: &Context) {
-        if let Some(identity_to_remove) = self.identity_to_remove.clone() {
-            egui::Window::new("Confirm Removal")
-                .collapsible(false)
-                .resizable(false)
-                .show(ctx, |ui| {
+        let Some(confirmation) = self.identity_to_remove.as_mut() else {
+            return;
+        };
+        let message = format!(
+            "Are you sure you want to no longer track this {} identity? It can be restored from Recently Deleted.",
+            confirmation.identity.identity_type
+        );
+        match confirmation.show(ctx, "Confirm Removal", &message) {
+            ConfirmationResponse::Pending => {}
+            ConfirmationResponse::Cancelled => self.identity_to_remove = None,
+            ConfirmationResponse::Confirmed => {
+                let Some(confirmation) = self.identity_to_remove.take() else {
+                    return;
+                };
+                let identity_to_remove = confirmation.identity;
+                let identity_id = identity_to_remove.identity.id();
+                self.identities.lock().unwrap().shift_remove(&identity_id);
+
+                self.app_context
+                    .db
+                    .delete_local_qualified_identity(&identity_id, &self.app_context)
+                    .ok();
+
+                if let Some((voter_identity, _)) = &identity_to_remove.associated_voter_identity {
+                    let voter_identity_id = voter_identity.id();
+                    self.app_context
+                        .db
+                        .delete_local_qualified_identity(&voter_identity_id, &self.app_context)
+                        .ok();
+                }
+            }
+        }
+    }
+
+    /// Asks whether to save the first refreshed identity whose keys changed on Platform.
+    fn show_pending_identity_changes(&mut self, ctx: &Context) {
+        let Some((qualified_identity, changes)) = self.pending_identity_changes.first().cloned()
+        else {
+            return;
+        };
+        let mut save = false;
+        let mut discard = false;
+
+        egui::Window::new("Identity Changed on Platform")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "The keys of {} on Platform differ from the stored ones:",
+                    qualified_identity.display_string()
+                ));
+                ui.add_space(5.0);
+                for line in changes.summary() {
+                    ui.label(line);
+                }
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Save Changes")
+                        .on_hover_text("Replace the stored identity with the one on Platform")
+                        .clicked()
+                    {
+                        save = true;
+                    }
+                    if ui
+                        .button("Keep Stored")
+                        .on_hover_text("Leave the stored identity as it is")
+                        .clicked()
+                    {
+                        discard = true;
+                    }
+                });
+                if self.pending_identity_changes.len() > 1 {
                     ui.label(format!(
-                        "Are you sure you want to no longer track this {} identity?",
-                        identity_to_remove.identity_type
+                        "{} more identities changed",
+                        self.pending_identity_changes.len() - 1
                     ));
-                    ui.label(format!(
-                        "Identity ID: {}",
-                        identity_to_remove
-                            .identity
-                            .id()
-                            .to_string(identity_to_remove.identity_type.default_encoding())
+                }
+            });
+
+        if save {
+            let identity_id = qualified_identity.identity.id();
+            let result = self
+                .app_context
+                .insert_local_qualified_identity(&qualified_identity, None)
+                .and_then(|_| {
+                    self.app_context
+                        .db
+                        .mark_identity_synced(&identity_id, &self.app_context)
+                });
+            if let Err(e) = result {
+                self.backend_message = Some((
+                    format!("Error saving refreshed identity: {}", e),
+                    MessageType::Error,
+                    Utc::now(),
+                ));
+            }
+            self.refresh();
+        }
+        if save || discard {
+            self.pending_identity_changes.remove(0);
+        }
+    }
+
+    fn show_recently_deleted(&mut self, ctx: &Context) {
+        let Some(deleted) = self.recently_deleted.clone() else {
+            return;
+        };
+        let mut open = true;
+        let mut restored = None;
+        let mut purge = false;
+
+        egui::Window::new("Recently Deleted")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if deleted.is_empty() {
+                    ui.label("No deleted identities.");
+                    return;
+                }
+                egui::Grid::new("recently_deleted_grid")
+                    .num_columns(3)
+                    .spacing([10.0, 5.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for qualified_identity in &deleted {
+                            ui.label(qualified_identity.display_string());
+                            ui.label(qualified_identity.identity_type.to_string());
+                            if ui.button("Restore").clicked() {
+                                restored = Some(qualified_identity.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(10.0);
+                if ui
+                    .button("Delete Permanently")
+                    .on_hover_text("Removes all deleted identities and their keys for good")
+                    .clicked()
+                {
+                    purge = true;
+                }
+            });
+
+        if let Some(qualified_identity) = restored {
+            let identity_id = qualified_identity.identity.id();
+            let mut result = self
+                .app_context
+                .db
+                .restore_local_qualified_identity(&identity_id, &self.app_context);
+            if let Some((voter_identity, _)) = &qualified_identity.associated_voter_identity {
+                result = result.and(
+                    self.app_context
+                        .db
+                        .restore_local_qualified_identity(&voter_identity.id(), &self.app_context),
+                );
+            }
+            match result {
+                Ok(()) => {
+                    self.identities
+                        .lock()
+                        .unwrap()
+                        .insert(identity_id, qualified_identity);
+                    if let Some(deleted) = self.recently_deleted.as_mut() {
+                        deleted.retain(|qi| qi.identity.id() != identity_id);
+                    }
+                }
+                Err(e) => self.display_message(
+                    &format!("Failed to restore identity: {}", e),
+                    MessageType::Error,
+                ),
+            }
+        }
+
+        if purge {
+            match self
+                .app_context
+                .db
+                .purge_deleted_identities(&self.app_context)
+            {
+                Ok(()) => self.recently_deleted = Some(vec![]),
+                Err(e) => self.display_message(
+                    &format!("Failed to delete identities: {}", e),
+                    MessageType::Error,
+                ),
+            }
+        }
+
+        if !open {
+            self.recently_deleted = None;
+        }
+    }
+
+    /// Overwrites the stored balance of an identity with the balance fetched from Platform.
+    fn apply_platform_balance(&mut self, identity_id: &Identifier, platform_balance: u64) {
+        let mut lock = self.identities.lock().unwrap();
+        let Some(qualified_identity) = lock.get_mut(identity_id) else {
+            return;
+        };
+        qualified_identity.identity.set_balance(platform_balance);
+        if let Err(e) = self
+            .app_context
+            .update_local_qualified_identity(qualified_identity)
+        {
+            drop(lock);
+            self.display_message(
+                &format!("Failed to update stored balance: {}", e),
+                MessageType::Error,
+            );
+            return;
+        }
+        drop(lock);
+
+        if let Some(reconciliations) = self.balance_reconciliations.as_mut() {
+            for reconciliation in reconciliations
+                .iter_mut()
+                .filter(|r| &r.identity_id == identity_id)
+            {
+                reconciliation.stored_balance = platform_balance;
+            }
+        }
+    }
+
+    fn show_balance_reconciliations(&mut self, ctx: &Context) {
+        let Some(reconciliations) = self.balance_reconciliations.clone() else {
+            return;
+        };
+
+        let out_of_sync: Vec<(Identifier, u64)> = reconciliations
+            .iter()
+            .filter_map(|r| match r.platform_balance {
+                Ok(balance) if balance != r.stored_balance => Some((r.identity_id, balance)),
+                _ => None,
+            })
+            .collect();
+        let failed_count = reconciliations
+            .iter()
+            .filter(|r| r.platform_balance.is_err())
+            .count();
+
+        let mut to_update: Vec<(Identifier, u64)> = Vec::new();
+        let mut close = false;
+
+        egui::Window::new("Balance Check")
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Checked {} identities: {} in sync, {} out of sync, {} could not be fetched.",
+                    reconciliations.len(),
+                    reconciliations.iter().filter(|r| r.is_in_sync()).count(),
+                    out_of_sync.len(),
+                    failed_count
+                ));
+                ui.add_space(5.0);
+
+                egui::Grid::new("balance_reconciliation_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Identity").strong());
+                        ui.label(RichText::new("Stored").strong());
+                        ui.label(RichText::new("Platform").strong());
+                        ui.label("");
+                        ui.end_row();
+
+                        for reconciliation in &reconciliations {
+                            ui.label(&reconciliation.display_name);
+                            ui.label(Self::format_credits(reconciliation.stored_balance))
+                                .on_hover_text(reconciliation.stored_balance.to_string());
+                            match &reconciliation.platform_balance {
+                                Ok(balance) => {
+                                    ui.label(Self::format_credits(*balance))
+                                        .on_hover_text(balance.to_string());
+                                    if *balance == reconciliation.stored_balance {
+                                        ui.colored_label(Color32::DARK_GREEN, "In sync");
+                                    } else if ui.button("Update stored balance").clicked() {
+                                        to_update.push((reconciliation.identity_id, *balance));
+                                    }
+                                }
+                                Err(e) => {
+                                    ui.colored_label(Color32::DARK_RED, "Error")
+                                        .on_hover_text(e);
+                                    ui.label("");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if !out_of_sync.is_empty() && ui.button("Update all").clicked() {
+                        to_update.extend(out_of_sync.iter().copied());
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        for (identity_id, platform_balance) in to_update {
+            self.apply_platform_balance(&identity_id, platform_balance);
+        }
+        if close {
+            self.balance_reconciliations = None;
+        }
+    }
+
+    /// Finds the keys, on main and voter identities, that match the search query.
+    fn find_keys(
+        &self,
+        kind: KeySearchKind,
+        query: &str,
+    ) -> Vec<(QualifiedIdentity, PrivateKeyTarget, IdentityPublicKey)> {
+        let query = query.trim();
+        let query = query.strip_prefix("0x").unwrap_or(query).to_lowercase();
+        if query.is_empty() {
+            return vec![];
+        }
+
+        let mut matches = vec![];
+        for qualified_identity in self.identities.lock().unwrap().values() {
+            for key in qualified_identity.identity.public_keys().values() {
+                if kind.matches(key, &query) {
+                    matches.push((
+                        qualified_identity.clone(),
+                        PrivateKeyOnMainIdentity,
+                        key.clone(),
                     ));
-                    ui.horizontal(|ui| {
-                        if ui.button("Yes").clicked() {
-                            let identity_id = identity_to_remove.identity.id();
-                            let mut lock = self.identities.lock().unwrap();
-                            lock.shift_remove(&identity_id);
-
-                            self.app_context
-                                .db
-                                .delete_local_qualified_identity(&identity_id, &self.app_context)
-                                .ok();
-
-                            if let Some((voter_identity, _)) =
-                                &identity_to_remove.associated_voter_identity
-                            {
-                                let voter_identity_id = voter_identity.id();
-                                self.app_context
-                                    .db
-                                    .delete_local_qualified_identity(
-                                        &voter_identity_id,
-                                        &self.app_context,
-                                    )
-                                    .ok();
+                }
+            }
+            if let Some((voter_identity, _)) = &qualified_identity.associated_voter_identity {
+                for key in voter_identity.public_keys().values() {
+                    if kind.matches(key, &query) {
+                        matches.push((
+                            qualified_identity.clone(),
+                            PrivateKeyOnVoterIdentity,
+                            key.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        matches
+    }
+
+    fn show_key_search(&mut self, ctx: &Context) -> AppAction {
+        let mut action = AppAction::None;
+        let Some(mut search) = self.key_search.take() else {
+            return action;
+        };
+        let mut open = true;
+
+        egui::Window::new("Find Key")
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_salt("key_search_kind")
+                        .selected_text(search.kind.label())
+                        .show_ui(ui, |ui| {
+                            for kind in [
+                                KeySearchKind::KeyId,
+                                KeySearchKind::PublicKeyHex,
+                                KeySearchKind::PublicKeyHash,
+                            ] {
+                                ui.selectable_value(&mut search.kind, kind, kind.label());
                             }
+                        });
+                    search_field(ui, &mut search.query, "Key ID, public key or hash");
+                });
+                ui.add_space(5.0);
 
-                            self.identity_to_remove = None;
+                let matches = self.find_keys(search.kind, &search.query);
+                if search.query.trim().is_empty() {
+                    ui.label("Enter a key ID, public key or public key hash to search for.");
+                } else if matches.is_empty() {
+                    ui.label("No loaded identity has a matching key.");
+                } else {
+                    egui::Grid::new("key_search_results")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (qualified_identity, target, key) in &matches {
+                                ui.label(qualified_identity.display_short_string());
+                                ui.label(match target {
+                                    PrivateKeyOnMainIdentity => "Main identity",
+                                    PrivateKeyOnVoterIdentity => "Voter identity",
+                                    PrivateKeyTarget::PrivateKeyOnOperatorIdentity => {
+                                        "Operator identity"
+                                    }
+                                });
+                                let private_key = qualified_identity
+                                    .private_keys
+                                    .get_cloned_private_key_data_and_wallet_info(&(
+                                        target.clone(),
+                                        key.id(),
+                                    ));
+                                let key_action =
+                                    self.show_public_key(ui, qualified_identity, key, private_key);
+                                // Main identity keys are opened from the database, as stored,
+                                // rather than from the copy loaded in this list
+                                action |= match (target, key_action) {
+                                    (PrivateKeyOnMainIdentity, AppAction::AddScreen(_)) => {
+                                        AppAction::OpenKeyInfo {
+                                            identity_id: qualified_identity.identity.id(),
+                                            key_id: key.id(),
+                                        }
+                                    }
+                                    (_, key_action) => key_action,
+                                };
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.add_space(5.0);
+                if ui.button("Close").clicked() {
+                    open = false;
+                }
+            });
+
+        if open {
+            self.key_search = Some(search);
+        }
+        action
+    }
+
+    fn show_identity_details(&mut self, ctx: &Context) -> AppAction {
+        let mut action = AppAction::None;
+        let Some(identity_id) = self.identity_details else {
+            return action;
+        };
+        let Some(qualified_identity) = self.identities.lock().unwrap().get(&identity_id).cloned()
+        else {
+            self.identity_details = None;
+            return action;
+        };
+
+        egui::Window::new("Identity Details")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("identity_details_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Identity ID:").strong());
+                        ui.label(
+                            identity_id
+                                .to_string(qualified_identity.identity_type.default_encoding()),
+                        );
+                        ui.end_row();
+
+                        ui.label(RichText::new("Balance:").strong());
+                        Self::show_balance(ui, &qualified_identity, self.dash_price.as_ref());
+                        ui.end_row();
+
+                        if let Some(dash_price) = &self.dash_price {
+                            ui.label(RichText::new("Value:").strong());
+                            ui.label(format!(
+                                "≈ {}",
+                                dash_price.format_fiat(
+                                    qualified_identity.identity.balance() as f64 * 1e-11
+                                )
+                            ));
+                            ui.end_row();
                         }
-                        if ui.button("No").clicked() {
-                            self.identity_to_remove = None;
+
+                        ui.label(RichText::new("Revision:").strong());
+                        ui.label(qualified_identity.identity.revision().to_string());
+                        ui.end_row();
+
+                        ui.label(RichText::new("Last synced:").strong());
+                        let last_synced = self.last_synced.get(&identity_id).copied();
+                        let text = format_last_synced(last_synced, Utc::now().timestamp());
+                        let stale = last_synced.map_or(true, |synced_at| {
+                            Utc::now().timestamp() - synced_at > STALE_REFRESH_SECONDS
+                        });
+                        if stale {
+                            ui.colored_label(Color32::from_rgb(200, 120, 0), text)
+                                .on_hover_text("The balance and keys shown may be outdated, use Refresh to sync them");
+                        } else {
+                            ui.label(text);
                         }
+                        ui.end_row();
+
+                        ui.label(RichText::new("Identity nonce:").strong());
+                        match self.identity_nonces.get(&identity_id) {
+                            Some(nonce) => ui.label(nonce.to_string()),
+                            None => ui.label("Fetching..."),
+                        };
+                        ui.end_row();
                     });
+
+                self.show_alias_history(ui, &qualified_identity);
+
+                ui.add_space(5.0);
+                self.show_identity_tags(ui, identity_id);
+
+                if !qualified_identity.top_ups.is_empty() {
+                    ui.add_space(5.0);
+                    ui.label(RichText::new("Top Ups").strong());
+                    // Top up indexes are handed out in order, so they are chronological
+                    egui::Grid::new("identity_top_ups_grid")
+                        .num_columns(3)
+                        .spacing([10.0, 5.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (index, top_up) in &qualified_identity.top_ups {
+                                ui.label(format!("#{}", index));
+                                ui.label(format!("{:.8} DASH", top_up.amount as f64 / 1e8));
+                                ui.label(
+                                    top_up
+                                        .created_at
+                                        .and_then(|time| DateTime::from_timestamp(time, 0))
+                                        .map(|time| {
+                                            time.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+                                        })
+                                        .unwrap_or_else(|| "Unknown date".to_string()),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Refresh")
+                        .on_hover_text("Fetch the latest identity and nonce from Platform")
+                        .clicked()
+                    {
+                        action = AppAction::BackendTasks(
+                            vec![
+                                BackendTask::IdentityTask(IdentityTask::RefreshIdentity(
+                                    qualified_identity.clone(),
+                                )),
+                                BackendTask::IdentityTask(IdentityTask::FetchIdentityNonce(
+                                    qualified_identity.clone(),
+                                )),
+                            ],
+                            BackendTasksExecutionMode::Concurrent,
+                        );
+                    }
+                    if ui
+                        .button("Export Backup")
+                        .on_hover_text(
+                            "Save the identity and its private keys to an encrypted file",
+                        )
+                        .clicked()
+                    {
+                        self.identity_backup = Some(IdentityBackupDialog::Export {
+                            identity_id,
+                            passphrase: String::new(),
+                        });
+                    }
+                    if ui.button("Close").clicked() {
+                        self.identity_details = None;
+                    }
                 });
+            });
+
+        action
+    }
+
+    fn pick_identity_backup_files(&mut self) {
+        let Some(paths) = rfd::FileDialog::new()
+            .add_filter("Identity backup", &[IDENTITY_BACKUP_EXTENSION])
+            .pick_files()
+        else {
+            return;
+        };
+        match paths
+            .iter()
+            .map(|path| {
+                std::fs::read(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| IdentityBackup::from_bytes(&bytes))
+                    .map_err(|e| format!("{}: {}", path.display(), e))
+            })
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(backups) if backups.is_empty() => {}
+            Ok(backups) => {
+                self.identity_backup = Some(IdentityBackupDialog::Import {
+                    backups,
+                    passphrase: String::new(),
+                });
+            }
+            Err(e) => {
+                self.backend_message = Some((
+                    format!("Error reading identity backup: {}", e),
+                    MessageType::Error,
+                    Utc::now(),
+                ));
+            }
+        }
+    }
+
+    /// Returns `None` when the user cancels the save dialog.
+    fn export_identity_backup(
+        &self,
+        identity_id: &Identifier,
+        passphrase: &str,
+    ) -> Result<Option<String>, String> {
+        let qualified_identity = self
+            .identities
+            .lock()
+            .unwrap()
+            .get(identity_id)
+            .cloned()
+            .ok_or_else(|| "Identity not found".to_string())?;
+        let backup = IdentityBackup::seal(
+            &qualified_identity,
+            &self.app_context.network_string(),
+            passphrase,
+        )?;
+        let file_name = format!(
+            "{}.{}",
+            qualified_identity
+                .alias
+                .clone()
+                .unwrap_or_else(|| identity_id.to_string(Encoding::Base58)),
+            IDENTITY_BACKUP_EXTENSION
+        );
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Identity backup", &[IDENTITY_BACKUP_EXTENSION])
+            .set_file_name(file_name)
+            .save_file()
+        else {
+            return Ok(None);
+        };
+        std::fs::write(&path, backup.to_bytes()?).map_err(|e| e.to_string())?;
+        Ok(Some(format!("Identity backup saved to {}", path.display())))
+    }
+
+    /// Saves the public info of all local identities as JSON. Returns `None` when the user
+    /// cancels the save dialog.
+    fn export_public_info(&self) -> Result<Option<String>, String> {
+        let identities: Vec<QualifiedIdentity> =
+            self.identities.lock().unwrap().values().cloned().collect();
+        let json = identities_public_info_json(&identities)?;
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name(format!(
+                "identities-{}.json",
+                self.app_context.network_string()
+            ))
+            .save_file()
+        else {
+            return Ok(None);
+        };
+        std::fs::write(&path, json).map_err(|e| e.to_string())?;
+        Ok(Some(format!(
+            "Public info of {} identities saved to {}",
+            identities.len(),
+            path.display()
+        )))
+    }
+
+    /// Sets the aliases listed in a CSV or JSON file picked by the user, see
+    /// [`parse_alias_import_file`]. Every entry gets a result line in `alias_import_results`.
+    fn import_aliases_from_file(&mut self) -> Result<(), String> {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Alias files", &["csv", "json"])
+            .pick_file()
+        else {
+            return Ok(());
+        };
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let parsed = parse_alias_import_file(&contents)?;
+
+        let entries: Vec<_> = parsed
+            .iter()
+            .filter_map(|(_, entry)| entry.as_ref().ok().cloned())
+            .collect();
+        let mut outcomes = self
+            .app_context
+            .db
+            .import_aliases(&entries, &self.app_context)
+            .map_err(|e| format!("Importing aliases failed: {}", e))?
+            .into_iter();
+
+        let results = parsed
+            .into_iter()
+            .map(|(label, entry)| {
+                let result = entry.and_then(|(_, alias)| {
+                    match outcomes.next().expect("expected an outcome per entry") {
+                        AliasImportOutcome::Set => Ok(match alias {
+                            Some(alias) => format!("Alias set to \"{}\"", alias),
+                            None => "Alias cleared".to_string(),
+                        }),
+                        AliasImportOutcome::Unchanged => Ok("Already has this alias".to_string()),
+                        AliasImportOutcome::UnknownIdentity => {
+                            Err("No identity with this id, skipped".to_string())
+                        }
+                        AliasImportOutcome::AliasTaken(alias) => {
+                            Err(format!("\"{}\" is already used by another identity", alias))
+                        }
+                    }
+                });
+                (label, result)
+            })
+            .collect();
+        self.alias_import_results = Some(results);
+        self.refresh();
+        Ok(())
+    }
+
+    fn show_alias_import_results(&mut self, ctx: &Context) {
+        let Some(results) = &self.alias_import_results else {
+            return;
+        };
+        let mut open = true;
+        let imported = results.iter().filter(|(_, result)| result.is_ok()).count();
+
+        egui::Window::new("Alias Import")
+            .collapsible(false)
+            .resizable(true)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} of {} entries imported",
+                    imported,
+                    results.len()
+                ));
+                ui.add_space(5.0);
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("alias_import_results_grid")
+                            .num_columns(2)
+                            .spacing([10.0, 5.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (label, result) in results {
+                                    ui.label(label);
+                                    match result {
+                                        Ok(message) => ui.label(message),
+                                        Err(e) => ui.colored_label(Color32::DARK_RED, e),
+                                    };
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.alias_import_results = None;
+        }
+    }
+
+    /// Imports backups sharing the same passphrase. They are written in one transaction, so
+    /// either all of them are imported or none.
+    fn import_identity_backups(
+        &mut self,
+        backups: &[IdentityBackup],
+        passphrase: &str,
+    ) -> Result<String, String> {
+        let identities = backups
+            .iter()
+            .map(|backup| Ok((backup.open(passphrase)?, None)))
+            .collect::<Result<Vec<_>, String>>()?;
+        self.app_context
+            .insert_local_qualified_identities(&identities)
+            .map_err(|e| e.to_string())?;
+        let message = match identities.as_slice() {
+            [(qualified_identity, _)] => {
+                format!("Imported identity {}", qualified_identity.display_string())
+            }
+            _ => format!("Imported {} identities", identities.len()),
+        };
+        let mut loaded_identities = self.identities.lock().unwrap();
+        for (qualified_identity, _) in identities {
+            loaded_identities.insert(qualified_identity.identity.id(), qualified_identity);
+        }
+        Ok(message)
+    }
+
+    fn show_identity_backup_dialog(&mut self, ctx: &Context) {
+        let Some(dialog) = self.identity_backup.as_mut() else {
+            return;
+        };
+        let network = self.app_context.network_string();
+        let mut open = true;
+        let mut confirmed = false;
+
+        let title = match dialog {
+            IdentityBackupDialog::Export { .. } => "Export Identity Backup",
+            IdentityBackupDialog::Import { .. } => "Import Identity Backup",
+        };
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let passphrase = match dialog {
+                    IdentityBackupDialog::Export { passphrase, .. } => {
+                        ui.label(
+                            "The identity and its private keys are encrypted with this passphrase. It is needed to import the backup.",
+                        );
+                        passphrase
+                    }
+                    IdentityBackupDialog::Import { backups, passphrase } => {
+                        for backup in backups.iter() {
+                            let created_at = DateTime::from_timestamp(backup.created_at, 0)
+                                .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                                .unwrap_or_default();
+                            ui.label(format!(
+                                "Backup of a {} identity created {}",
+                                backup.network, created_at
+                            ));
+                            if backup.network != network {
+                                ui.colored_label(
+                                    Color32::DARK_RED,
+                                    format!(
+                                        "This backup was made on {}, but the app is on {}. The identity will not be found on this network.",
+                                        backup.network, network
+                                    ),
+                                );
+                            }
+                        }
+                        if backups.len() > 1 {
+                            ui.label("All backups must use the same passphrase.");
+                        }
+                        passphrase
+                    }
+                };
+                ui.horizontal(|ui| {
+                    ui.label("Passphrase:");
+                    ui.add(egui::TextEdit::singleline(passphrase).password(true));
+                });
+                ui.add_space(5.0);
+                let button = match dialog {
+                    IdentityBackupDialog::Export { .. } => "Save…",
+                    IdentityBackupDialog::Import { .. } => "Import",
+                };
+                confirmed = ui.button(button).clicked();
+            });
+
+        if confirmed {
+            let result = match self.identity_backup.take() {
+                Some(IdentityBackupDialog::Export {
+                    identity_id,
+                    passphrase,
+                }) => self.export_identity_backup(&identity_id, &passphrase),
+                Some(IdentityBackupDialog::Import {
+                    backups,
+                    passphrase,
+                }) => self
+                    .import_identity_backups(&backups, &passphrase)
+                    .map(Some),
+                None => return,
+            };
+            match result {
+                Ok(None) => {}
+                Ok(Some(message)) => {
+                    self.backend_message = Some((message, MessageType::Success, Utc::now()));
+                }
+                Err(e) => {
+                    self.backend_message = Some((
+                        format!("Identity backup failed: {}", e),
+                        MessageType::Error,
+                        Utc::now(),
+                    ));
+                }
+            }
+        } else if !open {
+            self.identity_backup = None;
         }
     }
 
@@ -822,6 +2409,16 @@ impl IdentitiesScreen {
         action
     }
 
+    /// Builds one `RefreshIdentity` task per loaded identity.
+    fn refresh_identities_tasks(&self) -> Vec<BackendTask> {
+        self.identities
+            .lock()
+            .unwrap()
+            .values()
+            .map(|qi| BackendTask::IdentityTask(IdentityTask::RefreshIdentity(qi.clone())))
+            .collect()
+    }
+
     fn dismiss_message(&mut self) {
         self.backend_message = None;
     }
@@ -841,15 +2438,29 @@ impl IdentitiesScreen {
 
 impl ScreenLike for IdentitiesScreen {
     fn refresh(&mut self) {
-        let mut identities = self.identities.lock().unwrap();
-        *identities = self
+        // Reload as many identities as were listed
+        let (loaded_identities, skipped) = self
             .app_context
-            .load_local_qualified_identities()
-            .unwrap_or_default()
+            .load_local_qualified_identities_page(self.identities_loaded_rows, 0)
+            .unwrap_or_default();
+        self.identity_count = self
+            .app_context
+            .db
+            .count_local_qualified_identities(&self.app_context)
+            .unwrap_or_default();
+        let mut identities = self.identities.lock().unwrap();
+        *identities = loaded_identities
             .into_iter()
             .map(|qi| (qi.identity.id(), qi))
             .collect();
         drop(identities);
+        if skipped > 0 {
+            self.backend_message = Some((
+                skipped_identities_message(skipped),
+                MessageType::Error,
+                Utc::now(),
+            ));
+        }
 
         // Keep order after refreshing
         if let Ok(saved_ids) = self.app_context.db.load_identity_order() {
@@ -857,24 +2468,95 @@ impl ScreenLike for IdentitiesScreen {
             self.use_custom_order = true;
         }
 
+        self.dash_price = self.app_context.db.get_dash_price().unwrap_or_default();
+        self.favorite_ids = self
+            .app_context
+            .db
+            .get_favorite_identity_ids(&self.app_context)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        self.in_creation_ids = self
+            .app_context
+            .db
+            .get_in_creation_identity_ids(&self.app_context)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        self.in_creation_wallets = Self::load_in_creation_wallets(&self.app_context);
+        self.last_synced = self
+            .app_context
+            .db
+            .get_identity_last_synced_times(&self.app_context)
+            .unwrap_or_default();
+
+        if let Ok((table_font_size, monospace_ids)) =
+            self.app_context.db.get_table_display_settings()
+        {
+            self.table_font_size = table_font_size;
+            self.monospace_ids = monospace_ids;
+        }
+
+        // Column widths were reset from the settings screen
+        if let Ok(None) = self.app_context.db.get_identities_column_widths() {
+            if self.column_widths != DEFAULT_COLUMN_WIDTHS {
+                self.column_widths = DEFAULT_COLUMN_WIDTHS.to_vec();
+                self.column_widths_generation += 1;
+            }
+        }
+
+        self.reload_tags();
+        self.reload_alias_search();
+
         self.show_more_keys_popup = None;
     }
 
+    fn refresh_on_arrival(&mut self) {
+        self.refresh();
+        self.pending_auto_refresh = self
+            .app_context
+            .db
+            .get_identities_auto_refresh_on_open()
+            .unwrap_or(false);
+    }
+
     fn display_message(&mut self, message: &str, message_type: crate::ui::MessageType) {
         if message.contains("Error refreshing identities")
             || message.contains("Successfully refreshed identity")
         {
             self.refreshing_status = IdentitiesRefreshingStatus::NotRefreshing;
         }
+        if message_type == MessageType::Success
+            && message.contains("Successfully refreshed identity")
+        {
+            self.last_refreshed = Some(Utc::now());
+        }
         self.backend_message = Some((message.to_string(), message_type, Utc::now()));
     }
 
     fn display_task_result(
         &mut self,
-        _backend_task_success_result: crate::ui::BackendTaskSuccessResult,
+        backend_task_success_result: crate::ui::BackendTaskSuccessResult,
     ) {
-        // Nothing
-        // If we don't include this, success messages from ZMQ listener will keep popping up
+        // Other results are ignored, otherwise success messages from ZMQ listener will keep
+        // popping up
+        match backend_task_success_result {
+            BackendTaskSuccessResult::IdentityBalanceReconciliation(reconciliations) => {
+                self.balance_reconciliations = Some(reconciliations);
+            }
+            BackendTaskSuccessResult::IdentityNonce(identity_id, nonce) => {
+                self.identity_nonces.insert(identity_id, nonce);
+            }
+            BackendTaskSuccessResult::IdentityChangesToConfirm(qualified_identity, changes) => {
+                let identity_id = qualified_identity.identity.id();
+                self.pending_identity_changes
+                    .retain(|(pending, _)| pending.identity.id() != identity_id);
+                self.pending_identity_changes
+                    .push((qualified_identity, changes));
+                self.refreshing_status = IdentitiesRefreshingStatus::NotRefreshing;
+            }
+            _ => {}
+        }
     }
 
     fn ui(&mut self, ctx: &Context) -> AppAction {
@@ -903,13 +2585,7 @@ impl ScreenLike for IdentitiesScreen {
         ));
         if self.identities.lock().unwrap().len() > 0 {
             // Create a vec of RefreshIdentity(identity) DesiredAppAction for each identity
-            let backend_tasks: Vec<BackendTask> = self
-                .identities
-                .lock()
-                .unwrap()
-                .values()
-                .map(|qi| BackendTask::IdentityTask(IdentityTask::RefreshIdentity(qi.clone())))
-                .collect();
+            let backend_tasks = self.refresh_identities_tasks();
             right_buttons.push((
                 "Refresh",
                 DesiredAppAction::BackendTasks(
@@ -917,8 +2593,43 @@ impl ScreenLike for IdentitiesScreen {
                     BackendTasksExecutionMode::Concurrent,
                 ),
             ));
+            if self.app_context.has_wallet.load(Ordering::Relaxed) {
+                right_buttons.push((
+                    "Bulk Top Up",
+                    DesiredAppAction::AddScreenType(ScreenType::BulkTopUp),
+                ));
+            }
+            let all_identities = self.identities.lock().unwrap().values().cloned().collect();
+            right_buttons.push(("Find Key", DesiredAppAction::Custom("Find Key".to_string())));
+            right_buttons.push((
+                "Export Public Info",
+                DesiredAppAction::Custom("Export Public Info".to_string()),
+            ));
+            right_buttons.push((
+                "Check Balances",
+                DesiredAppAction::BackendTask(BackendTask::IdentityTask(
+                    IdentityTask::ReconcileIdentityBalances(all_identities),
+                )),
+            ));
         }
 
+        right_buttons.push((
+            "Overview",
+            DesiredAppAction::AddScreenType(ScreenType::IdentitiesOverview),
+        ));
+        right_buttons.push((
+            "Recently Deleted",
+            DesiredAppAction::Custom("Recently Deleted".to_string()),
+        ));
+        right_buttons.push((
+            "Import Backup",
+            DesiredAppAction::Custom("Import Backup".to_string()),
+        ));
+        right_buttons.push((
+            "Import Aliases",
+            DesiredAppAction::Custom("Import Aliases".to_string()),
+        ));
+
         let mut action = add_top_panel(
             ctx,
             &self.app_context,
@@ -926,8 +2637,69 @@ impl ScreenLike for IdentitiesScreen {
             right_buttons,
         );
 
+        if action == AppAction::Custom("Recently Deleted".to_string()) {
+            self.recently_deleted = Some(
+                self.app_context
+                    .db
+                    .get_deleted_local_identities(&self.app_context)
+                    .unwrap_or_default(),
+            );
+            action = AppAction::None;
+        }
+
+        if action == AppAction::Custom("Import Backup".to_string()) {
+            self.pick_identity_backup_files();
+            action = AppAction::None;
+        }
+
+        if action == AppAction::Custom("Import Aliases".to_string()) {
+            if let Err(e) = self.import_aliases_from_file() {
+                self.backend_message = Some((e, MessageType::Error, Utc::now()));
+            }
+            action = AppAction::None;
+        }
+
+        if action == AppAction::Custom("Export Public Info".to_string()) {
+            match self.export_public_info() {
+                Ok(None) => {}
+                Ok(Some(message)) => {
+                    self.backend_message = Some((message, MessageType::Success, Utc::now()));
+                }
+                Err(e) => {
+                    self.backend_message = Some((
+                        format!("Exporting public info failed: {}", e),
+                        MessageType::Error,
+                        Utc::now(),
+                    ));
+                }
+            }
+            action = AppAction::None;
+        }
+
+        if action == AppAction::Custom("Find Key".to_string()) {
+            self.key_search = Some(KeySearch {
+                kind: KeySearchKind::PublicKeyHash,
+                query: String::new(),
+            });
+            action = AppAction::None;
+        }
+
         action |= add_left_panel(ctx, &self.app_context, RootScreenType::RootScreenIdentities);
 
+        // Refresh from Platform on arrival if enabled, unless a refresh is already running
+        if self.pending_auto_refresh {
+            self.pending_auto_refresh = false;
+            if self.refreshing_status == IdentitiesRefreshingStatus::NotRefreshing {
+                let backend_tasks = self.refresh_identities_tasks();
+                if !backend_tasks.is_empty() {
+                    action |= AppAction::BackendTasks(
+                        backend_tasks,
+                        BackendTasksExecutionMode::Concurrent,
+                    );
+                }
+            }
+        }
+
         let identities_vec = {
             let guard = self.identities.lock().unwrap();
             guard.values().cloned().collect::<Vec<_>>()
@@ -937,7 +2709,21 @@ impl ScreenLike for IdentitiesScreen {
             if identities_vec.is_empty() {
                 self.render_no_identities_view(ui);
             } else {
+                self.render_last_refreshed(ui);
+                action |= self.render_identities_in_creation(ui, &identities_vec);
+                self.render_alias_search(ui);
+                self.render_tag_filter(ui);
+                let searching_alias = !self.alias_search.trim().is_empty();
+                let identities_vec = identities_vec
+                    .into_iter()
+                    .filter(|qi| {
+                        let id = qi.identity.id();
+                        (self.tag_filter.is_none() || self.tag_filter_ids.contains(&id))
+                            && (!searching_alias || self.alias_search_ids.contains(&id))
+                    })
+                    .collect();
                 action |= self.render_identities_view(ui, &identities_vec);
+                self.render_load_more_identities(ui);
             }
 
             // If we are refreshing, show a spinner at the bottom
@@ -955,11 +2741,7 @@ impl ScreenLike for IdentitiesScreen {
 
             let message = self.backend_message.clone();
             if let Some((message, message_type, timestamp)) = message {
-                let message_color = match message_type {
-                    MessageType::Error => egui::Color32::DARK_RED,
-                    MessageType::Info => egui::Color32::BLACK,
-                    MessageType::Success => egui::Color32::DARK_GREEN,
-                };
+                let message_color = message_type.color(ui.visuals());
 
                 ui.add_space(7.0);
                 ui.allocate_ui(egui::Vec2::new(ui.available_width(), 30.0), |ui| {
@@ -994,6 +2776,34 @@ impl ScreenLike for IdentitiesScreen {
             self.show_identity_to_remove(ctx);
         }
 
+        if self.balance_reconciliations.is_some() {
+            self.show_balance_reconciliations(ctx);
+        }
+
+        if self.identity_details.is_some() {
+            action |= self.show_identity_details(ctx);
+        }
+
+        if self.key_search.is_some() {
+            action |= self.show_key_search(ctx);
+        }
+
+        if self.recently_deleted.is_some() {
+            self.show_recently_deleted(ctx);
+        }
+
+        if self.identity_backup.is_some() {
+            self.show_identity_backup_dialog(ctx);
+        }
+
+        if !self.pending_identity_changes.is_empty() {
+            self.show_pending_identity_changes(ctx);
+        }
+
+        if self.alias_import_results.is_some() {
+            self.show_alias_import_results(ctx);
+        }
+
         match action {
             AppAction::BackendTask(BackendTask::IdentityTask(IdentityTask::RefreshIdentity(_))) => {
                 self.refreshing_status =
@@ -1010,3 +2820,17 @@ impl ScreenLike for IdentitiesScreen {
         action
     }
 }
+
+/// How long ago an identity was fetched from Platform, or "never".
+fn format_last_synced(last_synced: Option<i64>, now: i64) -> String {
+    let Some(synced_at) = last_synced else {
+        return "never".to_string();
+    };
+    let seconds = (now - synced_at).max(0);
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{} minutes ago", seconds / 60),
+        3600..=86399 => format!("{} hours ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
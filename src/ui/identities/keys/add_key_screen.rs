@@ -213,6 +213,10 @@ impl ScreenLike for AddKeyScreen {
         }
     }
 
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
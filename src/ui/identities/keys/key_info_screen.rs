@@ -7,25 +7,299 @@ use crate::model::qualified_identity::QualifiedIdentity;
 use crate::model::wallet::Wallet;
 use crate::ui::components::top_panel::add_top_panel;
 use crate::ui::components::wallet_unlock::ScreenWithWalletUnlock;
+use crate::ui::identities::funding_common::copy_to_clipboard;
 use crate::ui::ScreenLike;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use dash_sdk::dashcore_rpc::dashcore::PrivateKey as RPCPrivateKey;
 use dash_sdk::dpp::dashcore::address::Payload;
 use dash_sdk::dpp::dashcore::hashes::Hash;
-use dash_sdk::dpp::dashcore::secp256k1::{Message, Secp256k1, SecretKey};
+use dash_sdk::dpp::dashcore::secp256k1::ecdsa::{RecoverableSignature, RecoveryId, Signature};
+use dash_sdk::dpp::dashcore::secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
 use dash_sdk::dpp::dashcore::sign_message::signed_msg_hash;
-use dash_sdk::dpp::dashcore::{Address, PrivateKey, PubkeyHash, ScriptHash};
+use dash_sdk::dpp::dashcore::{Address, Network, PrivateKey, PubkeyHash, ScriptHash};
+use dash_sdk::dpp::identity::accessors::IdentityGettersV0;
 use dash_sdk::dpp::identity::hash::IdentityPublicKeyHashMethodsV0;
 use dash_sdk::dpp::identity::identity_public_key::accessors::v0::IdentityPublicKeyGettersV0;
-use dash_sdk::dpp::identity::KeyType;
 use dash_sdk::dpp::identity::KeyType::BIP13_SCRIPT_HASH;
+use dash_sdk::dpp::identity::{KeyID, KeyType};
 use dash_sdk::dpp::platform_value::string_encoding::Encoding;
 use dash_sdk::platform::IdentityPublicKey;
 use eframe::egui::{self, Context};
 use egui::{Color32, RichText, ScrollArea, TextEdit};
+use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 
+#[derive(Debug, PartialEq, thiserror::Error)]
+enum PrivateKeyInputError {
+    #[error("{0}")]
+    InvalidEncoding(String),
+    #[error(
+        "Expected {} hex characters ({expected_bytes} bytes) for a {key_type:?} private key, got {actual_hex_chars}.{hint}",
+        .expected_bytes * 2
+    )]
+    WrongLength {
+        key_type: KeyType,
+        expected_bytes: usize,
+        actual_hex_chars: usize,
+        hint: &'static str,
+    },
+    #[error("{0}")]
+    WrongNetwork(String),
+    #[error("{0:?} keys don't have a private key that can be stored.")]
+    Unsupported(KeyType),
+    #[error("Issue verifying {0:?} private key: {1}")]
+    Validation(KeyType, String),
+    #[error("{0:?} private key does not match the public key.")]
+    Mismatch(KeyType),
+}
+
+/// Size of the private keys of the supported key types. BLS private keys are 32 bytes as well,
+/// 48 bytes is the size of a BLS public key.
+const PRIVATE_KEY_LENGTH: usize = 32;
+
+fn wrong_private_key_length(key_type: KeyType, actual_hex_chars: usize) -> PrivateKeyInputError {
+    PrivateKeyInputError::WrongLength {
+        key_type,
+        expected_bytes: PRIVATE_KEY_LENGTH,
+        actual_hex_chars,
+        hint: match (key_type, actual_hex_chars) {
+            (KeyType::BLS12_381, 96) => " This looks like a BLS public key.",
+            (KeyType::EDDSA_25519_HASH160, 128) => {
+                " This looks like an Ed25519 key pair, enter only its first 32 bytes."
+            }
+            _ => "",
+        },
+    }
+}
+
+/// Decodes a private key entered for `key` and checks that it belongs to it. ECDSA keys may be
+/// given as hex or WIF, BLS and EdDSA keys as hex only. All supported private keys are 32 bytes.
+fn parse_private_key_for(
+    key: &IdentityPublicKey,
+    input: &str,
+    network: Network,
+) -> Result<[u8; 32], PrivateKeyInputError> {
+    let key_type = key.key_type();
+    // Check the length of hex input before decoding it, an odd number of characters would
+    // otherwise only be reported as invalid hex, and a short key as not matching
+    if key_type != KeyType::BIP13_SCRIPT_HASH
+        && !input.is_empty()
+        && input.chars().all(|c| c.is_ascii_hexdigit())
+        && input.len() != PRIVATE_KEY_LENGTH * 2
+    {
+        return Err(wrong_private_key_length(key_type, input.len()));
+    }
+    let hex_bytes = hex::decode(input);
+
+    let private_key_bytes: Vec<u8> = match key_type {
+        KeyType::ECDSA_SECP256K1 | KeyType::ECDSA_HASH160 => match hex_bytes {
+            Ok(bytes) => bytes,
+            Err(_) => match PrivateKey::from_wif(input) {
+                // All test networks share the same WIF prefix, so only mainnet can be told apart
+                Ok(key) if (key.network == Network::Dash) != (network == Network::Dash) => {
+                    return Err(PrivateKeyInputError::WrongNetwork(format!(
+                        "This WIF key is for {}, but the app is connected to {}.",
+                        if key.network == Network::Dash {
+                            "mainnet"
+                        } else {
+                            "a test network"
+                        },
+                        network
+                    )));
+                }
+                Ok(key) => key.inner.secret_bytes().to_vec(),
+                Err(_) => {
+                    return Err(PrivateKeyInputError::InvalidEncoding(
+                        "Invalid hex string or WIF for private key.".to_string(),
+                    ));
+                }
+            },
+        },
+        KeyType::BLS12_381 | KeyType::EDDSA_25519_HASH160 => hex_bytes.map_err(|_| {
+            PrivateKeyInputError::InvalidEncoding(format!(
+                "{:?} private keys must be entered as hex.",
+                key_type
+            ))
+        })?,
+        KeyType::BIP13_SCRIPT_HASH => return Err(PrivateKeyInputError::Unsupported(key_type)),
+    };
+
+    let private_key_bytes: [u8; PRIVATE_KEY_LENGTH] = private_key_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| wrong_private_key_length(key_type, private_key_bytes.len() * 2))?;
+
+    match key.validate_private_key_bytes(&private_key_bytes, network) {
+        Ok(true) => Ok(private_key_bytes),
+        Ok(false) => Err(PrivateKeyInputError::Mismatch(key_type)),
+        Err(e) => Err(PrivateKeyInputError::Validation(key_type, e.to_string())),
+    }
+}
+
+/// Reads the entries of a key import file. JSON files hold an object mapping key ids to private
+/// keys, text files one `<key id> <private key>` pair per line, separated by whitespace, `:`,
+/// `=` or `,`. Empty lines and lines starting with `#` are ignored. Each entry is parsed on its
+/// own so a bad line doesn't prevent importing the others.
+fn parse_key_import_file(
+    contents: &str,
+) -> Result<Vec<(String, Result<(KeyID, String), String>)>, String> {
+    let parse_key_id = |key_id: &str| {
+        key_id
+            .trim()
+            .parse::<KeyID>()
+            .map_err(|_| format!("\"{}\" is not a key id", key_id.trim()))
+    };
+
+    if contents.trim_start().starts_with('{') {
+        let entries: BTreeMap<String, String> =
+            serde_json::from_str(contents).map_err(|e| format!("Invalid JSON key file: {}", e))?;
+        return Ok(entries
+            .into_iter()
+            .map(|(key_id, private_key)| {
+                let entry = parse_key_id(&key_id).map(|id| (id, private_key.trim().to_string()));
+                (format!("Key {}", key_id.trim()), entry)
+            })
+            .collect());
+    }
+
+    Ok(contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            let label = format!("Line {}", index + 1);
+            let entry = match line
+                .trim()
+                .split_once(|c: char| c.is_whitespace() || c == ':' || c == '=' || c == ',')
+            {
+                Some((key_id, private_key)) if !private_key.trim().is_empty() => {
+                    parse_key_id(key_id).map(|id| (id, private_key.trim().to_string()))
+                }
+                _ => Err("Expected a key id followed by a private key".to_string()),
+            };
+            (label, entry)
+        })
+        .collect())
+}
+
+/// Signs `message` as a Dash signed message. The signature is 65 bytes: a header holding the
+/// recovery id of a compressed public key, followed by the compact signature.
+fn sign_message_with_key(
+    key_type: KeyType,
+    private_key_bytes: &[u8; 32],
+    message: &str,
+) -> Result<Vec<u8>, String> {
+    match key_type {
+        KeyType::ECDSA_SECP256K1 | KeyType::ECDSA_HASH160 => {
+            let secret_key = SecretKey::from_byte_array(private_key_bytes)
+                .map_err(|e| format!("Invalid private key: {}", e))?;
+            let message_hash = signed_msg_hash(message);
+            let message = Message::from_digest(*message_hash.as_byte_array());
+            let (recovery_id, compact) = Secp256k1::signing_only()
+                .sign_ecdsa_recoverable(&message, &secret_key)
+                .serialize_compact();
+
+            let mut signature = Vec::with_capacity(65);
+            signature.push(31 + i32::from(recovery_id) as u8);
+            signature.extend_from_slice(&compact);
+            Ok(signature)
+        }
+        key_type => Err(format!(
+            "Signing messages with {:?} keys is not supported.",
+            key_type
+        )),
+    }
+}
+
+/// Checks `signature`, in hex or base64, against `message` and the public key of `key`. Accepts
+/// 65 byte signatures as produced by Sign Message as well as bare 64 byte compact ones.
+/// `ECDSA_HASH160` keys only hold the hash of their public key, so the public key is recovered
+/// from the signature and its hash compared. Every recovery id is tried rather than trusting the
+/// header, which older versions of Sign Message wrote as a fixed value.
+fn verify_message_signature(
+    key: &IdentityPublicKey,
+    message: &str,
+    signature: &str,
+) -> Result<bool, String> {
+    if !matches!(
+        key.key_type(),
+        KeyType::ECDSA_SECP256K1 | KeyType::ECDSA_HASH160
+    ) {
+        return Err(format!(
+            "Verifying messages with {:?} keys is not supported.",
+            key.key_type()
+        ));
+    }
+
+    let input = signature.trim();
+    let signature_bytes = hex::decode(input)
+        .or_else(|_| STANDARD.decode(input))
+        .map_err(|_| "The signature is neither valid hex nor base64.".to_string())?;
+    let compact = match signature_bytes.len() {
+        65 => &signature_bytes[1..],
+        64 => &signature_bytes[..],
+        len => {
+            return Err(format!(
+                "Expected a 64 or 65 byte signature, got {} bytes.",
+                len
+            ))
+        }
+    };
+    let message_hash = signed_msg_hash(message);
+    let message = Message::from_digest(*message_hash.as_byte_array());
+
+    if key.key_type() == KeyType::ECDSA_HASH160 {
+        let secp = Secp256k1::verification_only();
+        let mut checked_any = false;
+        for recovery_id in (0..4).filter_map(|id| RecoveryId::try_from(id).ok()) {
+            let Ok(signature) = RecoverableSignature::from_compact(compact, recovery_id) else {
+                continue;
+            };
+            checked_any = true;
+            if let Ok(public_key) = secp.recover_ecdsa(&message, &signature) {
+                if PubkeyHash::hash(&public_key.serialize()).as_byte_array()
+                    == key.data().as_slice()
+                {
+                    return Ok(true);
+                }
+            }
+        }
+        if !checked_any {
+            return Err("Invalid signature.".to_string());
+        }
+        return Ok(false);
+    }
+
+    let public_key = PublicKey::from_slice(key.data().as_slice())
+        .map_err(|e| format!("Invalid public key: {}", e))?;
+    let mut signature =
+        Signature::from_compact(compact).map_err(|e| format!("Invalid signature: {}", e))?;
+    signature.normalize_s();
+    Ok(Secp256k1::verification_only()
+        .verify_ecdsa(&message, &signature, &public_key)
+        .is_ok())
+}
+
+/// What confirming a key import does to the stored private key of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyImportChange {
+    Add,
+    /// The same private key is already stored
+    Unchanged,
+    /// A different private key is stored, it is only replaced once the user ticks `confirmed`
+    Replace {
+        confirmed: bool,
+    },
+}
+
+/// A key from an import file that passed validation, stored once the import is confirmed.
+struct KeyImportCandidate {
+    key: IdentityPublicKey,
+    private_key_bytes: [u8; 32],
+    change: KeyImportChange,
+}
+
 pub struct KeyInfoScreen {
     pub identity: QualifiedIdentity,
     pub key: IdentityPublicKey,
@@ -38,12 +312,53 @@ pub struct KeyInfoScreen {
     wallet_password: String,
     show_password: bool,
     message_input: String,
-    signed_message: Option<String>,
+    signed_message: Option<Vec<u8>>,
     sign_error_message: Option<String>,
+    verify_message_input: String,
+    verify_signature_input: String,
+    verify_result: Option<bool>,
+    verify_error_message: Option<String>,
     view_wallet_unlock: bool,
     wallet_open: bool,
     view_private_key_even_if_encrypted_or_in_wallet: bool,
     show_pop_up_info: Option<String>,
+    reveal_private_key: bool,
+    private_key_copy_error: Option<String>,
+    copy_error: Option<String>,
+    pending_private_key: Option<([u8; 32], &'static str)>,
+    /// Entries of an import file, shown for review before anything is stored
+    key_import_preview: Option<Vec<(String, Result<KeyImportCandidate, String>)>>,
+    key_import_results: Option<Vec<(String, Result<KeyID, String>)>>,
+    stored_keys_message: Option<String>,
+}
+
+/// Shows a private key masked as dots, with a toggle to reveal it and a button that copies it
+/// without revealing it.
+fn render_masked_private_key(
+    ui: &mut egui::Ui,
+    private_key: &str,
+    reveal: &mut bool,
+    copy_error: &mut Option<String>,
+) {
+    ui.horizontal(|ui| {
+        ui.add(
+            TextEdit::singleline(&mut private_key.to_owned())
+                .password(!*reveal)
+                .desired_width(ui.available_width() - 120.0),
+        );
+        let toggle_text = if *reveal { "Hide" } else { "👁 Show" };
+        if ui.button(toggle_text).clicked() {
+            *reveal = !*reveal;
+        }
+        if ui.button("Copy").clicked() {
+            *copy_error = copy_to_clipboard(private_key)
+                .err()
+                .map(|e| format!("Failed to copy private key: {}", e));
+        }
+    });
+    if let Some(error) = copy_error {
+        ui.colored_label(Color32::RED, error.as_str());
+    }
 }
 
 // /// The prefix for signed messages using Dash's message signing protocol.
@@ -59,7 +374,23 @@ pub struct KeyInfoScreen {
 // }
 
 impl ScreenLike for KeyInfoScreen {
-    fn refresh(&mut self) {}
+    fn refresh(&mut self) {
+        // The identity may have been loaded before the keys were unlocked
+        if let Some(encryption_key) = self.app_context.private_key_encryption_key() {
+            self.identity.private_keys.decrypt_keys(&encryption_key);
+            self.private_key_data = self
+                .identity
+                .private_keys
+                .get_cloned_private_key_data_and_wallet_info(&(
+                    self.key.purpose().into(),
+                    self.key.id(),
+                ));
+        }
+    }
+
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
 
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let action = add_top_panel(
@@ -74,6 +405,22 @@ impl ScreenLike for KeyInfoScreen {
 
         egui::CentralPanel::default().show(ctx, |ui| {
             ScrollArea::vertical().show(ui, |ui| {
+                // The identity this key belongs to
+                let identity_id = self
+                    .identity
+                    .identity
+                    .id()
+                    .to_string(self.identity.identity_type.default_encoding());
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Identity:").strong());
+                    ui.label(self.identity.alias.as_deref().unwrap_or("(no alias)"));
+                    ui.label(RichText::new(&identity_id).monospace());
+                    if ui.small_button("Copy").clicked() {
+                        self.copy_error = copy_to_clipboard(&identity_id).err();
+                    }
+                });
+                ui.add_space(10.0);
+
                 ui.heading("Key Information");
                 ui.add_space(10.0);
 
@@ -167,16 +514,22 @@ impl ScreenLike for KeyInfoScreen {
                         match self.key.public_key_hash() {
                             Ok(hash) => {
                                 let hash_hex = hex::encode(hash);
-                                ui.label(hash_hex);
+                                ui.horizontal(|ui| {
+                                    ui.label(&hash_hex);
+                                    if ui.small_button("Copy").clicked() {
+                                        self.copy_error = copy_to_clipboard(&hash_hex).err();
+                                    }
+                                });
                             }
                             Err(e) => {
                                 ui.colored_label(egui::Color32::RED, format!("Error: {}", e));
                             }
                         }
+                        ui.end_row();
 
+                        // Address, only core address key types map to one
+                        ui.label(RichText::new("Address:").strong());
                         if self.key.key_type().is_core_address_key_type() {
-                            // Public Key Hash
-                            ui.label(RichText::new("Address:").strong());
                             match self.key.public_key_hash() {
                                 Ok(hash) => {
                                     let address = if self.key.key_type() == BIP13_SCRIPT_HASH {
@@ -189,41 +542,92 @@ impl ScreenLike for KeyInfoScreen {
                                             self.app_context.network,
                                             Payload::PubkeyHash(PubkeyHash::from_byte_array(hash)),
                                         )
-                                    };
-                                    ui.label(address.to_string());
+                                    }
+                                    .to_string();
+                                    ui.horizontal(|ui| {
+                                        ui.label(&address);
+                                        if ui.small_button("Copy").clicked() {
+                                            self.copy_error = copy_to_clipboard(&address).err();
+                                        }
+                                    });
                                 }
                                 Err(e) => {
                                     ui.colored_label(egui::Color32::RED, format!("Error: {}", e));
                                 }
                             }
+                        } else {
+                            ui.label("N/A");
                         }
-
                         ui.end_row();
                     });
 
+                if let Some(error) = &self.copy_error {
+                    ui.colored_label(Color32::RED, format!("Failed to copy: {}", error));
+                }
+
                 ui.add_space(10.0);
                 ui.separator();
                 ui.add_space(10.0);
 
                 // Display the private key if available
+                let mut unlock_requested = false;
+                let mut remove_requested = false;
                 if let Some((private_key, _)) = self.private_key_data.as_mut() {
                     ui.heading("Private Key");
                     ui.add_space(10.0);
 
                     match private_key {
                         PrivateKeyData::Clear(clear) | PrivateKeyData::AlwaysClear(clear) => {
+                            // The stored key may no longer match if the public key was replaced
+                            match self
+                                .key
+                                .validate_private_key_bytes(clear, self.app_context.network)
+                            {
+                                Ok(true) => {
+                                    ui.colored_label(Color32::DARK_GREEN, "Verified ✓");
+                                }
+                                Ok(false) => {
+                                    ui.horizontal(|ui| {
+                                        ui.colored_label(
+                                            Color32::RED,
+                                            "Mismatch ✗ the stored private key does not match this public key",
+                                        );
+                                        if ui.button("Remove stored key").clicked() {
+                                            remove_requested = true;
+                                        }
+                                    });
+                                }
+                                Err(e) => {
+                                    ui.colored_label(
+                                        Color32::RED,
+                                        format!("Could not verify the stored key: {}", e),
+                                    );
+                                }
+                            }
+                            ui.add_space(5.0);
+
                             let private_key_hex = hex::encode(clear);
-                            ui.add(
-                                TextEdit::singleline(&mut private_key_hex.as_str().to_owned())
-                                    .desired_width(f32::INFINITY),
+                            render_masked_private_key(
+                                ui,
+                                &private_key_hex,
+                                &mut self.reveal_private_key,
+                                &mut self.private_key_copy_error,
                             );
                             self.render_sign_input(ui);
                         }
                         PrivateKeyData::Encrypted(_) => {
-                            ui.label("Key is encrypted");
-                            ui.add_space(10.0);
-
-                            //todo decrypt key
+                            if self.app_context.private_key_encryption_key().is_some() {
+                                ui.colored_label(
+                                    Color32::RED,
+                                    "Key could not be decrypted with your main password",
+                                );
+                            } else {
+                                ui.label("Key is encrypted with your main password");
+                                ui.add_space(10.0);
+                                if ui.button("Unlock").clicked() {
+                                    unlock_requested = true;
+                                }
+                            }
                         }
                         PrivateKeyData::AtWalletDerivationPath(derivation_path) => {
                             if self.wallet_open
@@ -231,12 +635,11 @@ impl ScreenLike for KeyInfoScreen {
                                 && self.selected_wallet.is_some()
                             {
                                 if let Some(private_key) = self.decrypted_private_key {
-                                    let private_key_wif = private_key.to_wif();
-                                    ui.add(
-                                        TextEdit::multiline(
-                                            &mut private_key_wif.as_str().to_owned(),
-                                        )
-                                        .desired_width(f32::INFINITY),
+                                    render_masked_private_key(
+                                        ui,
+                                        &private_key.to_wif(),
+                                        &mut self.reveal_private_key,
+                                        &mut self.private_key_copy_error,
                                     );
                                 } else {
                                     let wallet =
@@ -245,12 +648,11 @@ impl ScreenLike for KeyInfoScreen {
                                         &derivation_path.derivation_path,
                                     ) {
                                         Ok(private_key) => {
-                                            let private_key_wif = private_key.to_wif();
-                                            ui.add(
-                                                TextEdit::multiline(
-                                                    &mut private_key_wif.as_str().to_owned(),
-                                                )
-                                                .desired_width(f32::INFINITY),
+                                            render_masked_private_key(
+                                                ui,
+                                                &private_key.to_wif(),
+                                                &mut self.reveal_private_key,
+                                                &mut self.private_key_copy_error,
                                             );
                                             self.decrypted_private_key = Some(private_key);
                                         }
@@ -276,12 +678,11 @@ impl ScreenLike for KeyInfoScreen {
                                         &derivation_path.derivation_path,
                                     ) {
                                         Ok(private_key) => {
-                                            let private_key_wif = private_key.to_wif();
-                                            ui.add(
-                                                TextEdit::multiline(
-                                                    &mut private_key_wif.as_str().to_owned(),
-                                                )
-                                                .desired_width(f32::INFINITY),
+                                            render_masked_private_key(
+                                                ui,
+                                                &private_key.to_wif(),
+                                                &mut self.reveal_private_key,
+                                                &mut self.private_key_copy_error,
                                             );
                                             self.decrypted_private_key = Some(private_key);
                                         }
@@ -311,9 +712,21 @@ impl ScreenLike for KeyInfoScreen {
                     ui.label("Enter Private Key:");
                     ui.text_edit_singleline(&mut self.private_key_input);
 
-                    if ui.button("Add Private Key").clicked() {
-                        self.validate_and_store_private_key();
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Add Private Key").clicked() {
+                            self.validate_and_store_private_key();
+                        }
+                        if ui
+                            .button("Import Keys from File")
+                            .on_hover_text(
+                                "Import the private keys of several keys of this identity from a \
+                                 JSON or text file mapping key ids to private keys",
+                            )
+                            .clicked()
+                        {
+                            self.import_private_keys_from_file();
+                        }
+                    });
 
                     // Display error message if validation fails
                     if let Some(error_message) = &self.error_message {
@@ -321,6 +734,22 @@ impl ScreenLike for KeyInfoScreen {
                     }
                 }
 
+                if let Some(message) = &self.stored_keys_message {
+                    ui.colored_label(Color32::DARK_GREEN, message);
+                }
+                self.render_key_import_preview(ui);
+                self.render_key_import_results(ui);
+
+                if unlock_requested {
+                    self.app_context.request_private_key_unlock();
+                }
+
+                if remove_requested {
+                    self.remove_stored_private_key();
+                }
+
+                self.render_verify_input(ui);
+
                 if self.view_wallet_unlock {
                     let (needed_unlock, just_unlocked) = self.render_wallet_unlock_if_needed(ui);
                     if !needed_unlock || just_unlocked {
@@ -328,6 +757,25 @@ impl ScreenLike for KeyInfoScreen {
                     }
                 }
 
+                if let Some((private_key_bytes, question)) = self.pending_private_key {
+                    egui::Window::new("Store Private Key?")
+                        .collapsible(false)
+                        .resizable(false)
+                        .show(ctx, |ui| {
+                            ui.label(question);
+                            ui.add_space(10.0);
+                            ui.horizontal(|ui| {
+                                if ui.button("Store Anyway").clicked() {
+                                    self.pending_private_key = None;
+                                    self.store_private_key(private_key_bytes);
+                                }
+                                if ui.button("Cancel").clicked() {
+                                    self.pending_private_key = None;
+                                }
+                            });
+                        });
+                }
+
                 // Show the popup window if `show_popup` is true
                 if let Some(show_pop_up_info_text) = self.show_pop_up_info.clone() {
                     egui::Window::new("Sign Message Info")
@@ -380,58 +828,346 @@ impl KeyInfoScreen {
             message_input: "".to_string(),
             signed_message: None,
             sign_error_message: None,
+            verify_message_input: String::new(),
+            verify_signature_input: String::new(),
+            verify_result: None,
+            verify_error_message: None,
             view_wallet_unlock: false,
             wallet_open: false,
             view_private_key_even_if_encrypted_or_in_wallet: false,
             show_pop_up_info: None,
+            reveal_private_key: false,
+            private_key_copy_error: None,
+            copy_error: None,
+            pending_private_key: None,
+            key_import_preview: None,
+            key_import_results: None,
+            stored_keys_message: None,
         }
     }
 
     fn validate_and_store_private_key(&mut self) {
-        // Convert the input string to bytes (hex decoding)
-        let private_key_bytes = match hex::decode(&self.private_key_input) {
-            Ok(private_key_bytes_vec) if private_key_bytes_vec.len() == 32 => {
-                private_key_bytes_vec.try_into().unwrap()
+        match parse_private_key_for(
+            &self.key,
+            self.private_key_input.trim(),
+            self.app_context.network,
+        ) {
+            Ok(private_key_bytes) => {
+                // Replacing a different stored key loses it, and keys that can't be used to
+                // sign are most likely added by mistake, ask first
+                let question = if self.conflicts_with_stored_key(&self.key, &private_key_bytes) {
+                    Some("A different private key is already stored for this key, replace it?")
+                } else if self.key.read_only() {
+                    Some("This key is read-only, store its private key anyway?")
+                } else if self.key.is_disabled() {
+                    Some("This key is disabled, store its private key anyway?")
+                } else {
+                    None
+                };
+                match question {
+                    Some(question) => {
+                        self.pending_private_key = Some((private_key_bytes, question))
+                    }
+                    None => self.store_private_key(private_key_bytes),
+                }
             }
-            Ok(_) => {
-                self.error_message = Some("Private key not 32 bytes".to_string());
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    fn import_private_keys_from_file(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Key files", &["json", "txt"])
+            .pick_file()
+        else {
+            return;
+        };
+        let entries = match std::fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))
+            .and_then(|contents| parse_key_import_file(&contents))
+        {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.error_message = Some(e);
                 return;
             }
-            Err(_) => match PrivateKey::from_wif(&self.private_key_input) {
-                Ok(key) => key.inner.secret_bytes(),
-                Err(_) => {
-                    self.error_message =
-                        Some("Invalid hex string or WIF for private key.".to_string());
-                    return;
+        };
+
+        let preview = entries
+            .into_iter()
+            .map(|(label, entry)| {
+                let candidate = entry.and_then(|(key_id, private_key_input)| {
+                    let key = self
+                        .identity
+                        .identity
+                        .public_keys()
+                        .get(&key_id)
+                        .cloned()
+                        .ok_or_else(|| format!("The identity has no key with id {}", key_id))?;
+                    let private_key_bytes =
+                        parse_private_key_for(&key, &private_key_input, self.app_context.network)
+                            .map_err(|e| e.to_string())?;
+                    let change = if self.conflicts_with_stored_key(&key, &private_key_bytes) {
+                        KeyImportChange::Replace { confirmed: false }
+                    } else if self
+                        .identity
+                        .private_keys
+                        .get_private_key_data(&(key.purpose().into(), key_id))
+                        .is_some()
+                    {
+                        KeyImportChange::Unchanged
+                    } else {
+                        KeyImportChange::Add
+                    };
+                    Ok(KeyImportCandidate {
+                        key,
+                        private_key_bytes,
+                        change,
+                    })
+                });
+                (label, candidate)
+            })
+            .collect();
+        self.key_import_results = None;
+        self.stored_keys_message = None;
+        self.key_import_preview = Some(preview);
+    }
+
+    /// Stores the keys of the reviewed import file. Keys with a different private key already
+    /// stored are only replaced when the user ticked them.
+    fn confirm_key_import(&mut self) {
+        let Some(preview) = self.key_import_preview.take() else {
+            return;
+        };
+        let mut identity = self.identity.clone();
+        let mut results = Vec::with_capacity(preview.len());
+        for (label, candidate) in preview {
+            let result = candidate.and_then(|candidate| {
+                let key_id = candidate.key.id();
+                if matches!(
+                    candidate.change,
+                    KeyImportChange::Replace { confirmed: false }
+                ) {
+                    return Err(format!(
+                        "Kept the private key already stored for key {}",
+                        key_id
+                    ));
                 }
-            },
+                identity.private_keys.insert_non_encrypted(
+                    (candidate.key.purpose().into(), key_id),
+                    (candidate.key.into(), candidate.private_key_bytes),
+                );
+                Ok(key_id)
+            });
+            results.push((label, result));
+        }
+
+        let any_imported = results.iter().any(|(_, result)| result.is_ok());
+        if any_imported && !self.save_imported_keys(identity) {
+            for (_, result) in results.iter_mut() {
+                if let Ok(key_id) = result {
+                    *result = Err(format!("Key {} could not be saved", key_id));
+                }
+            }
+        }
+        self.key_import_results = Some(results);
+    }
+
+    fn render_key_import_preview(&mut self, ui: &mut egui::Ui) {
+        let Some(preview) = self.key_import_preview.as_mut() else {
+            return;
         };
 
-        let validation_result = self
-            .key
-            .validate_private_key_bytes(&private_key_bytes, self.app_context.network);
-        if let Err(err) = validation_result {
-            self.error_message = Some(format!("Issue verifying private key {}", err));
-        } else if validation_result.unwrap() {
-            // If valid, store the private key in the context and reset the input field
-            self.private_key_data = Some((PrivateKeyData::Clear(private_key_bytes), None));
-            self.identity.private_keys.insert_non_encrypted(
-                (self.key.purpose().into(), self.key.id()),
-                (self.key.clone().into(), private_key_bytes),
-            );
-            match self
-                .app_context
-                .insert_local_qualified_identity(&self.identity, None)
-            {
-                Ok(_) => {
-                    self.error_message = None;
+        ui.add_space(10.0);
+        ui.label(RichText::new("Review the keys to import").strong());
+        egui::Grid::new("key_import_preview")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(RichText::new("Entry").strong());
+                ui.label(RichText::new("Key").strong());
+                ui.label(RichText::new("Result").strong());
+                ui.end_row();
+
+                for (label, candidate) in preview.iter_mut() {
+                    ui.label(label.as_str());
+                    match candidate {
+                        Ok(candidate) => {
+                            ui.label(format!(
+                                "{} ({:?})",
+                                candidate.key.id(),
+                                candidate.key.purpose()
+                            ));
+                            match &mut candidate.change {
+                                KeyImportChange::Add => {
+                                    ui.colored_label(Color32::DARK_GREEN, "Will be stored");
+                                }
+                                KeyImportChange::Unchanged => {
+                                    ui.label("Already stored");
+                                }
+                                KeyImportChange::Replace { confirmed } => {
+                                    ui.checkbox(
+                                        confirmed,
+                                        RichText::new(
+                                            "Replace the different private key already stored",
+                                        )
+                                        .color(Color32::from_rgb(200, 120, 0)),
+                                    );
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            ui.label("");
+                            ui.colored_label(Color32::RED, e.as_str());
+                        }
+                    }
+                    ui.end_row();
                 }
-                Err(e) => {
-                    self.error_message = Some(format!("Issue saving: {}", e));
+            });
+
+        let valid = preview.iter().filter(|(_, c)| c.is_ok()).count();
+        let invalid = preview.len() - valid;
+        ui.label(format!(
+            "{} of {} entries are valid, {} failed validation.",
+            valid,
+            preview.len(),
+            invalid
+        ));
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(valid > 0, egui::Button::new("Confirm import"))
+                .clicked()
+            {
+                self.confirm_key_import();
+            }
+            if ui.button("Cancel").clicked() {
+                self.key_import_preview = None;
+            }
+        });
+    }
+
+    /// Saves `identity` holding the imported keys, and only then shows them as stored. Returns
+    /// whether it was saved.
+    fn save_imported_keys(&mut self, identity: QualifiedIdentity) -> bool {
+        match self
+            .app_context
+            .insert_local_qualified_identity(&identity, None)
+        {
+            Ok(_) => {
+                self.identity = identity;
+                self.private_key_data = self
+                    .identity
+                    .private_keys
+                    .get_cloned_private_key_data_and_wallet_info(&(
+                        self.key.purpose().into(),
+                        self.key.id(),
+                    ));
+                self.error_message = None;
+                self.stored_keys_message = Some(self.stored_key_count_message());
+                true
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Issue saving: {}", e));
+                false
+            }
+        }
+    }
+
+    /// Whether a private key other than `private_key_bytes` is already stored for `key`. Keys
+    /// that are encrypted or in a wallet can't be compared, so they count as different.
+    fn conflicts_with_stored_key(
+        &self,
+        key: &IdentityPublicKey,
+        private_key_bytes: &[u8; 32],
+    ) -> bool {
+        match self
+            .identity
+            .private_keys
+            .get_private_key_data(&(key.purpose().into(), key.id()))
+        {
+            None => false,
+            Some(PrivateKeyData::Clear(stored)) | Some(PrivateKeyData::AlwaysClear(stored)) => {
+                stored != private_key_bytes
+            }
+            Some(PrivateKeyData::Encrypted(_))
+            | Some(PrivateKeyData::AtWalletDerivationPath(_)) => true,
+        }
+    }
+
+    fn stored_key_count_message(&self) -> String {
+        let count = self.identity.private_keys.private_keys.len();
+        format!(
+            "{} private key{} now stored for this identity.",
+            count,
+            if count == 1 { " is" } else { "s are" }
+        )
+    }
+
+    fn render_key_import_results(&mut self, ui: &mut egui::Ui) {
+        let Some(results) = &self.key_import_results else {
+            return;
+        };
+
+        ui.add_space(10.0);
+        let imported = results.iter().filter(|(_, result)| result.is_ok()).count();
+        ui.label(format!(
+            "Imported {} of {} keys from file:",
+            imported,
+            results.len()
+        ));
+        egui::Grid::new("key_import_results")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (label, result) in results {
+                    ui.label(label);
+                    match result {
+                        Ok(key_id) => ui
+                            .colored_label(Color32::DARK_GREEN, format!("Imported key {}", key_id)),
+                        Err(e) => ui.colored_label(Color32::RED, e),
+                    };
+                    ui.end_row();
                 }
+            });
+        if ui.button("Dismiss").clicked() {
+            self.key_import_results = None;
+            self.stored_keys_message = None;
+        }
+    }
+
+    fn remove_stored_private_key(&mut self) {
+        self.identity
+            .private_keys
+            .remove(&(self.key.purpose().into(), self.key.id()));
+        self.private_key_data = None;
+        if let Err(e) = self
+            .app_context
+            .insert_local_qualified_identity(&self.identity, None)
+        {
+            self.error_message = Some(format!("Issue saving: {}", e));
+        }
+    }
+
+    fn store_private_key(&mut self, private_key_bytes: [u8; 32]) {
+        // The key only shows as stored once the identity holding it was saved
+        let mut identity = self.identity.clone();
+        identity.private_keys.insert_non_encrypted(
+            (self.key.purpose().into(), self.key.id()),
+            (self.key.clone().into(), private_key_bytes),
+        );
+        match self
+            .app_context
+            .insert_local_qualified_identity(&identity, None)
+        {
+            Ok(_) => {
+                self.identity = identity;
+                self.private_key_data = Some((PrivateKeyData::Clear(private_key_bytes), None));
+                self.error_message = None;
+                self.stored_keys_message = Some(self.stored_key_count_message());
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Issue saving: {}", e));
             }
-        } else {
-            self.error_message = Some("Private key does not match the public key.".to_string());
         }
     }
 
@@ -478,14 +1214,97 @@ impl KeyInfoScreen {
             ui.separator();
             ui.add_space(10.0);
 
-            ui.label("Signed Message (Base64):");
+            ui.label("Signature:");
             ui.add_space(5.0);
-            ui.add(
-                egui::TextEdit::multiline(&mut signed_message.as_str().to_owned())
+            egui::Grid::new("signature_grid")
+                .num_columns(3)
+                .spacing([10.0, 5.0])
+                .show(ui, |ui| {
+                    for (encoding, signature) in [
+                        ("Base64", STANDARD.encode(signed_message)),
+                        ("Hex", hex::encode(signed_message)),
+                    ] {
+                        ui.label(encoding);
+                        ui.add(
+                            TextEdit::singleline(&mut signature.as_str())
+                                .desired_width(ui.available_width() - 60.0),
+                        );
+                        if ui.button("Copy").clicked() {
+                            self.sign_error_message = copy_to_clipboard(&signature)
+                                .err()
+                                .map(|e| format!("Failed to copy signature: {}", e));
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+    }
+
+    fn render_verify_input(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(10.0);
+        ui.separator();
+        ui.add_space(10.0);
+
+        ui.heading("Verify");
+        ui.add_space(5.0);
+
+        ui.label("Message:");
+        let message_changed = ui
+            .add(
+                egui::TextEdit::multiline(&mut self.verify_message_input)
                     .desired_width(f32::INFINITY)
                     .desired_rows(3),
-            );
+            )
+            .changed();
+        ui.label("Signature (base64 or hex):");
+        let signature_changed = ui
+            .add(
+                egui::TextEdit::singleline(&mut self.verify_signature_input)
+                    .desired_width(f32::INFINITY),
+            )
+            .changed();
+        if message_changed || signature_changed {
+            self.verify_result = None;
+            self.verify_error_message = None;
         }
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            if ui.button("Verify Signature").clicked() {
+                match self.verify_signature() {
+                    Ok(valid) => {
+                        self.verify_result = Some(valid);
+                        self.verify_error_message = None;
+                    }
+                    Err(e) => {
+                        self.verify_result = None;
+                        self.verify_error_message = Some(e);
+                    }
+                }
+            }
+            match self.verify_result {
+                Some(true) => {
+                    ui.colored_label(Color32::DARK_GREEN, "✔ Valid signature for this key");
+                }
+                Some(false) => {
+                    ui.colored_label(Color32::RED, "✖ Not signed by this key");
+                }
+                None => {}
+            }
+        });
+
+        if let Some(error_message) = &self.verify_error_message {
+            ui.colored_label(Color32::RED, error_message);
+        }
+    }
+
+    /// Checks the signature input against the message input and this key's public key.
+    fn verify_signature(&self) -> Result<bool, String> {
+        verify_message_signature(
+            &self.key,
+            &self.verify_message_input,
+            &self.verify_signature_input,
+        )
     }
 
     fn sign_message(&mut self) {
@@ -503,31 +1322,18 @@ impl KeyInfoScreen {
                 }
             };
 
-            // Use the key type to determine how to sign
-            match self.key.key_type() {
-                KeyType::ECDSA_SECP256K1 | KeyType::ECDSA_HASH160 => {
-                    // Sign the message using ECDSA
-                    let secp = Secp256k1::new();
-
-                    let message_hash = signed_msg_hash(self.message_input.as_str());
-                    let message = Message::from_digest(*message_hash.as_byte_array());
-
-                    let secret_key = SecretKey::from_byte_array(&private_key_bytes).unwrap();
-
-                    let signature = secp.sign_ecdsa(&message, &secret_key);
-
-                    // Serialize the signature
-                    let mut serialized_signature = signature.serialize_compact().to_vec();
-                    serialized_signature.insert(0, 32);
-
-                    // Encode to Base64
-                    let signature_base64 = STANDARD.encode(serialized_signature);
-
-                    self.signed_message = Some(signature_base64);
+            match sign_message_with_key(
+                self.key.key_type(),
+                &private_key_bytes,
+                &self.message_input,
+            ) {
+                Ok(signature) => {
+                    self.signed_message = Some(signature);
                     self.sign_error_message = None;
                 }
-                _ => {
-                    self.sign_error_message = Some("Unsupported key type for signing.".to_string());
+                Err(e) => {
+                    self.signed_message = None;
+                    self.sign_error_message = Some(e);
                 }
             }
         } else {
@@ -565,3 +1371,232 @@ impl ScreenWithWalletUnlock for KeyInfoScreen {
         self.error_message.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dash_sdk::dpp::identity::identity_public_key::v0::IdentityPublicKeyV0;
+    use dash_sdk::dpp::identity::{Purpose, SecurityLevel};
+    use dash_sdk::dpp::platform_value::BinaryData;
+    use dash_sdk::dpp::version::PlatformVersion;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Returns a public key of `key_type` with its private key, and the private key of another
+    /// key of the same type.
+    fn key_with_private_keys(key_type: KeyType) -> (IdentityPublicKey, [u8; 32], [u8; 32]) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let platform_version = PlatformVersion::latest();
+        let (public_key_data, private_key) = key_type
+            .random_public_and_private_key_data(&mut rng, platform_version)
+            .expect("expected to generate key");
+        let (_, other_private_key) = key_type
+            .random_public_and_private_key_data(&mut rng, platform_version)
+            .expect("expected to generate key");
+        let key = IdentityPublicKey::V0(IdentityPublicKeyV0 {
+            id: 1,
+            purpose: Purpose::AUTHENTICATION,
+            security_level: SecurityLevel::HIGH,
+            contract_bounds: None,
+            key_type,
+            read_only: false,
+            data: BinaryData::new(public_key_data),
+            disabled_at: None,
+        });
+        (key, private_key, other_private_key)
+    }
+
+    #[test]
+    fn test_parse_private_key_for_supported_key_types() {
+        for key_type in [
+            KeyType::ECDSA_SECP256K1,
+            KeyType::ECDSA_HASH160,
+            KeyType::BLS12_381,
+            KeyType::EDDSA_25519_HASH160,
+        ] {
+            let (key, private_key, other_private_key) = key_with_private_keys(key_type);
+
+            assert_eq!(
+                parse_private_key_for(&key, &hex::encode(private_key), Network::Testnet),
+                Ok(private_key),
+                "{:?}",
+                key_type
+            );
+            assert_eq!(
+                parse_private_key_for(&key, &hex::encode(other_private_key), Network::Testnet),
+                Err(PrivateKeyInputError::Mismatch(key_type)),
+                "{:?}",
+                key_type
+            );
+            assert!(
+                matches!(
+                    parse_private_key_for(&key, &hex::encode(&private_key[..31]), Network::Testnet),
+                    Err(PrivateKeyInputError::WrongLength {
+                        actual_hex_chars: 62,
+                        ..
+                    })
+                ),
+                "{:?}",
+                key_type
+            );
+            // Odd lengths are reported the same way rather than as invalid hex
+            let error =
+                parse_private_key_for(&key, &hex::encode(private_key)[..63], Network::Testnet)
+                    .unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .starts_with("Expected 64 hex characters (32 bytes)")
+                    && error.to_string().contains("got 63."),
+                "{:?}: {}",
+                key_type,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_signed_messages_verify_against_their_key() {
+        for key_type in [KeyType::ECDSA_SECP256K1, KeyType::ECDSA_HASH160] {
+            let (key, private_key, other_private_key) = key_with_private_keys(key_type);
+
+            let signature =
+                sign_message_with_key(key_type, &private_key, "hello").expect("expected to sign");
+            assert_eq!(signature.len(), 65);
+            for encoded in [hex::encode(&signature), STANDARD.encode(&signature)] {
+                assert_eq!(
+                    verify_message_signature(&key, "hello", &encoded),
+                    Ok(true),
+                    "{:?}",
+                    key_type
+                );
+            }
+            // Bare compact signatures and the fixed header of older versions verify too
+            assert_eq!(
+                verify_message_signature(&key, "hello", &hex::encode(&signature[1..])),
+                Ok(true),
+                "{:?}",
+                key_type
+            );
+            let mut old_signature = signature.clone();
+            old_signature[0] = 32;
+            assert_eq!(
+                verify_message_signature(&key, "hello", &hex::encode(&old_signature)),
+                Ok(true),
+                "{:?}",
+                key_type
+            );
+
+            assert_eq!(
+                verify_message_signature(&key, "goodbye", &hex::encode(&signature)),
+                Ok(false),
+                "{:?}",
+                key_type
+            );
+            let other_signature = sign_message_with_key(key_type, &other_private_key, "hello")
+                .expect("expected to sign");
+            assert_eq!(
+                verify_message_signature(&key, "hello", &hex::encode(other_signature)),
+                Ok(false),
+                "{:?}",
+                key_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_private_key_for_hints_at_public_key_length() {
+        let (key, ..) = key_with_private_keys(KeyType::BLS12_381);
+        assert!(matches!(
+            parse_private_key_for(&key, &hex::encode([1u8; 48]), Network::Testnet),
+            Err(PrivateKeyInputError::WrongLength {
+                expected_bytes: 32,
+                actual_hex_chars: 96,
+                hint,
+                ..
+            }) if !hint.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_private_key_for_accepts_wif_only_for_ecdsa() {
+        let (key, private_key, _) = key_with_private_keys(KeyType::ECDSA_SECP256K1);
+        let wif = PrivateKey::from_slice(&private_key, Network::Testnet)
+            .expect("expected valid secret key")
+            .to_wif();
+        assert_eq!(
+            parse_private_key_for(&key, &wif, Network::Testnet),
+            Ok(private_key)
+        );
+        assert!(matches!(
+            parse_private_key_for(&key, &wif, Network::Dash),
+            Err(PrivateKeyInputError::WrongNetwork(_))
+        ));
+
+        let (key, ..) = key_with_private_keys(KeyType::BLS12_381);
+        assert!(matches!(
+            parse_private_key_for(&key, &wif, Network::Testnet),
+            Err(PrivateKeyInputError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_key_import_file_reads_text_lines() {
+        let entries = parse_key_import_file("# exported keys\n0 aa\n\n1: bb\n2=cc\nthree dd\n4\n")
+            .expect("expected a text file to parse");
+        assert_eq!(
+            entries,
+            vec![
+                ("Line 2".to_string(), Ok((0, "aa".to_string()))),
+                ("Line 4".to_string(), Ok((1, "bb".to_string()))),
+                ("Line 5".to_string(), Ok((2, "cc".to_string()))),
+                (
+                    "Line 6".to_string(),
+                    Err("\"three\" is not a key id".to_string())
+                ),
+                (
+                    "Line 7".to_string(),
+                    Err("Expected a key id followed by a private key".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_key_import_file_reads_json() {
+        let entries = parse_key_import_file(r#"{"0": "aa", "2": " bb ", "x": "cc"}"#)
+            .expect("expected a JSON file to parse");
+        assert_eq!(
+            entries,
+            vec![
+                ("Key 0".to_string(), Ok((0, "aa".to_string()))),
+                ("Key 2".to_string(), Ok((2, "bb".to_string()))),
+                (
+                    "Key x".to_string(),
+                    Err("\"x\" is not a key id".to_string())
+                ),
+            ]
+        );
+        assert!(parse_key_import_file("{ not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_private_key_for_rejects_script_hash_keys() {
+        let key = IdentityPublicKey::V0(IdentityPublicKeyV0 {
+            id: 1,
+            purpose: Purpose::TRANSFER,
+            security_level: SecurityLevel::CRITICAL,
+            contract_bounds: None,
+            key_type: KeyType::BIP13_SCRIPT_HASH,
+            read_only: false,
+            data: BinaryData::new(vec![1; 20]),
+            disabled_at: None,
+        });
+        assert_eq!(
+            parse_private_key_for(&key, &hex::encode([1u8; 32]), Network::Testnet),
+            Err(PrivateKeyInputError::Unsupported(
+                KeyType::BIP13_SCRIPT_HASH
+            ))
+        );
+    }
+}
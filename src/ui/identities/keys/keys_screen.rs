@@ -15,6 +15,10 @@ pub struct KeysScreen {
 impl ScreenLike for KeysScreen {
     fn refresh(&mut self) {}
 
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Identity Keys");
@@ -9,6 +9,8 @@ use dash_sdk::{
 };
 
 use crate::{
+    app::AppAction,
+    backend_task::{identity::IdentityTask, BackendTask},
     context::AppContext,
     model::{
         qualified_identity::{
@@ -20,7 +22,9 @@ use crate::{
 
 pub mod add_existing_identity_screen;
 pub mod add_new_identity_screen;
+pub mod bulk_top_up_screen;
 mod funding_common;
+pub mod identities_overview_screen;
 pub mod identities_screen;
 pub mod keys;
 pub mod register_dpns_name_screen;
@@ -123,3 +127,38 @@ pub fn get_selected_wallet(
         None
     }
 }
+
+/// Whether an error returned by Platform was caused by the identity nonce being out of step,
+/// which usually means a previous transition is still pending or the local nonce is stale.
+pub fn is_nonce_error(error_message: &str) -> bool {
+    error_message.to_lowercase().contains("nonce")
+}
+
+/// Renders a hint below a nonce-related error, showing the identity nonce last fetched from
+/// Platform and a button to fetch it again.
+pub fn render_nonce_error_hint(
+    ui: &mut egui::Ui,
+    qualified_identity: &QualifiedIdentity,
+    identity_nonce: Option<u64>,
+) -> AppAction {
+    let mut action = AppAction::None;
+
+    ui.add_space(5.0);
+    ui.label(
+        "This looks like an identity nonce mismatch. Wait for any pending transitions from this \
+         identity to finish, then check the current nonce below before retrying.",
+    );
+    ui.horizontal(|ui| {
+        match identity_nonce {
+            Some(nonce) => ui.label(format!("Identity nonce on Platform: {}", nonce)),
+            None => ui.label("Identity nonce on Platform: unknown"),
+        };
+        if ui.button("Refresh nonce").clicked() {
+            action = AppAction::BackendTask(BackendTask::IdentityTask(
+                IdentityTask::FetchIdentityNonce(qualified_identity.clone()),
+            ));
+        }
+    });
+
+    action
+}
@@ -253,6 +253,10 @@ impl ScreenLike for RegisterDpnsNameScreen {
         }
     }
 
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
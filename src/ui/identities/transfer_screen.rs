@@ -1,6 +1,6 @@
 use crate::app::AppAction;
 use crate::backend_task::identity::IdentityTask;
-use crate::backend_task::BackendTask;
+use crate::backend_task::{BackendTask, BackendTaskSuccessResult};
 use crate::context::AppContext;
 use crate::model::qualified_identity::QualifiedIdentity;
 use crate::model::wallet::Wallet;
@@ -21,8 +21,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::ui::components::wallet_unlock::ScreenWithWalletUnlock;
 
-use super::get_selected_wallet;
 use super::keys::add_key_screen::AddKeyScreen;
+use super::{get_selected_wallet, is_nonce_error, render_nonce_error_hint};
 
 #[derive(PartialEq)]
 pub enum TransferCreditsStatus {
@@ -45,6 +45,7 @@ pub struct TransferScreen {
     selected_wallet: Option<Arc<RwLock<Wallet>>>,
     wallet_password: String,
     show_password: bool,
+    identity_nonce: Option<u64>,
 }
 
 impl TransferScreen {
@@ -73,6 +74,7 @@ impl TransferScreen {
             selected_wallet,
             wallet_password: String::new(),
             show_password: false,
+            identity_nonce: None,
         }
     }
 
@@ -258,6 +260,16 @@ impl ScreenLike for TransferScreen {
         }
     }
 
+    fn display_task_result(&mut self, backend_task_success_result: BackendTaskSuccessResult) {
+        if let BackendTaskSuccessResult::IdentityNonce(identity_id, nonce) =
+            backend_task_success_result
+        {
+            if identity_id == self.identity.identity.id() {
+                self.identity_nonce = Some(nonce);
+            }
+        }
+    }
+
     fn refresh(&mut self) {
         // Refresh the identity because there might be new keys
         self.identity = self
@@ -271,6 +283,10 @@ impl ScreenLike for TransferScreen {
     }
 
     /// Renders the UI components for the withdrawal screen
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
@@ -421,6 +437,10 @@ impl ScreenLike for TransferScreen {
                     }
                     TransferCreditsStatus::ErrorMessage(msg) => {
                         ui.colored_label(egui::Color32::RED, format!("Error: {}", msg));
+                        if is_nonce_error(msg) {
+                            action |=
+                                render_nonce_error_hint(ui, &self.identity, self.identity_nonce);
+                        }
                     }
                     TransferCreditsStatus::Complete => {
                         // Handled above
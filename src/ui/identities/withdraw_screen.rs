@@ -1,6 +1,6 @@
 use crate::app::AppAction;
 use crate::backend_task::identity::IdentityTask;
-use crate::backend_task::BackendTask;
+use crate::backend_task::{BackendTask, BackendTaskSuccessResult};
 use crate::context::AppContext;
 use crate::model::qualified_identity::encrypted_key_storage::PrivateKeyData;
 use crate::model::qualified_identity::{IdentityType, PrivateKeyTarget, QualifiedIdentity};
@@ -21,9 +21,9 @@ use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use super::get_selected_wallet;
 use super::keys::add_key_screen::AddKeyScreen;
 use super::keys::key_info_screen::KeyInfoScreen;
+use super::{get_selected_wallet, is_nonce_error, render_nonce_error_hint};
 
 #[derive(PartialEq)]
 pub enum WithdrawFromIdentityStatus {
@@ -46,6 +46,7 @@ pub struct WithdrawalScreen {
     wallet_password: String,
     show_password: bool,
     error_message: Option<String>,
+    identity_nonce: Option<u64>,
 }
 
 impl WithdrawalScreen {
@@ -74,6 +75,7 @@ impl WithdrawalScreen {
             wallet_password: String::new(),
             show_password: false,
             error_message,
+            identity_nonce: None,
         }
     }
 
@@ -155,6 +157,24 @@ impl WithdrawalScreen {
         }
     }
 
+    /// Parses the entered withdrawal address and checks that it belongs to the current network.
+    /// Returns `None` if no address was entered.
+    fn parse_withdrawal_address(&self) -> Result<Option<Address>, String> {
+        let address_input = self.withdrawal_address.trim();
+        if address_input.is_empty() {
+            return Ok(None);
+        }
+        let address = Address::from_str(address_input)
+            .map_err(|_| "Invalid withdrawal address".to_string())?;
+        if !address.is_valid_for_network(self.app_context.network) {
+            return Err(format!(
+                "Withdrawal address {} is not valid for the current network ({})",
+                address_input, self.app_context.network
+            ));
+        }
+        Ok(Some(address.assume_checked()))
+    }
+
     fn show_confirmation_popup(&mut self, ui: &mut Ui) -> AppAction {
         let mut app_action = AppAction::None;
         let mut is_open = true;
@@ -162,18 +182,13 @@ impl WithdrawalScreen {
             .collapsible(false)
             .open(&mut is_open)
             .show(ui.ctx(), |ui| {
-                let address = if self.withdrawal_address.is_empty() {
-                    None
-                } else {
-                    match Address::from_str(&self.withdrawal_address) {
-                        Ok(address) => Some(address.assume_checked()),
-                        Err(_) => {
-                            self.withdraw_from_identity_status =
-                                WithdrawFromIdentityStatus::ErrorMessage(
-                                    "Invalid withdrawal address".to_string(),
-                                );
-                            None
-                        }
+                let address = match self.parse_withdrawal_address() {
+                    Ok(address) => address,
+                    Err(e) => {
+                        self.withdraw_from_identity_status =
+                            WithdrawFromIdentityStatus::ErrorMessage(e);
+                        self.confirmation_popup = false;
+                        return;
                     }
                 };
 
@@ -289,6 +304,16 @@ impl ScreenLike for WithdrawalScreen {
         }
     }
 
+    fn display_task_result(&mut self, backend_task_success_result: BackendTaskSuccessResult) {
+        if let BackendTaskSuccessResult::IdentityNonce(identity_id, nonce) =
+            backend_task_success_result
+        {
+            if identity_id == self.identity.identity.id() {
+                self.identity_nonce = Some(nonce);
+            }
+        }
+    }
+
     fn refresh(&mut self) {
         // Refresh the identity because there might be new keys
         self.identity = self
@@ -302,6 +327,10 @@ impl ScreenLike for WithdrawalScreen {
     }
 
     /// Renders the UI components for the withdrawal screen
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
@@ -431,7 +460,15 @@ impl ScreenLike for WithdrawalScreen {
                     .min_size(egui::vec2(60.0, 30.0));
 
                 if ui.add(button).clicked() {
-                    self.confirmation_popup = true;
+                    match self.parse_withdrawal_address() {
+                        Ok(_) => {
+                            self.confirmation_popup = true;
+                        }
+                        Err(e) => {
+                            self.withdraw_from_identity_status =
+                                WithdrawFromIdentityStatus::ErrorMessage(e);
+                        }
+                    }
                 }
 
                 if self.confirmation_popup {
@@ -477,6 +514,13 @@ impl ScreenLike for WithdrawalScreen {
                     }
                     WithdrawFromIdentityStatus::ErrorMessage(msg) => {
                         ui.colored_label(egui::Color32::RED, format!("Error: {}", msg));
+                        if is_nonce_error(msg) {
+                            action |= render_nonce_error_hint(
+                                ui,
+                                &self.identity,
+                                self.identity_nonce,
+                            );
+                        }
                     }
                     WithdrawFromIdentityStatus::Complete => {
                         ui.colored_label(egui::Color32::DARK_GREEN, format!("Successfully withdrew from identity"));
@@ -7,6 +7,8 @@ use crate::model::qualified_identity::encrypted_key_storage::{
 use crate::model::qualified_identity::QualifiedIdentity;
 use crate::ui::contracts_documents::document_query_screen::DocumentQueryScreen;
 use crate::ui::dpns::dpns_contested_names_screen::DPNSScreen;
+use crate::ui::identities::bulk_top_up_screen::BulkTopUpScreen;
+use crate::ui::identities::identities_overview_screen::IdentitiesOverviewScreen;
 use crate::ui::identities::keys::add_key_screen::AddKeyScreen;
 use crate::ui::identities::keys::key_info_screen::KeyInfoScreen;
 use crate::ui::identities::keys::keys_screen::KeysScreen;
@@ -38,11 +40,14 @@ pub mod contracts_documents;
 pub mod dpns;
 pub(crate) mod identities;
 pub mod network_chooser_screen;
+pub mod theme;
 pub mod tools;
 pub(crate) mod wallets;
 
-#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
 pub enum RootScreenType {
+    /// Shown when no screen was saved, or when the saved one no longer exists
+    #[default]
     RootScreenIdentities,
     RootScreenDPNSActiveContests,
     RootScreenDPNSPastContests,
@@ -140,6 +145,8 @@ pub enum ScreenType {
     RegisterDpnsName,
     ProofLog,
     TopUpIdentity(QualifiedIdentity),
+    BulkTopUp,
+    IdentitiesOverview,
     ScheduledVotes,
     AddContracts,
     ProofVisualizer,
@@ -164,6 +171,10 @@ impl ScreenType {
             ScreenType::TopUpIdentity(identity) => {
                 Screen::TopUpIdentityScreen(TopUpIdentityScreen::new(identity.clone(), app_context))
             }
+            ScreenType::BulkTopUp => Screen::BulkTopUpScreen(BulkTopUpScreen::new(app_context)),
+            ScreenType::IdentitiesOverview => {
+                Screen::IdentitiesOverviewScreen(IdentitiesOverviewScreen::new(app_context))
+            }
             ScreenType::AddExistingIdentity => {
                 Screen::AddExistingIdentityScreen(AddExistingIdentityScreen::new(app_context))
             }
@@ -235,6 +246,8 @@ pub enum Screen {
     RegisterDpnsNameScreen(RegisterDpnsNameScreen),
     WithdrawalScreen(WithdrawalScreen),
     TopUpIdentityScreen(TopUpIdentityScreen),
+    BulkTopUpScreen(BulkTopUpScreen),
+    IdentitiesOverviewScreen(IdentitiesOverviewScreen),
     TransferScreen(TransferScreen),
     AddKeyScreen(AddKeyScreen),
     ProofLogScreen(ProofLogScreen),
@@ -263,6 +276,8 @@ impl Screen {
             Screen::AddNewWalletScreen(screen) => screen.app_context = app_context,
             Screen::TransferScreen(screen) => screen.app_context = app_context,
             Screen::TopUpIdentityScreen(screen) => screen.app_context = app_context,
+            Screen::BulkTopUpScreen(screen) => screen.app_context = app_context,
+            Screen::IdentitiesOverviewScreen(screen) => screen.app_context = app_context,
             Screen::WalletsBalancesScreen(screen) => screen.app_context = app_context,
             Screen::ImportWalletScreen(screen) => screen.app_context = app_context,
             Screen::ProofLogScreen(screen) => screen.app_context = app_context,
@@ -291,6 +306,12 @@ pub trait ScreenLike {
     }
 
     fn pop_on_success(&mut self) {}
+
+    /// The action to take when the user asks to go back, for example with Escape. Screens
+    /// pushed on top of a root screen usually pop themselves.
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::None
+    }
 }
 
 // Implement Debug for Screen using the ScreenType
@@ -345,6 +366,8 @@ impl Screen {
             Screen::TopUpIdentityScreen(screen) => {
                 ScreenType::TopUpIdentity(screen.identity.clone())
             }
+            Screen::BulkTopUpScreen(_) => ScreenType::BulkTopUp,
+            Screen::IdentitiesOverviewScreen(_) => ScreenType::IdentitiesOverview,
             Screen::RegisterDpnsNameScreen(_) => ScreenType::RegisterDpnsName,
             Screen::AddNewWalletScreen(_) => ScreenType::AddNewWallet,
             Screen::TransferScreen(screen) => ScreenType::TransferScreen(screen.identity.clone()),
@@ -367,6 +390,8 @@ impl ScreenLike for Screen {
             Screen::ImportWalletScreen(screen) => screen.refresh(),
             Screen::AddNewIdentityScreen(screen) => screen.refresh(),
             Screen::TopUpIdentityScreen(screen) => screen.refresh(),
+            Screen::BulkTopUpScreen(screen) => screen.refresh(),
+            Screen::IdentitiesOverviewScreen(screen) => screen.refresh(),
             Screen::AddExistingIdentityScreen(screen) => screen.refresh(),
             Screen::KeyInfoScreen(screen) => screen.refresh(),
             Screen::KeysScreen(screen) => screen.refresh(),
@@ -392,6 +417,8 @@ impl ScreenLike for Screen {
             Screen::ImportWalletScreen(screen) => screen.refresh_on_arrival(),
             Screen::AddNewIdentityScreen(screen) => screen.refresh_on_arrival(),
             Screen::TopUpIdentityScreen(screen) => screen.refresh_on_arrival(),
+            Screen::BulkTopUpScreen(screen) => screen.refresh_on_arrival(),
+            Screen::IdentitiesOverviewScreen(screen) => screen.refresh_on_arrival(),
             Screen::AddExistingIdentityScreen(screen) => screen.refresh_on_arrival(),
             Screen::KeyInfoScreen(screen) => screen.refresh_on_arrival(),
             Screen::KeysScreen(screen) => screen.refresh_on_arrival(),
@@ -417,6 +444,8 @@ impl ScreenLike for Screen {
             Screen::ImportWalletScreen(screen) => screen.ui(ctx),
             Screen::AddNewIdentityScreen(screen) => screen.ui(ctx),
             Screen::TopUpIdentityScreen(screen) => screen.ui(ctx),
+            Screen::BulkTopUpScreen(screen) => screen.ui(ctx),
+            Screen::IdentitiesOverviewScreen(screen) => screen.ui(ctx),
             Screen::AddExistingIdentityScreen(screen) => screen.ui(ctx),
             Screen::KeyInfoScreen(screen) => screen.ui(ctx),
             Screen::KeysScreen(screen) => screen.ui(ctx),
@@ -442,6 +471,10 @@ impl ScreenLike for Screen {
             Screen::ImportWalletScreen(screen) => screen.display_message(message, message_type),
             Screen::AddNewIdentityScreen(screen) => screen.display_message(message, message_type),
             Screen::TopUpIdentityScreen(screen) => screen.display_message(message, message_type),
+            Screen::BulkTopUpScreen(screen) => screen.display_message(message, message_type),
+            Screen::IdentitiesOverviewScreen(screen) => {
+                screen.display_message(message, message_type)
+            }
             Screen::AddExistingIdentityScreen(screen) => {
                 screen.display_message(message, message_type)
             }
@@ -485,6 +518,12 @@ impl ScreenLike for Screen {
             Screen::TopUpIdentityScreen(screen) => {
                 screen.display_task_result(backend_task_success_result.clone())
             }
+            Screen::BulkTopUpScreen(screen) => {
+                screen.display_task_result(backend_task_success_result.clone())
+            }
+            Screen::IdentitiesOverviewScreen(screen) => {
+                screen.display_task_result(backend_task_success_result.clone())
+            }
             Screen::AddExistingIdentityScreen(screen) => {
                 screen.display_task_result(backend_task_success_result.clone())
             }
@@ -536,6 +575,8 @@ impl ScreenLike for Screen {
             Screen::ImportWalletScreen(screen) => screen.pop_on_success(),
             Screen::AddNewIdentityScreen(screen) => screen.pop_on_success(),
             Screen::TopUpIdentityScreen(screen) => screen.pop_on_success(),
+            Screen::BulkTopUpScreen(screen) => screen.pop_on_success(),
+            Screen::IdentitiesOverviewScreen(screen) => screen.pop_on_success(),
             Screen::AddExistingIdentityScreen(screen) => screen.pop_on_success(),
             Screen::KeyInfoScreen(screen) => screen.pop_on_success(),
             Screen::KeysScreen(screen) => screen.pop_on_success(),
@@ -551,4 +592,31 @@ impl ScreenLike for Screen {
             Screen::ProofVisualizerScreen(screen) => screen.pop_on_success(),
         }
     }
+
+    fn pop_screen(&mut self) -> AppAction {
+        match self {
+            Screen::IdentitiesScreen(screen) => screen.pop_screen(),
+            Screen::DPNSScreen(screen) => screen.pop_screen(),
+            Screen::DocumentQueryScreen(screen) => screen.pop_screen(),
+            Screen::AddNewWalletScreen(screen) => screen.pop_screen(),
+            Screen::ImportWalletScreen(screen) => screen.pop_screen(),
+            Screen::AddNewIdentityScreen(screen) => screen.pop_screen(),
+            Screen::TopUpIdentityScreen(screen) => screen.pop_screen(),
+            Screen::BulkTopUpScreen(screen) => screen.pop_screen(),
+            Screen::IdentitiesOverviewScreen(screen) => screen.pop_screen(),
+            Screen::AddExistingIdentityScreen(screen) => screen.pop_screen(),
+            Screen::KeyInfoScreen(screen) => screen.pop_screen(),
+            Screen::KeysScreen(screen) => screen.pop_screen(),
+            Screen::RegisterDpnsNameScreen(screen) => screen.pop_screen(),
+            Screen::WithdrawalScreen(screen) => screen.pop_screen(),
+            Screen::TransferScreen(screen) => screen.pop_screen(),
+            Screen::AddKeyScreen(screen) => screen.pop_screen(),
+            Screen::TransitionVisualizerScreen(screen) => screen.pop_screen(),
+            Screen::NetworkChooserScreen(screen) => screen.pop_screen(),
+            Screen::WalletsBalancesScreen(screen) => screen.pop_screen(),
+            Screen::ProofLogScreen(screen) => screen.pop_screen(),
+            Screen::AddContractsScreen(screen) => screen.pop_screen(),
+            Screen::ProofVisualizerScreen(screen) => screen.pop_screen(),
+        }
+    }
 }
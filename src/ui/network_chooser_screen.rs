@@ -2,16 +2,31 @@ use crate::app::AppAction;
 use crate::backend_task::core::{CoreItem, CoreTask};
 use crate::backend_task::{BackendTask, BackendTaskSuccessResult};
 use crate::context::AppContext;
+use crate::database::{IdentityAuditEntry, QueryPlanReport};
+use crate::model::dash_price::DashPrice;
+use crate::model::state_archive::StateArchive;
 use crate::ui::components::left_panel::add_left_panel;
 use crate::ui::components::top_panel::add_top_panel;
 use crate::ui::wallets::add_new_wallet_screen::AddNewWalletScreen;
-use crate::ui::{RootScreenType, Screen, ScreenLike};
+use crate::ui::{MessageType, RootScreenType, Screen, ScreenLike};
 use dash_sdk::dpp::dashcore::Network;
 use dash_sdk::dpp::identity::TimestampMillis;
+use dash_sdk::dpp::platform_value::string_encoding::Encoding;
 use eframe::egui::{self, Color32, Context, Ui};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+/// Number of audit log entries shown, newest first.
+const AUDIT_LOG_ENTRIES: u32 = 100;
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
 pub struct NetworkChooserScreen {
     pub mainnet_app_context: Arc<AppContext>,
     pub testnet_app_context: Option<Arc<AppContext>>,
@@ -22,6 +37,21 @@ pub struct NetworkChooserScreen {
     custom_dash_qt_path: Option<String>,
     custom_dash_qt_error_message: Option<String>,
     overwrite_dash_conf: bool,
+    table_font_size: f32,
+    monospace_ids: bool,
+    identities_auto_refresh_on_open: bool,
+    read_only: bool,
+    dash_price_input: String,
+    dash_price_currency_input: String,
+    dash_price_error: Option<String>,
+    maintenance_report: Option<Result<Vec<QueryPlanReport>, String>>,
+    prune_result: Option<Result<usize, String>>,
+    integrity_result: Option<Result<bool, String>>,
+    vacuum_result: Option<Result<(u64, u64), String>>,
+    audit_log: Option<Result<Vec<IdentityAuditEntry>, String>>,
+    archive_passphrase: String,
+    archive_status: Option<Result<String, String>>,
+    pending_archive: Option<(StateArchive, Vec<bool>)>,
 }
 
 impl NetworkChooserScreen {
@@ -32,6 +62,18 @@ impl NetworkChooserScreen {
         custom_dash_qt_path: Option<String>,
         overwrite_dash_conf: bool,
     ) -> Self {
+        let (table_font_size, monospace_ids) = mainnet_app_context
+            .db
+            .get_table_display_settings()
+            .expect("Failed to db get table display settings");
+        let identities_auto_refresh_on_open = mainnet_app_context
+            .db
+            .get_identities_auto_refresh_on_open()
+            .expect("Failed to db get auto refresh setting");
+        let dash_price = mainnet_app_context
+            .db
+            .get_dash_price()
+            .expect("Failed to db get DASH price");
         Self {
             mainnet_app_context: mainnet_app_context.clone(),
             testnet_app_context: testnet_app_context.cloned(),
@@ -42,6 +84,26 @@ impl NetworkChooserScreen {
             custom_dash_qt_path,
             custom_dash_qt_error_message: None,
             overwrite_dash_conf,
+            table_font_size,
+            monospace_ids,
+            identities_auto_refresh_on_open,
+            read_only: mainnet_app_context.is_read_only(),
+            dash_price_input: dash_price
+                .as_ref()
+                .map(|price| price.price.to_string())
+                .unwrap_or_default(),
+            dash_price_currency_input: dash_price
+                .map(|price| price.currency)
+                .unwrap_or_else(|| "USD".to_string()),
+            dash_price_error: None,
+            maintenance_report: None,
+            prune_result: None,
+            integrity_result: None,
+            vacuum_result: None,
+            audit_log: None,
+            archive_passphrase: String::new(),
+            archive_status: None,
+            pending_archive: None,
         }
     }
 
@@ -85,6 +147,8 @@ impl NetworkChooserScreen {
 
         ui.add_space(10.0);
 
+        // Settings are saved as soon as they change, so they can't be edited in read-only mode
+        let writable = !self.read_only;
         egui::CollapsingHeader::new("Advanced settings")
             .default_open(false)
             .show(ui, |ui| {
@@ -92,7 +156,11 @@ impl NetworkChooserScreen {
                     .show(ui, |ui| {
                         ui.label("Custom Dash-QT path:");
 
-                        if ui.button("Select file").clicked() {
+                        if ui
+                            .add_enabled(writable, egui::Button::new("Select file"))
+                            .on_disabled_hover_text("Not available in read-only mode")
+                            .clicked()
+                        {
                             if let Some(path) = rfd::FileDialog::new().pick_file() {
                                 {
                                     let file_name = path.file_name().and_then(|f| f.to_str());
@@ -133,7 +201,13 @@ impl NetworkChooserScreen {
                         }
                         ui.end_row();
 
-                        if ui.checkbox(&mut self.overwrite_dash_conf, "Overwrite dash.conf").clicked() {
+                        if ui
+                            .add_enabled(
+                                writable,
+                                egui::Checkbox::new(&mut self.overwrite_dash_conf, "Overwrite dash.conf"),
+                            )
+                            .clicked()
+                        {
                             self.current_app_context().db.update_dash_core_execution_settings(self.custom_dash_qt_path.clone(), self.overwrite_dash_conf).expect("Expected to save db settings");
                         }
                         if !self.overwrite_dash_conf {
@@ -155,10 +229,490 @@ impl NetworkChooserScreen {
                         }
 
                     });
+
+                ui.add_space(10.0);
+
+                egui::Grid::new("table_display_settings").show(ui, |ui| {
+                    ui.label("Table font size:");
+                    let font_size_response = ui.add_enabled(
+                        writable,
+                        egui::Slider::new(&mut self.table_font_size, 10.0..=24.0).step_by(1.0),
+                    );
+                    ui.end_row();
+
+                    let monospace_response = ui.add_enabled(
+                        writable,
+                        egui::Checkbox::new(&mut self.monospace_ids, "Monospace font for IDs"),
+                    );
+                    ui.end_row();
+
+                    if font_size_response.drag_stopped()
+                        || (font_size_response.changed() && !font_size_response.dragged())
+                        || monospace_response.clicked()
+                    {
+                        self.current_app_context()
+                            .db
+                            .update_table_display_settings(self.table_font_size, self.monospace_ids)
+                            .expect("Expected to save db settings");
+                    }
+
+                    if ui
+                        .add_enabled(
+                            writable,
+                            egui::Checkbox::new(
+                                &mut self.identities_auto_refresh_on_open,
+                                "Auto-refresh identities on open",
+                            ),
+                        )
+                        .on_hover_text(
+                            "Fetch the latest state of all identities from Platform every time the Identities screen is opened",
+                        )
+                        .clicked()
+                    {
+                        self.current_app_context()
+                            .db
+                            .update_identities_auto_refresh_on_open(
+                                self.identities_auto_refresh_on_open,
+                            )
+                            .expect("Expected to save db settings");
+                    }
+                    ui.end_row();
+
+                    if ui
+                        .checkbox(&mut self.read_only, "Read-only mode")
+                        .on_hover_text(
+                            "Browse without saving anything or broadcasting to Platform, until the app is closed",
+                        )
+                        .clicked()
+                    {
+                        self.mainnet_app_context.db.set_read_only(self.read_only);
+                        // Keys unlocked while nothing could be saved are still stored in the clear
+                        if let Err(e) = self.current_app_context().encrypt_stored_private_keys() {
+                            self.current_app_context().record_message(
+                                &format!("Encrypting the stored identity keys failed: {}", e),
+                                MessageType::Error,
+                            );
+                        }
+                    }
+                    ui.end_row();
+
+                    if ui
+                        .add_enabled(writable, egui::Button::new("Reset column widths"))
+                        .on_hover_text("Restore the default column widths of the identities table")
+                        .clicked()
+                    {
+                        self.current_app_context()
+                            .db
+                            .update_identities_column_widths(None)
+                            .expect("Expected to save db settings");
+                    }
+                    ui.end_row();
+
+                    ui.label("DASH price:").on_hover_text(
+                        "Shown as a fiat value next to DASH amounts, leave empty to show DASH only",
+                    );
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.dash_price_input)
+                                .hint_text("Price")
+                                .desired_width(80.0),
+                        );
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.dash_price_currency_input)
+                                .hint_text("Currency")
+                                .desired_width(50.0),
+                        );
+                        if ui
+                            .add_enabled(writable, egui::Button::new("Save"))
+                            .on_disabled_hover_text("Not available in read-only mode")
+                            .clicked()
+                        {
+                            self.save_dash_price();
+                        }
+                    });
+                    ui.end_row();
+                    if let Some(error) = &self.dash_price_error {
+                        ui.colored_label(Color32::DARK_RED, error);
+                        ui.end_row();
+                    }
+                });
+
+                ui.add_space(10.0);
+
+                if ui
+                    .button("Analyze & reindex database")
+                    .on_hover_text(
+                        "Refresh the query planner statistics and rebuild indexes, then compare the query plans used to load identities",
+                    )
+                    .clicked()
+                {
+                    let app_context = self.current_app_context();
+                    self.maintenance_report = Some(
+                        app_context
+                            .db
+                            .analyze_and_reindex(&app_context.network_string())
+                            .map_err(|e| e.to_string()),
+                    );
+                }
+
+                match &self.maintenance_report {
+                    Some(Ok(reports)) => {
+                        egui::Grid::new("maintenance_report")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for report in reports {
+                                    ui.label(report.query_name);
+                                    if report.changed() {
+                                        ui.label(format!(
+                                            "{} -> {}",
+                                            report.plan_before, report.plan_after
+                                        ));
+                                    } else {
+                                        ui.label(format!("{} (unchanged)", report.plan_after));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(Color32::DARK_RED, format!("Maintenance failed: {}", e));
+                    }
+                    None => {}
+                }
+
+                ui.add_space(10.0);
+
+                if ui
+                    .button("Prune unused remote identities")
+                    .on_hover_text(
+                        "Delete identities fetched from Platform that no contested name, scheduled vote, asset lock or stored contract refers to. Your own identities are kept",
+                    )
+                    .clicked()
+                {
+                    let app_context = self.current_app_context();
+                    self.prune_result = Some(
+                        app_context
+                            .db
+                            .prune_orphan_remote_identities(app_context)
+                            .map_err(|e| e.to_string()),
+                    );
+                }
+
+                match &self.prune_result {
+                    Some(Ok(removed)) => {
+                        ui.label(format!(
+                            "Removed {} unused remote identit{}.",
+                            removed,
+                            if *removed == 1 { "y" } else { "ies" }
+                        ));
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(Color32::DARK_RED, format!("Pruning failed: {}", e));
+                    }
+                    None => {}
+                }
+
+                ui.add_space(10.0);
+
+                self.render_integrity_and_vacuum(ui);
+
+                ui.add_space(10.0);
+
+                self.render_audit_log(ui);
+
+                ui.add_space(10.0);
+
+                self.render_state_archive(ui);
             });
         app_action
     }
 
+    fn render_integrity_and_vacuum(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if ui
+                .button("Check database integrity")
+                .on_hover_text("Run SQLite's integrity check over the whole database")
+                .clicked()
+            {
+                self.integrity_result = Some(
+                    self.current_app_context()
+                        .db
+                        .integrity_check()
+                        .map_err(|e| e.to_string()),
+                );
+            }
+            if ui
+                .button("Vacuum database")
+                .on_hover_text("Rebuild the database file to reclaim unused space")
+                .clicked()
+            {
+                let db = &self.current_app_context().db;
+                self.vacuum_result = Some(
+                    db.size_in_bytes()
+                        .and_then(|before| {
+                            db.vacuum()?;
+                            Ok((before, db.size_in_bytes()?))
+                        })
+                        .map_err(|e| e.to_string()),
+                );
+            }
+        });
+
+        match &self.integrity_result {
+            Some(Ok(true)) => {
+                ui.label("Integrity check passed.");
+            }
+            Some(Ok(false)) => {
+                ui.colored_label(
+                    Color32::DARK_RED,
+                    "Integrity check found problems, restore a backup from the backups folder.",
+                );
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::DARK_RED, format!("Integrity check failed: {}", e));
+            }
+            None => {}
+        }
+
+        match &self.vacuum_result {
+            Some(Ok((before, after))) => {
+                ui.label(format!(
+                    "Database size: {} -> {}",
+                    format_size(*before),
+                    format_size(*after)
+                ));
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::DARK_RED, format!("Vacuum failed: {}", e));
+            }
+            None => {}
+        }
+    }
+
+    fn render_audit_log(&mut self, ui: &mut Ui) {
+        if self.audit_log.is_none() {
+            if ui
+                .button("Show audit log")
+                .on_hover_text(
+                    "List the latest identity inserts, updates, alias changes and deletions",
+                )
+                .clicked()
+            {
+                // Every network shares the same database, so either context will do.
+                self.audit_log = Some(
+                    self.mainnet_app_context
+                        .db
+                        .get_audit_log(AUDIT_LOG_ENTRIES)
+                        .map_err(|e| e.to_string()),
+                );
+            }
+            return;
+        }
+
+        if ui.button("Hide audit log").clicked() {
+            self.audit_log = None;
+            return;
+        }
+
+        match &self.audit_log {
+            Some(Ok(entries)) if entries.is_empty() => {
+                ui.label("The audit log is empty.");
+            }
+            Some(Ok(entries)) => {
+                egui::ScrollArea::vertical()
+                    .id_salt("audit_log_scroll")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("audit_log")
+                            .num_columns(4)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for entry in entries {
+                                    let time = chrono::DateTime::from_timestamp(entry.time, 0)
+                                        .map(|date| {
+                                            date.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+                                        })
+                                        .unwrap_or_default();
+                                    ui.label(time);
+                                    ui.label(entry.action.as_str());
+                                    ui.label(entry.identity_id.to_string(Encoding::Base58));
+                                    ui.label(&entry.network);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+            Some(Err(e)) => {
+                ui.colored_label(
+                    Color32::DARK_RED,
+                    format!("Reading the audit log failed: {}", e),
+                );
+            }
+            None => {}
+        }
+    }
+
+    fn save_dash_price(&mut self) {
+        let price_input = self.dash_price_input.trim();
+        let price = if price_input.is_empty() {
+            None
+        } else {
+            match price_input.parse::<f64>() {
+                Ok(price) if price.is_finite() && price > 0.0 => Some(DashPrice {
+                    price,
+                    currency: self.dash_price_currency_input.trim().to_uppercase(),
+                }),
+                _ => {
+                    self.dash_price_error =
+                        Some("The DASH price must be a positive number".to_string());
+                    return;
+                }
+            }
+        };
+        // Settings are shared by every network, so either context will do.
+        self.dash_price_error = self
+            .mainnet_app_context
+            .db
+            .update_dash_price(price.as_ref())
+            .err()
+            .map(|e| format!("Saving the DASH price failed: {}", e));
+    }
+
+    fn render_state_archive(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Archive passphrase:");
+            ui.add(egui::TextEdit::singleline(&mut self.archive_passphrase).password(true));
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Export everything…")
+                .on_hover_text(
+                    "Save wallets, identities, contracts, top ups, contested names, scheduled votes and settings to a single file. Wallets, identities and settings are encrypted with the passphrase",
+                )
+                .clicked()
+            {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_file_name("dash-evo-tool-archive.bin")
+                    .save_file()
+                {
+                    self.archive_status = Some(
+                        StateArchive::export(&self.current_app_context().db, &self.archive_passphrase)
+                            .and_then(|archive| archive.to_bytes())
+                            .and_then(|bytes| {
+                                std::fs::write(&path, bytes).map_err(|e| e.to_string())
+                            })
+                            .map(|_| format!("Archive saved to {}", path.display())),
+                    );
+                }
+            }
+
+            if ui.button("Import archive…").clicked() {
+                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                    match std::fs::read(&path)
+                        .map_err(|e| e.to_string())
+                        .and_then(|bytes| StateArchive::from_bytes(&bytes))
+                    {
+                        Ok(archive) => {
+                            let selected = vec![true; archive.sections.len()];
+                            self.pending_archive = Some((archive, selected));
+                            self.archive_status = None;
+                        }
+                        Err(e) => self.archive_status = Some(Err(e)),
+                    }
+                }
+            }
+        });
+
+        match &self.archive_status {
+            Some(Ok(message)) => {
+                ui.colored_label(Color32::DARK_GREEN, message);
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::DARK_RED, format!("Archive error: {}", e));
+            }
+            None => {}
+        }
+    }
+
+    fn show_archive_import_window(&mut self, ctx: &Context) {
+        let Some((archive, selected)) = &mut self.pending_archive else {
+            return;
+        };
+
+        let mut open = true;
+        let mut restore_clicked = false;
+        egui::Window::new("Import Archive")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let created_at = chrono::DateTime::from_timestamp(archive.created_at, 0)
+                    .map(|date| date.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                    .unwrap_or_default();
+                ui.label(format!(
+                    "Created {} (format {}, database version {})",
+                    created_at, archive.format_version, archive.db_version
+                ));
+                ui.add_space(5.0);
+
+                egui::Grid::new("archive_manifest")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (section, selected) in archive.sections.iter().zip(selected.iter_mut())
+                        {
+                            ui.checkbox(selected, &section.table);
+                            ui.label(format!("{} rows", section.row_count));
+                            ui.label(if section.is_encrypted() {
+                                "encrypted"
+                            } else {
+                                ""
+                            });
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(5.0);
+                ui.label("Selected tables are replaced with the archived rows. Settings and identities are always restored together.");
+                restore_clicked = ui.button("Restore selected").clicked();
+            });
+
+        if restore_clicked {
+            let tables: Vec<String> = archive
+                .sections
+                .iter()
+                .zip(selected.iter())
+                .filter(|(_, selected)| **selected)
+                .map(|(section, _)| section.table.clone())
+                .collect();
+            // Every network shares the same database, so either context will do.
+            let result = archive.restore(
+                &self.mainnet_app_context.db,
+                &self.archive_passphrase,
+                &tables,
+            );
+            self.archive_status = Some(result.map(|_| {
+                "Archive restored. Restart the application to load the restored data.".to_string()
+            }));
+            if self
+                .archive_status
+                .as_ref()
+                .is_some_and(|status| status.is_ok())
+            {
+                self.pending_archive = None;
+                // The restored identity keys may be encrypted with another main password
+                self.mainnet_app_context.lock_private_keys();
+                if let Some(testnet_app_context) = &self.testnet_app_context {
+                    testnet_app_context.lock_private_keys();
+                }
+            }
+        } else if !open {
+            self.pending_archive = None;
+        }
+    }
+
     /// Render a single row for the network table
     fn render_network_row(&mut self, ui: &mut Ui, network: Network, name: &str) -> AppAction {
         let mut app_action = AppAction::None;
@@ -285,6 +839,8 @@ impl ScreenLike for NetworkChooserScreen {
             action |= self.render_network_table(ui);
         });
 
+        self.show_archive_import_window(ctx);
+
         // Recheck both network status every 3 seconds
         let recheck_time = Duration::from_secs(3);
         if action == AppAction::None {
@@ -0,0 +1,74 @@
+use crate::ui::MessageType;
+use egui::{Color32, Context, Theme, Visuals};
+
+/// Color scheme of the app, chosen from the top panel and saved in the settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    /// Follows the operating system, dark when it can't be detected
+    #[default]
+    System,
+}
+
+impl ThemeMode {
+    pub const ALL: [ThemeMode; 3] = [ThemeMode::Light, ThemeMode::Dark, ThemeMode::System];
+
+    pub fn to_int(self) -> u32 {
+        match self {
+            ThemeMode::Light => 0,
+            ThemeMode::Dark => 1,
+            ThemeMode::System => 2,
+        }
+    }
+
+    pub fn from_int(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(ThemeMode::Light),
+            1 => Some(ThemeMode::Dark),
+            2 => Some(ThemeMode::System),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeMode::Light => "Light",
+            ThemeMode::Dark => "Dark",
+            ThemeMode::System => "Follow System",
+        }
+    }
+
+    fn is_dark(self, ctx: &Context) -> bool {
+        match self {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => ctx.system_theme() != Some(Theme::Light),
+        }
+    }
+
+    /// Switches `ctx` to the light or dark visuals of this mode, if it isn't using them already.
+    pub fn apply(self, ctx: &Context) {
+        let dark = self.is_dark(ctx);
+        if ctx.style().visuals.dark_mode != dark {
+            ctx.set_visuals(if dark {
+                Visuals::dark()
+            } else {
+                Visuals::light()
+            });
+        }
+    }
+}
+
+impl MessageType {
+    /// Color of a message of this type, readable on the background of `visuals`.
+    pub fn color(self, visuals: &Visuals) -> Color32 {
+        match (self, visuals.dark_mode) {
+            (MessageType::Error, true) => Color32::LIGHT_RED,
+            (MessageType::Error, false) => Color32::DARK_RED,
+            (MessageType::Success, true) => Color32::LIGHT_GREEN,
+            (MessageType::Success, false) => Color32::DARK_GREEN,
+            (MessageType::Info, _) => visuals.text_color(),
+        }
+    }
+}
@@ -2,16 +2,16 @@ use crate::app::AppAction;
 use crate::context::AppContext;
 use crate::model::proof_log_item::ProofLogItem;
 use crate::ui::components::left_panel::add_left_panel;
+use crate::ui::components::sortable_table::{SortableColumn, SortableTable};
 use crate::ui::components::tools_subscreen_chooser_panel::add_tools_subscreen_chooser_panel;
 use crate::ui::components::top_panel::add_top_panel;
 use crate::ui::{MessageType, RootScreenType, ScreenLike};
 use dash_sdk::drive::grovedb::operations::proof::GroveDBProof;
 use dash_sdk::drive::query::PathQuery;
-use eframe::egui::{self, Context, Grid, ScrollArea, Ui};
+use eframe::egui::{self, Context, ScrollArea, Ui};
 use egui::text::LayoutJob;
 use egui::{Color32, FontId, Frame, Stroke, TextFormat, TextStyle, Vec2};
 use regex::Regex;
-use std::ops::Range;
 use std::sync::Arc;
 
 /// Screen to visualize proofs from the proof log.
@@ -20,10 +20,7 @@ pub struct ProofLogScreen {
     proof_items: Vec<ProofLogItem>,
     selected_proof_index: Option<usize>,
     show_errors_only: bool,
-    sort_column: ProofLogColumn,
-    sort_ascending: bool,
-    pagination_range: Range<u64>,
-    items_per_page: u64,
+    table: SortableTable<ProofLogItem>,
     display_mode: DisplayMode,
 }
 
@@ -34,14 +31,6 @@ fn extract_hashes_from_error(error: &str) -> Vec<String> {
         .collect()
 }
 
-#[derive(Clone, Copy)]
-enum ProofLogColumn {
-    RequestType,
-    Height,
-    Time,
-    Error,
-}
-
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum DisplayMode {
     Hex,
@@ -57,34 +46,35 @@ impl ProofLogScreen {
             proof_items: Vec::new(),
             selected_proof_index: None,
             show_errors_only: false,
-            sort_column: ProofLogColumn::Time,
-            sort_ascending: false,
-            pagination_range: 0..100,
-            items_per_page: 100,
+            table: SortableTable::new(
+                "proof_log_table",
+                vec![
+                    SortableColumn::new("Request Type", |a: &ProofLogItem, b: &ProofLogItem| {
+                        a.request_type.cmp(&b.request_type)
+                    }),
+                    SortableColumn::new("Height", |a: &ProofLogItem, b: &ProofLogItem| {
+                        a.height.cmp(&b.height)
+                    }),
+                    SortableColumn::new("Time", |a: &ProofLogItem, b: &ProofLogItem| {
+                        a.time_ms.cmp(&b.time_ms)
+                    }),
+                    SortableColumn::new("Error", |a: &ProofLogItem, b: &ProofLogItem| {
+                        a.error.cmp(&b.error)
+                    }),
+                ],
+                100,
+            )
+            .sorted_by(2, false),
             display_mode: DisplayMode::Hex,
         }
     }
 
-    /// Fetches proof log items from the database based on current settings.
+    /// Fetches proof log items from the database based on current settings. The whole log is
+    /// loaded so sorting covers all of it, the table pages through it.
     fn fetch_proof_items(&mut self) {
         let db = &self.app_context.db;
-        if let Ok(mut items) =
-            db.get_proof_log_items(self.show_errors_only, self.pagination_range.clone())
-        {
-            // Sort items based on current sorting settings
-            items.sort_by(|a, b| {
-                let ordering = match self.sort_column {
-                    ProofLogColumn::RequestType => a.request_type.cmp(&b.request_type),
-                    ProofLogColumn::Height => a.height.cmp(&b.height),
-                    ProofLogColumn::Time => a.time_ms.cmp(&b.time_ms),
-                    ProofLogColumn::Error => a.error.cmp(&b.error),
-                };
-                if self.sort_ascending {
-                    ordering
-                } else {
-                    ordering.reverse()
-                }
-            });
+        if let Ok(mut items) = db.get_proof_log_items(self.show_errors_only, 0..i64::MAX as u64) {
+            self.table.sort(&mut items);
             self.proof_items = items;
         }
     }
@@ -109,104 +99,42 @@ impl ProofLogScreen {
         ScrollArea::vertical()
             .id_salt("proof_list_scroll_area")
             .show(ui, |ui| {
-                Grid::new("proof_log_table")
-                    .num_columns(4)
-                    .striped(true)
-                    .show(ui, |ui| {
-                        // Table headers with sorting
+                let selected_proof_index = &mut self.selected_proof_index;
+                let sorted = self
+                    .table
+                    .show(ui, &mut self.proof_items, |ui, index, item| {
+                        // First column: selectable label for Request Type
                         if ui
-                            .button("Request Type")
-                            .on_hover_text("Click to sort by Request Type")
-                            .clicked()
-                        {
-                            self.sort_column = ProofLogColumn::RequestType;
-                            self.sort_ascending = !self.sort_ascending;
-                            self.fetch_proof_items();
-                        }
-                        if ui
-                            .button("Height")
-                            .on_hover_text("Click to sort by Height")
-                            .clicked()
-                        {
-                            self.sort_column = ProofLogColumn::Height;
-                            self.sort_ascending = !self.sort_ascending;
-                            self.fetch_proof_items();
-                        }
-                        if ui
-                            .button("Time")
-                            .on_hover_text("Click to sort by Time")
-                            .clicked()
-                        {
-                            self.sort_column = ProofLogColumn::Time;
-                            self.sort_ascending = !self.sort_ascending;
-                            self.fetch_proof_items();
-                        }
-                        if ui
-                            .button("Error")
-                            .on_hover_text("Click to sort by Error")
+                            .selectable_label(
+                                *selected_proof_index == Some(index),
+                                format!("{:?}", item.request_type),
+                            )
                             .clicked()
                         {
-                            self.sort_column = ProofLogColumn::Error;
-                            self.sort_ascending = !self.sort_ascending;
-                            self.fetch_proof_items();
+                            *selected_proof_index = Some(index);
                         }
-                        ui.end_row();
-
-                        // Data rows
-                        for (index, item) in self.proof_items.iter().enumerate() {
-                            // First column: selectable label for Request Type
-                            if ui
-                                .selectable_label(
-                                    self.selected_proof_index == Some(index),
-                                    format!("{:?}", item.request_type),
-                                )
-                                .clicked()
-                            {
-                                self.selected_proof_index = Some(index);
-                            }
 
-                            // Second column: Height
-                            ui.label(item.height.to_string());
+                        // Second column: Height
+                        ui.label(item.height.to_string());
 
-                            // Third column: Time
-                            ui.label(item.time_ms.to_string());
+                        // Third column: Time
+                        ui.label(item.time_ms.to_string());
 
-                            // Fourth column: Error (first 20 chars, full error on hover)
-                            let error_text =
-                                item.error.as_ref().map_or("No Error".to_string(), |e| {
-                                    if e.len() > 40 {
-                                        format!("{}...", &e[..40])
-                                    } else {
-                                        e.clone()
-                                    }
-                                });
-                            ui.label(error_text)
-                                .on_hover_text(item.error.as_deref().unwrap_or("No Error"));
-
-                            ui.end_row();
-                        }
+                        // Fourth column: Error (first 20 chars, full error on hover)
+                        let error_text = item.error.as_ref().map_or("No Error".to_string(), |e| {
+                            if e.len() > 40 {
+                                format!("{}...", &e[..40])
+                            } else {
+                                e.clone()
+                            }
+                        });
+                        ui.label(error_text)
+                            .on_hover_text(item.error.as_deref().unwrap_or("No Error"));
                     });
+                if sorted {
+                    self.selected_proof_index = None;
+                }
             });
-
-        // Pagination controls
-        ui.horizontal(|ui| {
-            if ui.button("Previous").clicked() && self.pagination_range.start >= self.items_per_page
-            {
-                self.pagination_range = (self.pagination_range.start - self.items_per_page)
-                    ..(self.pagination_range.end - self.items_per_page);
-                self.fetch_proof_items();
-            }
-            if ui.button("Next").clicked() {
-                self.pagination_range = (self.pagination_range.start + self.items_per_page)
-                    ..(self.pagination_range.end + self.items_per_page);
-                self.fetch_proof_items();
-            }
-            ui.label(format!(
-                "Showing items {} to {}",
-                self.pagination_range.start + 1,
-                self.pagination_range.end
-            ));
-        });
     }
 
     fn highlight_proof_text(proof_text: &str, hashes: &[String], font_id: FontId) -> LayoutJob {
@@ -4,7 +4,6 @@ use crate::ui::components::top_panel::add_top_panel;
 use crate::ui::{wallets, ScreenLike};
 use eframe::egui::Context;
 
-use crate::model::wallet::encryption::{encrypt_message, DASH_SECRET_MESSAGE};
 use crate::model::wallet::{ClosedKeyItem, OpenWalletSeed, Wallet, WalletSeed};
 use crate::ui::components::entropy_grid::U256EntropyGrid;
 use bip39::{Language, Mnemonic};
@@ -97,12 +96,7 @@ impl AddNewWalletScreen {
                 let (encrypted_seed, salt, nonce) =
                     ClosedKeyItem::encrypt_seed(&seed, self.password.as_str())?;
                 if self.use_password_for_app {
-                    let (encrypted_message, salt, nonce) =
-                        encrypt_message(DASH_SECRET_MESSAGE, self.password.as_str())?;
-                    self.app_context
-                        .db
-                        .update_main_password(&salt, &nonce, &encrypted_message)
-                        .map_err(|e| e.to_string())?;
+                    self.app_context.set_main_password(self.password.as_str())?;
                 }
                 (encrypted_seed, salt, nonce, true)
             };
@@ -309,6 +303,10 @@ impl AddNewWalletScreen {
 }
 
 impl ScreenLike for AddNewWalletScreen {
+    fn pop_screen(&mut self) -> AppAction {
+        AppAction::PopScreen
+    }
+
     fn ui(&mut self, ctx: &Context) -> AppAction {
         let mut action = add_top_panel(
             ctx,
@@ -787,11 +787,7 @@ impl ScreenLike for WalletsBalancesScreen {
 
             let message = self.message.clone();
             if let Some((message, message_type, timestamp)) = message {
-                let message_color = match message_type {
-                    MessageType::Error => egui::Color32::DARK_RED,
-                    MessageType::Info => egui::Color32::BLACK,
-                    MessageType::Success => egui::Color32::DARK_GREEN,
-                };
+                let message_color = message_type.color(ui.visuals());
 
                 ui.add_space(10.0);
                 ui.allocate_ui(egui::Vec2::new(ui.available_width(), 30.0), |ui| {